@@ -0,0 +1,42 @@
+use crate::codegen::{go_package_decl, write_to_file};
+use fen_parser::codegen::{name_transforms::pascal_to_snake, Context, GenCode};
+
+pub fn gen_go_client(
+    path: &str,
+    package: Option<&str>,
+    routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let package_decl = go_package_decl(path, package);
+    let api_client_text = include_str!("templates/api.go");
+
+    write_to_file(path, "api.go", &format!("{package_decl}{api_client_text}"))?;
+
+    if !shared.is_empty() {
+        write_to_file(
+            path,
+            "shared.go",
+            &format!(
+                "{package_decl}{}",
+                shared.go_client_code(&Context {
+                    override_name: None,
+                })
+            ),
+        )?;
+    }
+
+    for route in routes {
+        write_to_file(
+            path,
+            &format!("{}.go", pascal_to_snake(&route.name)),
+            &format!(
+                "{package_decl}{}",
+                route.go_client_code(&Context {
+                    override_name: None,
+                })
+            ),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"net/http"
+	"net/url"
+	"reflect"
+	"strings"
+)
+
+type Client struct {
+	Endpoint   string
+	HTTPClient *http.Client
+}
+
+func NewClient(endpoint string) *Client {
+	return &Client{
+		Endpoint:   strings.TrimSuffix(endpoint, "/"),
+		HTTPClient: http.DefaultClient,
+	}
+}
+
+// Error is returned when the server responds with a failure.
+type Error struct {
+	Message string
+	Status  int
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("%d: %s", e.Status, e.Message)
+}
+
+// RouteError is returned when the server responds with one of the errors in the route's
+// `@error` section (use errors.As to get at it).
+type RouteError[E any] struct {
+	Value E
+}
+
+func (e *RouteError[E]) Error() string {
+	return fmt.Sprintf("route error: %+v", e.Value)
+}
+
+type response struct {
+	Type    string          `json:"type"`
+	Value   json.RawMessage `json:"value"`
+	Message string          `json:"message"`
+	Status  int             `json:"status"`
+}
+
+func get[T any](ctx context.Context, c *Client, path string, sessionToken string) (T, error) {
+	return send[T, json.RawMessage](ctx, c, http.MethodGet, path, nil, nil, sessionToken)
+}
+
+func post[T any](ctx context.Context, c *Client, path string, input any, sessionToken string) (T, error) {
+	return request[T](ctx, c, http.MethodPost, path, input, sessionToken)
+}
+
+// request sends input as the body, unless it's nil.
+func request[T any](ctx context.Context, c *Client, method string, path string, input any, sessionToken string) (T, error) {
+	return requestWithError[T, json.RawMessage](ctx, c, method, path, input, sessionToken)
+}
+
+// requestWithError is request for routes with an `@error` section, whose errors are decoded as E.
+func requestWithError[T any, E any](ctx context.Context, c *Client, method string, path string, input any, sessionToken string) (T, error) {
+	return requestWithHeaders[T, E](ctx, c, method, path, input, nil, sessionToken)
+}
+
+// requestWithHeaders is requestWithError for routes with `@header` fields.
+func requestWithHeaders[T any, E any](ctx context.Context, c *Client, method string, path string, input any, headers []param, sessionToken string) (T, error) {
+	if input == nil {
+		return send[T, E](ctx, c, method, path, nil, headers, sessionToken)
+	}
+	body, err := json.Marshal(input)
+	if err != nil {
+		var zero T
+		return zero, err
+	}
+	return send[T, E](ctx, c, method, path, body, headers, sessionToken)
+}
+
+// pathSegment escapes a path parameter, so it can't be mistaken for more than one segment of the path.
+func pathSegment(value any) string {
+	return url.PathEscape(fmt.Sprint(value))
+}
+
+// param is a `@query` or `@header` field, which isn't sent at all if its value is a nil pointer.
+type param struct {
+	name  string
+	value any
+}
+
+func (p param) text() (string, bool) {
+	value := reflect.ValueOf(p.value)
+	if value.Kind() == reflect.Pointer {
+		if value.IsNil() {
+			return "", false
+		}
+		value = value.Elem()
+	}
+	return fmt.Sprint(value.Interface()), true
+}
+
+// queryString builds a query string out of the params that have a value (or an empty string, if none do).
+func queryString(params ...param) string {
+	query := url.Values{}
+	for _, p := range params {
+		if text, ok := p.text(); ok {
+			query.Set(p.name, text)
+		}
+	}
+	if len(query) == 0 {
+		return ""
+	}
+	return "?" + query.Encode()
+}
+
+func send[T any, E any](ctx context.Context, c *Client, method string, path string, body []byte, headers []param, sessionToken string) (T, error) {
+	var zero T
+
+	var reader io.Reader
+	if body != nil {
+		reader = bytes.NewReader(body)
+	}
+	req, err := http.NewRequestWithContext(ctx, method, c.Endpoint+path, reader)
+	if err != nil {
+		return zero, err
+	}
+	if body != nil {
+		req.Header.Set("Content-Type", "application/json")
+	}
+	if sessionToken != "" {
+		req.Header.Set("Authorization", "Bearer "+sessionToken)
+	}
+	for _, p := range headers {
+		if text, ok := p.text(); ok {
+			req.Header.Set(p.name, text)
+		}
+	}
+
+	res, err := c.HTTPClient.Do(req)
+	if err != nil {
+		return zero, err
+	}
+	defer res.Body.Close()
+
+	var decoded response
+	if err := json.NewDecoder(res.Body).Decode(&decoded); err != nil {
+		return zero, err
+	}
+	switch decoded.Type {
+	case "failure":
+		return zero, &Error{Message: decoded.Message, Status: decoded.Status}
+	case "error":
+		var value E
+		if err := json.Unmarshal(decoded.Value, &value); err != nil {
+			return zero, err
+		}
+		return zero, &RouteError[E]{Value: value}
+	}
+	var value T
+	if len(decoded.Value) > 0 {
+		if err := json.Unmarshal(decoded.Value, &value); err != nil {
+			return zero, err
+		}
+	}
+	return value, nil
+}
@@ -0,0 +1,42 @@
+use crate::codegen::write_to_file;
+use fen_parser::codegen::{Context, GenCode};
+
+pub fn gen_kotlin_client(
+    path: &str,
+    package: Option<&str>,
+    routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let package_decl = package.map_or_else(String::new, |package| format!("package {package}\n\n"));
+    let api_client_text = include_str!("templates/Api.kt");
+
+    write_to_file(path, "Api.kt", &format!("{package_decl}{api_client_text}"))?;
+
+    if !shared.is_empty() {
+        write_to_file(
+            path,
+            "Shared.kt",
+            &format!(
+                "{package_decl}{}",
+                shared.kotlin_client_code(&Context {
+                    override_name: None,
+                })
+            ),
+        )?;
+    }
+
+    for route in routes {
+        write_to_file(
+            path,
+            &format!("{}.kt", route.name),
+            &format!(
+                "{package_decl}{}",
+                route.kotlin_client_code(&Context {
+                    override_name: None,
+                })
+            ),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,243 @@
+import java.net.HttpURLConnection
+import java.net.URL
+import kotlinx.coroutines.Dispatchers
+import kotlinx.coroutines.withContext
+import kotlinx.serialization.KSerializer
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.descriptors.PrimitiveKind
+import kotlinx.serialization.descriptors.PrimitiveSerialDescriptor
+import kotlinx.serialization.descriptors.SerialDescriptor
+import kotlinx.serialization.descriptors.buildClassSerialDescriptor
+import kotlinx.serialization.encoding.Decoder
+import kotlinx.serialization.encoding.Encoder
+import kotlinx.serialization.json.Json
+import kotlinx.serialization.json.JsonDecoder
+import kotlinx.serialization.json.JsonEncoder
+import kotlinx.serialization.json.JsonNull
+import kotlinx.serialization.json.int
+import kotlinx.serialization.json.jsonObject
+import kotlinx.serialization.json.jsonPrimitive
+
+class APIClient(val fetcher: Fetcher) {
+    suspend fun <T> get(
+        path: String,
+        responseSerializer: KSerializer<T>,
+        sessionToken: String?,
+    ): Response<T> = decode(fetcher.get(path, sessionToken), responseSerializer)
+
+    suspend fun <T, U> post(
+        path: String,
+        body: U,
+        bodySerializer: KSerializer<U>,
+        responseSerializer: KSerializer<T>,
+        sessionToken: String?,
+    ): Response<T> {
+        val payload = json.encodeToString(bodySerializer, body)
+        return decode(fetcher.post(path, payload, sessionToken), responseSerializer)
+    }
+
+    suspend fun <T> request(
+        method: String,
+        path: String,
+        responseSerializer: KSerializer<T>,
+        sessionToken: String?,
+        headers: Map<String, Any?> = emptyMap(),
+    ): Response<T> = decode(fetcher.request(method, path, null, sessionToken, sent(headers)), responseSerializer)
+
+    suspend fun <T, U> request(
+        method: String,
+        path: String,
+        body: U,
+        bodySerializer: KSerializer<U>,
+        responseSerializer: KSerializer<T>,
+        sessionToken: String?,
+        headers: Map<String, Any?> = emptyMap(),
+    ): Response<T> {
+        val payload = json.encodeToString(bodySerializer, body)
+        return decode(fetcher.request(method, path, payload, sessionToken, sent(headers)), responseSerializer)
+    }
+
+    suspend fun <T, E> request(
+        method: String,
+        path: String,
+        responseSerializer: KSerializer<T>,
+        errorSerializer: KSerializer<E>,
+        sessionToken: String?,
+        headers: Map<String, Any?> = emptyMap(),
+    ): ResponseWithError<T, E> =
+        decode(fetcher.request(method, path, null, sessionToken, sent(headers)), responseSerializer, errorSerializer)
+
+    suspend fun <T, U, E> request(
+        method: String,
+        path: String,
+        body: U,
+        bodySerializer: KSerializer<U>,
+        responseSerializer: KSerializer<T>,
+        errorSerializer: KSerializer<E>,
+        sessionToken: String?,
+        headers: Map<String, Any?> = emptyMap(),
+    ): ResponseWithError<T, E> {
+        val payload = json.encodeToString(bodySerializer, body)
+        return decode(fetcher.request(method, path, payload, sessionToken, sent(headers)), responseSerializer, errorSerializer)
+    }
+
+    /** The headers that have a value, as text. */
+    private fun sent(headers: Map<String, Any?>): Map<String, String> =
+        headers.mapNotNull { (name, value) -> value?.let { name to it.toString() } }.toMap()
+
+    private fun <T> decode(body: String, responseSerializer: KSerializer<T>): Response<T> {
+        val response = json.parseToJsonElement(body).jsonObject
+        return if (response["type"]?.jsonPrimitive?.content == "success") {
+            Response.Success(json.decodeFromJsonElement(responseSerializer, response["value"] ?: JsonNull))
+        } else {
+            Response.Failure(
+                message = response.getValue("message").jsonPrimitive.content,
+                status = response.getValue("status").jsonPrimitive.int,
+            )
+        }
+    }
+
+    private fun <T, E> decode(
+        body: String,
+        responseSerializer: KSerializer<T>,
+        errorSerializer: KSerializer<E>,
+    ): ResponseWithError<T, E> {
+        val response = json.parseToJsonElement(body).jsonObject
+        return when (response["type"]?.jsonPrimitive?.content) {
+            "success" -> ResponseWithError.Success(
+                json.decodeFromJsonElement(responseSerializer, response["value"] ?: JsonNull)
+            )
+            "error" -> ResponseWithError.Failure(
+                json.decodeFromJsonElement(errorSerializer, response["value"] ?: JsonNull)
+            )
+            else -> ResponseWithError.GenericFailure(
+                message = response.getValue("message").jsonPrimitive.content,
+                status = response.getValue("status").jsonPrimitive.int,
+            )
+        }
+    }
+
+    companion object {
+        val json = Json {
+            ignoreUnknownKeys = true
+            encodeDefaults = true
+        }
+
+        /** Escapes a path parameter, so it can't be mistaken for more than one segment of the path. */
+        fun pathSegment(value: Any): String =
+            java.net.URLEncoder.encode(value.toString(), Charsets.UTF_8).replace("+", "%20")
+
+        /** Builds a query string out of the parameters that have a value (or an empty string, if none do). */
+        fun queryString(vararg params: Pair<String, Any?>): String {
+            val pairs = params.mapNotNull { (name, value) -> value?.let { "$name=${pathSegment(it)}" } }
+            return if (pairs.isEmpty()) "" else "?" + pairs.joinToString("&")
+        }
+    }
+}
+
+interface Fetcher {
+    suspend fun request(
+        method: String,
+        path: String,
+        body: String?,
+        sessionToken: String?,
+        headers: Map<String, String> = emptyMap(),
+    ): String
+
+    suspend fun get(path: String, sessionToken: String?): String =
+        request("GET", path, null, sessionToken)
+
+    suspend fun post(path: String, body: String, sessionToken: String?): String =
+        request("POST", path, body, sessionToken)
+}
+
+/** Note that `HttpURLConnection` can't send PATCH requests, so PATCH routes need a [Fetcher] of your own. */
+class LiveFetcher(private val endpoint: String) : Fetcher {
+    override suspend fun request(
+        method: String,
+        path: String,
+        body: String?,
+        sessionToken: String?,
+        headers: Map<String, String>,
+    ): String = withContext(Dispatchers.IO) {
+        val connection = URL(endpoint + path).openConnection() as HttpURLConnection
+        try {
+            connection.requestMethod = method
+            connection.setRequestProperty("Content-Type", "application/json")
+            if (sessionToken != null) {
+                connection.setRequestProperty("Authorization", "Bearer $sessionToken")
+            }
+            for ((name, value) in headers) {
+                connection.setRequestProperty(name, value)
+            }
+            if (body != null) {
+                connection.doOutput = true
+                connection.outputStream.use { it.write(body.toByteArray()) }
+            }
+            val stream = if (connection.responseCode < 400) connection.inputStream else connection.errorStream
+            stream.bufferedReader().use { it.readText() }
+        } finally {
+            connection.disconnect()
+        }
+    }
+}
+
+sealed class Response<out T> {
+    data class Success<out T>(val value: T) : Response<T>()
+    data class Failure(val message: String, val status: Int) : Response<Nothing>()
+}
+
+/** What a route with an `@error` section responds with. [GenericFailure] covers failures that aren't the route's own, like a 401. */
+sealed class ResponseWithError<out T, out E> {
+    data class Success<out T>(val value: T) : ResponseWithError<T, Nothing>()
+    data class Failure<out E>(val error: E) : ResponseWithError<Nothing, E>()
+    data class GenericFailure(val message: String, val status: Int) : ResponseWithError<Nothing, Nothing>()
+}
+
+object NoDataSerializer : KSerializer<Unit> {
+    override val descriptor: SerialDescriptor = buildClassSerialDescriptor("NoData")
+
+    override fun serialize(encoder: Encoder, value: Unit) {
+        (encoder as JsonEncoder).encodeJsonElement(JsonNull)
+    }
+
+    override fun deserialize(decoder: Decoder) {
+        (decoder as JsonDecoder).decodeJsonElement()
+    }
+}
+
+object UUIDSerializer : KSerializer<java.util.UUID> {
+    override val descriptor: SerialDescriptor = PrimitiveSerialDescriptor("UUID", PrimitiveKind.STRING)
+
+    override fun serialize(encoder: Encoder, value: java.util.UUID) {
+        encoder.encodeString(value.toString())
+    }
+
+    override fun deserialize(decoder: Decoder): java.util.UUID = java.util.UUID.fromString(decoder.decodeString())
+}
+
+typealias UUID = @Serializable(with = UUIDSerializer::class) java.util.UUID
+
+object DecimalSerializer : KSerializer<java.math.BigDecimal> {
+    override val descriptor: SerialDescriptor = PrimitiveSerialDescriptor("Decimal", PrimitiveKind.STRING)
+
+    override fun serialize(encoder: Encoder, value: java.math.BigDecimal) {
+        encoder.encodeString(value.toPlainString())
+    }
+
+    override fun deserialize(decoder: Decoder): java.math.BigDecimal = java.math.BigDecimal(decoder.decodeString())
+}
+
+typealias Decimal = @Serializable(with = DecimalSerializer::class) java.math.BigDecimal
+
+object BytesSerializer : KSerializer<ByteArray> {
+    override val descriptor: SerialDescriptor = PrimitiveSerialDescriptor("Bytes", PrimitiveKind.STRING)
+
+    override fun serialize(encoder: Encoder, value: ByteArray) {
+        encoder.encodeString(java.util.Base64.getEncoder().encodeToString(value))
+    }
+
+    override fun deserialize(decoder: Decoder): ByteArray = java.util.Base64.getDecoder().decode(decoder.decodeString())
+}
+
+typealias Bytes = @Serializable(with = BytesSerializer::class) ByteArray
@@ -1 +1,6 @@
+pub mod go;
+pub mod kotlin;
+pub mod python;
+pub mod rust;
 pub mod swift;
+pub mod typescript;
@@ -0,0 +1,49 @@
+use crate::codegen::write_to_file;
+use fen_parser::codegen::{name_transforms::pascal_to_snake, Context, GenCode};
+
+pub fn gen_python_client(
+    path: &str,
+    routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let api_client_text = include_str!("templates/api.py");
+
+    write_to_file(path, "api.py", api_client_text)?;
+
+    if !shared.is_empty() {
+        write_to_file(
+            path,
+            "shared.py",
+            &shared.python_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    let exports = routes
+        .iter()
+        .map(|route| {
+            let name = pascal_to_snake(&route.name);
+            format!("from .{name} import {name}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    write_to_file(
+        path,
+        "__init__.py",
+        &format!("from .api import APIClient, FenError, Fetcher, LiveFetcher\n{exports}\n"),
+    )?;
+
+    for route in routes {
+        write_to_file(
+            path,
+            &format!("{}.py", pascal_to_snake(&route.name)),
+            &route.python_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    Ok(())
+}
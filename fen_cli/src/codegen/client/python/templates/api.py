@@ -0,0 +1,184 @@
+from __future__ import annotations
+
+import base64
+import json
+import urllib.error
+import urllib.parse
+import urllib.request
+from typing import Annotated, Any, Optional, Protocol, TypeVar
+
+from pydantic import BaseModel, BeforeValidator, ConfigDict, PlainSerializer, TypeAdapter
+from pydantic.alias_generators import to_camel
+
+T = TypeVar("T")
+
+
+class FenModel(BaseModel):
+    """Base class for all generated types, encoded with camelCase keys."""
+
+    model_config = ConfigDict(alias_generator=to_camel, populate_by_name=True)
+
+
+def _decode_base64(value: Any) -> Any:
+    return base64.b64decode(value, validate=True) if isinstance(value, str) else value
+
+
+# binary data, sent as a base64 string
+Bytes = Annotated[
+    bytes,
+    BeforeValidator(_decode_base64),
+    PlainSerializer(lambda value: base64.b64encode(value).decode("ascii"), return_type=str, when_used="json"),
+]
+
+
+class FenError(Exception):
+    """Raised when the server responds with a failure."""
+
+    def __init__(self, message: str, status: int) -> None:
+        super().__init__(f"{status}: {message}")
+        self.message = message
+        self.status = status
+
+
+class RouteError(Exception):
+    """Raised when the server responds with one of the errors in the route's `@error` section."""
+
+    def __init__(self, value: Any) -> None:
+        super().__init__(value)
+        self.value = value
+
+
+def _text(value: Any) -> str:
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    return str(value)
+
+
+def path_segment(value: Any) -> str:
+    """Escapes a path parameter, so it can't be mistaken for more than one segment of the path."""
+    return urllib.parse.quote(_text(value), safe="")
+
+
+def query_string(params: dict[str, Any]) -> str:
+    """Builds a query string out of the parameters that aren't `None` (or an empty string, if they all are)."""
+    pairs = [f"{name}={path_segment(value)}" for name, value in params.items() if value is not None]
+    return "?" + "&".join(pairs) if pairs else ""
+
+
+class Fetcher(Protocol):
+    def get(self, path: str, session_token: Optional[str]) -> Any: ...
+
+    def post(self, path: str, body: Any, session_token: Optional[str]) -> Any: ...
+
+    def request(
+        self,
+        method: str,
+        path: str,
+        body: Any,
+        session_token: Optional[str],
+        headers: Optional[dict[str, str]] = None,
+    ) -> Any:
+        """Sends any other kind of request (`body` is `None` when there isn't one)."""
+        ...
+
+
+class LiveFetcher:
+    def __init__(self, endpoint: str) -> None:
+        self.endpoint = endpoint.rstrip("/")
+
+    def get(self, path: str, session_token: Optional[str]) -> Any:
+        return self.request("GET", path, None, session_token)
+
+    def post(self, path: str, body: Any, session_token: Optional[str]) -> Any:
+        return self.request("POST", path, body, session_token)
+
+    def request(
+        self,
+        method: str,
+        path: str,
+        body: Any,
+        session_token: Optional[str],
+        headers: Optional[dict[str, str]] = None,
+    ) -> Any:
+        if body is None:
+            return self._send(urllib.request.Request(self.endpoint + path, method=method), session_token, headers)
+        request = urllib.request.Request(
+            self.endpoint + path,
+            data=json.dumps(body).encode("utf-8"),
+            method=method,
+        )
+        request.add_header("Content-Type", "application/json")
+        return self._send(request, session_token, headers)
+
+    def _send(
+        self,
+        request: urllib.request.Request,
+        session_token: Optional[str],
+        headers: Optional[dict[str, str]],
+    ) -> Any:
+        if session_token is not None:
+            request.add_header("Authorization", f"Bearer {session_token}")
+        for name, value in (headers or {}).items():
+            request.add_header(name, value)
+        try:
+            with urllib.request.urlopen(request) as response:
+                return json.loads(response.read())
+        except urllib.error.HTTPError as error:
+            # failures are still encoded as a `Response`
+            return json.loads(error.read())
+
+
+class APIClient:
+    def __init__(self, fetcher: Fetcher) -> None:
+        self.fetcher = fetcher
+
+    def get(
+        self,
+        path: str,
+        response_type: Any,
+        session_token: Optional[str],
+        error_type: Any = None,
+    ) -> Any:
+        return self._unwrap(self.fetcher.get(path, session_token), response_type, error_type)
+
+    def post(
+        self,
+        path: str,
+        body: T,
+        body_type: Any,
+        response_type: Any,
+        session_token: Optional[str],
+        error_type: Any = None,
+    ) -> Any:
+        payload = TypeAdapter(body_type).dump_python(body, mode="json", by_alias=True)
+        return self._unwrap(self.fetcher.post(path, payload, session_token), response_type, error_type)
+
+    def request(
+        self,
+        method: str,
+        path: str,
+        body: Optional[T],
+        body_type: Any,
+        response_type: Any,
+        session_token: Optional[str],
+        error_type: Any = None,
+        headers: Optional[dict[str, Any]] = None,
+    ) -> Any:
+        payload = None if body is None else TypeAdapter(body_type).dump_python(body, mode="json", by_alias=True)
+        if headers is None:
+            response = self.fetcher.request(method, path, payload, session_token)
+        else:
+            # headers that are `None` aren't sent at all
+            sent = {name: _text(value) for name, value in headers.items() if value is not None}
+            response = self.fetcher.request(method, path, payload, session_token, sent)
+        return self._unwrap(response, response_type, error_type)
+
+    @staticmethod
+    def _unwrap(response: Any, response_type: Any, error_type: Any) -> Any:
+        if response["type"] == "failure":
+            raise FenError(response["message"], response["status"])
+        if response["type"] == "error":
+            raise RouteError(TypeAdapter(error_type).validate_python(response["value"]))
+        if response_type is None:
+            return None
+        return TypeAdapter(response_type).validate_python(response["value"])
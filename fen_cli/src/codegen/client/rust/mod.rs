@@ -0,0 +1,40 @@
+use crate::codegen::write_to_file;
+use fen_parser::codegen::{name_transforms::rust_module_name, Context, GenCode};
+
+pub fn gen_rust_client(
+    path: &str,
+    routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let api_client_text = include_str!("templates/client.rs");
+
+    let mut mods = routes
+        .iter()
+        .map(|route| format!("pub mod {};", rust_module_name(&route.name)))
+        .collect::<Vec<String>>();
+    if !shared.is_empty() {
+        mods.push("pub mod shared;".to_string());
+        write_to_file(
+            path,
+            "shared.rs",
+            &shared.rust_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+    let mods = mods.join("\n");
+
+    write_to_file(path, "mod.rs", &format!("{mods}\n\n{api_client_text}"))?;
+
+    for route in routes {
+        write_to_file(
+            path,
+            &format!("{}.rs", rust_module_name(&route.name)),
+            &route.rust_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    Ok(())
+}
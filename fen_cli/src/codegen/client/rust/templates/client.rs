@@ -0,0 +1,174 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    endpoint: String,
+    http: reqwest::Client,
+}
+
+impl ApiClient {
+    pub fn new(endpoint: &str) -> Self {
+        Self::with_http_client(endpoint, reqwest::Client::new())
+    }
+
+    pub fn with_http_client(endpoint: &str, http: reqwest::Client) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            http,
+        }
+    }
+
+    async fn get<T: DeserializeOwned, E: DeserializeOwned>(
+        &self,
+        path: &str,
+        session_token: Option<&str>,
+    ) -> Result<Response<T, E>, reqwest::Error> {
+        self.request::<T, E, ()>(reqwest::Method::GET, path, None, session_token)
+            .await
+    }
+
+    async fn post<T: DeserializeOwned, E: DeserializeOwned, U: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        body: &U,
+        session_token: Option<&str>,
+    ) -> Result<Response<T, E>, reqwest::Error> {
+        self.request(reqwest::Method::POST, path, Some(body), session_token)
+            .await
+    }
+
+    async fn request<T: DeserializeOwned, E: DeserializeOwned, U: Serialize + ?Sized>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&U>,
+        session_token: Option<&str>,
+    ) -> Result<Response<T, E>, reqwest::Error> {
+        self.send(method, path, body, &[], session_token).await
+    }
+
+    /// Like `request`, but also sends the `headers` that have a value.
+    async fn send<T: DeserializeOwned, E: DeserializeOwned, U: Serialize + ?Sized>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&U>,
+        headers: &[(&str, Option<String>)],
+        session_token: Option<&str>,
+    ) -> Result<Response<T, E>, reqwest::Error> {
+        let mut request = self.http.request(method, format!("{}{path}", self.endpoint));
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        for (name, value) in headers {
+            if let Some(value) = value {
+                request = request.header(*name, value);
+            }
+        }
+        if let Some(session_token) = session_token {
+            request = request.bearer_auth(session_token);
+        }
+
+        request.send().await?.json().await
+    }
+}
+
+/// Escapes a path parameter, so it can't be mistaken for more than one segment of the path.
+fn path_segment(value: &impl std::fmt::Display) -> String {
+    value
+        .to_string()
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Builds a query string out of the parameters that have a value (or an empty string, if none
+/// do).
+fn query_string(params: &[(&str, Option<String>)]) -> String {
+    let pairs = params
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{name}={}", path_segment(value)))
+        })
+        .collect::<Vec<String>>();
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("?{}", pairs.join("&"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Response<T, E = NoError> {
+    Success(SuccessResponse<T>),
+    Error(ErrorResponse<E>),
+    Failure(FailureResponse),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct SuccessResponse<T> {
+    pub value: T,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ErrorResponse<E> {
+    pub value: E,
+}
+
+/// The error type of routes without an `@error` section, which can't be constructed.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum NoError {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct FailureResponse {
+    pub message: String,
+    pub status: isize,
+}
+
+/// Serde helpers for `Bytes` fields, which are sent as base64 strings.
+pub mod fen_base64 {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        STANDARD
+            .decode(String::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+        #[allow(clippy::ref_option)]
+        pub fn serialize<S: Serializer>(
+            bytes: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => serializer.serialize_some(&STANDARD.encode(bytes)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|encoded| STANDARD.decode(encoded).map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+}
@@ -6,11 +6,22 @@ pub fn gen_swift_client(
     dev_endpoint: &str,
     prod_endpoint: &str,
     routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
 ) -> Result<(), std::io::Error> {
     let api_client_text = include_str!("templates/Api.swift");
 
     write_to_file(path, "Api.swift", api_client_text)?;
 
+    if !shared.is_empty() {
+        write_to_file(
+            path,
+            "Shared.swift",
+            &shared.swift_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
     for route in routes {
         write_to_file(
             path,
@@ -0,0 +1,34 @@
+use crate::codegen::write_to_file;
+use fen_parser::codegen::{name_transforms::pascal_to_camel, Context, GenCode};
+
+pub fn gen_typescript_client(
+    path: &str,
+    routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let api_client_text = include_str!("templates/api.ts");
+
+    write_to_file(path, "api.ts", api_client_text)?;
+
+    if !shared.is_empty() {
+        write_to_file(
+            path,
+            "shared.ts",
+            &shared.typescript_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    for route in routes {
+        write_to_file(
+            path,
+            &format!("{}.ts", pascal_to_camel(&route.name)),
+            &route.typescript_client_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    Ok(())
+}
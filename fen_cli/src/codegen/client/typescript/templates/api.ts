@@ -0,0 +1,94 @@
+/** `E` is the route's `@error` type, for routes that have one. */
+export type Response<T, E = never> =
+  | { type: "success"; value: T }
+  | ([E] extends [never] ? never : { type: "error"; value: E })
+  | { type: "failure"; message: string; status: number };
+
+export type Decoder<T> = (json: any) => T;
+
+/** `@query` and `@header` values, which are left out when they're `null`. */
+export type Params = Record<string, string | number | boolean | null>;
+
+export interface Fetcher {
+  get<T>(path: string, sessionToken: string | null, decode: Decoder<T>): Promise<Response<T>>;
+  post<T, U>(
+    path: string,
+    body: U,
+    sessionToken: string | null,
+    decode: Decoder<T>,
+  ): Promise<Response<T>>;
+  request<T, U, E = never>(
+    method: string,
+    path: string,
+    body: U | undefined,
+    sessionToken: string | null,
+    decode: Decoder<T>,
+    decodeError?: Decoder<E>,
+    headers?: Params,
+  ): Promise<Response<T, E>>;
+}
+
+/** Escapes a path parameter, so it can't be mistaken for more than one segment of the path. */
+export function pathSegment(value: string | number | boolean): string {
+  return encodeURIComponent(String(value));
+}
+
+/** Builds a query string out of the parameters that have a value (or `""`, if none do). */
+export function queryString(params: Params): string {
+  const pairs = Object.entries(params)
+    .filter(([, value]) => value !== null)
+    .map(([name, value]) => `${name}=${encodeURIComponent(String(value))}`);
+  return pairs.length === 0 ? "" : `?${pairs.join("&")}`;
+}
+
+export class LiveFetcher implements Fetcher {
+  constructor(public endpoint: string) {}
+
+  get<T>(path: string, sessionToken: string | null, decode: Decoder<T>): Promise<Response<T>> {
+    return this.request("GET", path, undefined, sessionToken, decode);
+  }
+
+  post<T, U>(
+    path: string,
+    body: U,
+    sessionToken: string | null,
+    decode: Decoder<T>,
+  ): Promise<Response<T>> {
+    return this.request("POST", path, body, sessionToken, decode);
+  }
+
+  async request<T, U, E = never>(
+    method: string,
+    path: string,
+    body: U | undefined,
+    sessionToken: string | null,
+    decode: Decoder<T>,
+    decodeError: Decoder<E> = (json) => json,
+    headers: Params = {},
+  ): Promise<Response<T, E>> {
+    const requestHeaders: Record<string, string> = { "Content-Type": "application/json" };
+    for (const [name, value] of Object.entries(headers)) {
+      if (value !== null) {
+        requestHeaders[name] = String(value);
+      }
+    }
+    if (sessionToken !== null) {
+      requestHeaders["Authorization"] = `Bearer ${sessionToken}`;
+    }
+
+    const response = await fetch(this.endpoint + path, {
+      method,
+      headers: requestHeaders,
+      body: body === undefined ? undefined : JSON.stringify(body),
+    });
+    const json = await response.json();
+
+    if (json.type === "success") {
+      return { type: "success", value: decode(json.value) };
+    } else if (json.type === "error") {
+      return { type: "error", value: decodeError(json.value) } as Response<T, E>;
+    } else {
+      return { type: "failure", message: json.message, status: json.status };
+    }
+  }
+}
@@ -0,0 +1,14 @@
+use fen_parser::codegen::docs::{docs_pages, DocsFormat};
+
+pub fn gen_docs(
+    path: &str,
+    format: DocsFormat,
+    routes: &[&fen_parser::ast::FileNode],
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    for (file_name, text) in docs_pages(routes, shared, format) {
+        std::fs::write(format!("{path}/{file_name}"), text)?;
+    }
+
+    Ok(())
+}
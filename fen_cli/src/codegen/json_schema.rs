@@ -0,0 +1,23 @@
+use fen_parser::codegen::{
+    json_schema::{input_schema, output_schema},
+    name_transforms::pascal_to_kebab,
+};
+
+pub fn gen_json_schemas(
+    path: &str,
+    routes: &[&fen_parser::ast::FileNode],
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    // JSON has no comments, so these skip the generated file header
+    for route in routes {
+        let name = pascal_to_kebab(&route.name);
+        if let Some(schema) = input_schema(route, shared) {
+            let text = serde_json::to_string_pretty(&schema)?;
+            std::fs::write(format!("{path}/{name}.input.schema.json"), text + "\n")?;
+        }
+        let text = serde_json::to_string_pretty(&output_schema(route, shared))?;
+        std::fs::write(format!("{path}/{name}.output.schema.json"), text + "\n")?;
+    }
+
+    Ok(())
+}
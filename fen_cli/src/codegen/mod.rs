@@ -2,15 +2,36 @@ use std::fs::File;
 use std::io::prelude::*;
 
 pub mod client;
+pub mod docs;
+pub mod json_schema;
+pub mod openapi;
 pub mod server;
 
 pub fn write_to_file(dir: &str, file_name: &str, text: &str) -> Result<(), std::io::Error> {
     let mut file = File::create(format!("{dir}/{file_name}"))?;
-    let content = format!("{}\n\n{}", comment(), text);
+    let content = format!("{}\n\n{}", comment(file_name), text);
     file.write_all(content.as_bytes())?;
     Ok(())
 }
 
-fn comment() -> String {
-    format!("// Created by Fen v{} at {} on {}\n// Do not manually modify this file as it is automatically generated", crate::VERSION, chrono::Local::now().format("%H:%M:%S"), chrono::Local::now().format("%Y-%m-%d"))
+/// Go files must declare their package, which defaults to the name of the output directory.
+pub fn go_package_decl(dir: &str, package: Option<&str>) -> String {
+    let package = package.map_or_else(
+        || {
+            std::path::Path::new(dir).file_name().map_or_else(
+                || "api".to_string(),
+                |name| name.to_string_lossy().replace('-', "_"),
+            )
+        },
+        str::to_string,
+    );
+    format!("package {package}\n\n")
+}
+
+fn comment(file_name: &str) -> String {
+    let is_python = std::path::Path::new(file_name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("py"));
+    let prefix = if is_python { "#" } else { "//" };
+    format!("{prefix} Created by Fen v{} at {} on {}\n{prefix} Do not manually modify this file as it is automatically generated", crate::VERSION, chrono::Local::now().format("%H:%M:%S"), chrono::Local::now().format("%Y-%m-%d"))
 }
@@ -0,0 +1,17 @@
+use fen_parser::codegen::openapi::openapi_document;
+
+pub fn gen_openapi(
+    path: &str,
+    title: &str,
+    version: &str,
+    routes: &[&fen_parser::ast::FileNode],
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let document = openapi_document(routes, shared, title, version);
+
+    // JSON has no comments, so this skips the generated file header
+    let text = serde_json::to_string_pretty(&document)?;
+    std::fs::write(format!("{path}/openapi.json"), text + "\n")?;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+import (
+	"encoding/json"
+	"net/http"
+)
+
+type Response[T any] struct {
+	value    T
+	failed   bool
+	message  string
+	status   int
+	hasError bool
+	err      any
+}
+
+func Success[T any](value T) Response[T] {
+	return Response[T]{value: value}
+}
+
+// RouteError responds with one of the errors in the route's `@error` section.
+func RouteError[T any](err any) Response[T] {
+	return Response[T]{hasError: true, err: err}
+}
+
+func Failure[T any](status int, message string) Response[T] {
+	return Response[T]{failed: true, message: message, status: status}
+}
+
+func (r Response[T]) MarshalJSON() ([]byte, error) {
+	if r.failed {
+		return json.Marshal(struct {
+			Type    string `json:"type"`
+			Message string `json:"message"`
+			Status  int    `json:"status"`
+		}{"failure", r.message, r.status})
+	}
+	if r.hasError {
+		return json.Marshal(struct {
+			Type  string `json:"type"`
+			Value any    `json:"value"`
+		}{"error", r.err})
+	}
+	return json.Marshal(struct {
+		Type  string `json:"type"`
+		Value T      `json:"value"`
+	}{"success", r.value})
+}
+
+// Write encodes the response as JSON, using the failure status (if any) as the HTTP status.
+func (r Response[T]) Write(w http.ResponseWriter) error {
+	w.Header().Set("Content-Type", "application/json")
+	if r.failed {
+		w.WriteHeader(r.status)
+	}
+	return json.NewEncoder(w).Encode(r)
+}
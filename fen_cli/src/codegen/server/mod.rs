@@ -1 +1,3 @@
+pub mod go;
 pub mod rust;
+pub mod swift;
@@ -1,33 +1,64 @@
 use crate::codegen::write_to_file;
-use fen_parser::codegen::{name_transforms::pascal_to_snake, Context, GenCode};
+use fen_parser::codegen::{
+    name_transforms::rust_module_name, rust_router::rust_router_code, Context, GenCode,
+};
 
 pub fn gen_rust_server(
     path: &str,
     routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
 ) -> Result<(), std::io::Error> {
-    let response_types_text = include_str!("templates/response.rs");
+    let mut response_types_text = include_str!("templates/response.rs").to_string();
+    // only needed (along with the `futures` crate) when a route streams its output
+    if routes.iter().any(|route| route.stream) {
+        response_types_text.push_str(include_str!("templates/stream.rs"));
+    }
 
-    let mods = routes
+    let mut mods = routes
         .iter()
+        .map(|route| format!("pub mod {name};", name = rust_module_name(&route.name)))
+        .collect::<Vec<String>>();
+    let shared_code = shared.rust_server_code(&Context {
+        override_name: None,
+    });
+    if !shared.is_empty() {
+        mods.push("pub mod shared;".to_string());
+        write_to_file(path, "shared.rs", &shared_code)?;
+    }
+    let mods = mods.join("\n");
+
+    write_to_file(path, "handlers.rs", &rust_router_code(&routes))?;
+
+    let route_files = routes
+        .into_iter()
         .map(|route| {
-            format!(
-                "pub mod {name};",
-                name = pascal_to_snake(&route.name).to_lowercase()
+            (
+                format!("{}.rs", rust_module_name(&route.name)),
+                route.rust_server_code(&Context {
+                    override_name: None,
+                }),
             )
         })
-        .collect::<Vec<String>>()
-        .join("\n");
-
-    write_to_file(path, "mod.rs", &format!("{mods}\n\n{response_types_text}"))?;
-
-    for route in routes {
-        write_to_file(
-            path,
-            &format!("{}.rs", pascal_to_snake(&route.name)),
-            &route.rust_server_code(&Context {
-                override_name: None,
-            }),
-        )?;
+        .collect::<Vec<(String, String)>>();
+
+    // only needed (along with the `base64` crate) when a type has a `Bytes` field
+    if route_files
+        .iter()
+        .map(|(_, code)| code)
+        .chain([&shared_code])
+        .any(|code| code.contains("super::fen_base64"))
+    {
+        response_types_text.push_str(include_str!("templates/base64.rs"));
+    }
+
+    write_to_file(
+        path,
+        "mod.rs",
+        &format!("{mods}\npub mod handlers;\n\npub use handlers::{{router, Handlers}};\n\n{response_types_text}"),
+    )?;
+
+    for (file_name, code) in route_files {
+        write_to_file(path, &file_name, &code)?;
     }
 
     Ok(())
@@ -2,22 +2,38 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
-pub enum Response<T> {
+pub enum Response<T, E = NoError> {
     Success(SuccessResponse<T>),
+    Error(ErrorResponse<E>),
     Failure(FailureResponse),
 }
 
-impl<T> Response<T> {
+impl<T, E> Response<T, E> {
     pub const fn success(value: T) -> Self {
         Self::Success(SuccessResponse { value })
     }
 
+    /// One of the errors the route declares in its `@error` section.
+    pub const fn error(value: E) -> Self {
+        Self::Error(ErrorResponse { value })
+    }
+
     pub fn failure(status: isize, message: &str) -> Self {
         Self::Failure(FailureResponse {
             status,
             message: message.to_string(),
         })
     }
+
+    /// A 422 failure listing every constraint an input broke.
+    pub fn invalid(errors: &[ValidationError]) -> Self {
+        let message = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join("; ");
+        Self::failure(422, &message)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -25,12 +41,44 @@ pub struct SuccessResponse<T> {
     pub value: T,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct ErrorResponse<E> {
+    pub value: E,
+}
+
+/// The error type of routes without an `@error` section, which can't be constructed.
+#[derive(Serialize, Deserialize)]
+pub enum NoError {}
+
 #[derive(Serialize, Deserialize)]
 pub struct FailureResponse {
     pub message: String,
     pub status: isize,
 }
 
+/// A field of a route's input that broke one of its constraints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The field's name, as it's sent (in camelCase).
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.field, self.message)
+    }
+}
+
 pub fn fen_path(path: &str) -> String {
     format!("/_fen_{path}")
 }
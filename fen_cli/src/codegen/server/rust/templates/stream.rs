@@ -0,0 +1,81 @@
+
+/// One line of a streaming route's response, which is sent as newline-delimited JSON.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StreamEvent<T> {
+    Event(SuccessResponse<T>),
+    /// Ends the stream early (so it's the last line).
+    Failure(FailureResponse),
+    /// Sent after the last event, so clients can tell a finished stream from a dropped
+    /// connection.
+    End,
+}
+
+/// What a streaming route's handler responds with: each of its events as they're ready, then an
+/// `end` line (or a failure, if one comes first).
+pub struct EventStream<T> {
+    events: futures::stream::BoxStream<'static, Result<T, FailureResponse>>,
+}
+
+impl<T: Serialize + Send + 'static> EventStream<T> {
+    pub fn new(events: impl futures::Stream<Item = T> + Send + 'static) -> Self {
+        Self::try_new(futures::StreamExt::map(events, Ok))
+    }
+
+    /// Like `new`, but the first `Err` is sent as a failure, which ends the stream.
+    pub fn try_new(
+        events: impl futures::Stream<Item = Result<T, FailureResponse>> + Send + 'static,
+    ) -> Self {
+        Self {
+            events: futures::StreamExt::boxed(events),
+        }
+    }
+
+    /// A stream that fails before sending any events.
+    pub fn failure(status: isize, message: &str) -> Self {
+        Self::try_new(futures::stream::iter([Err(FailureResponse {
+            status,
+            message: message.to_string(),
+        })]))
+    }
+
+    /// A 422 failure listing every constraint an input broke.
+    pub fn invalid(errors: &[ValidationError]) -> Self {
+        let message = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join("; ");
+        Self::failure(422, &message)
+    }
+}
+
+impl<T: Serialize + Send + 'static> axum::response::IntoResponse for EventStream<T> {
+    fn into_response(self) -> axum::response::Response {
+        let lines = futures::stream::unfold(Some(self.events), |events| async move {
+            let mut events = events?;
+            let (line, events) = match futures::StreamExt::next(&mut events).await {
+                Some(Ok(value)) => (StreamEvent::Event(SuccessResponse { value }), Some(events)),
+                Some(Err(failure)) => (StreamEvent::Failure(failure), None),
+                None => (StreamEvent::End, None),
+            };
+            let line = serde_json::to_string(&line).unwrap_or_else(|error| {
+                let failure = StreamEvent::<()>::Failure(FailureResponse {
+                    status: 500,
+                    message: error.to_string(),
+                });
+                serde_json::to_string(&failure).unwrap_or_default()
+            });
+            Some((
+                Ok::<String, std::convert::Infallible>(line + "\n"),
+                events,
+            ))
+        });
+
+        (
+            [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+            axum::body::Body::from_stream(lines),
+        )
+            .into_response()
+    }
+}
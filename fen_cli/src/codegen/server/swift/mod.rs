@@ -0,0 +1,34 @@
+use crate::codegen::write_to_file;
+use fen_parser::codegen::{Context, GenCode};
+
+pub fn gen_swift_server(
+    path: &str,
+    routes: Vec<&fen_parser::ast::FileNode>,
+    shared: &fen_parser::ast::SharedTypes,
+) -> Result<(), std::io::Error> {
+    let response_types_text = include_str!("templates/Response.swift");
+
+    write_to_file(path, "Response.swift", response_types_text)?;
+
+    if !shared.is_empty() {
+        write_to_file(
+            path,
+            "Shared.swift",
+            &shared.swift_server_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    for route in routes {
+        write_to_file(
+            path,
+            &format!("{}.swift", route.name),
+            &route.swift_server_code(&Context {
+                override_name: None,
+            }),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+import Vapor
+
+enum Response<T: Codable & Sendable>: Codable, Sendable, AsyncResponseEncodable {
+  case success(T)
+  case failure(message: String, status: Int)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+    case message
+    case status
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(String.self, forKey: .type)
+
+    if type == "success" {
+      self = .success(try container.decode(T.self, forKey: .value))
+    } else {
+      self = .failure(
+        message: try container.decode(String.self, forKey: .message),
+        status: try container.decode(Int.self, forKey: .status)
+      )
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .success(let value):
+      try container.encode("success", forKey: .type)
+      try container.encode(value, forKey: .value)
+    case .failure(let message, let status):
+      try container.encode("failure", forKey: .type)
+      try container.encode(message, forKey: .message)
+      try container.encode(status, forKey: .status)
+    }
+  }
+
+  func encodeResponse(for request: Request) async throws -> Vapor.Response {
+    let response = Vapor.Response(status: .ok)
+    try response.content.encode(self, using: FenCoding.encoder)
+    return response
+  }
+}
+
+/// The response of a route with an `@error` type, which it can fail with on top of the failures
+/// any route can have (like a 401).
+enum ResponseWithError<T: Codable & Sendable, RouteError: Codable & Sendable>: Codable, Sendable,
+  AsyncResponseEncodable
+{
+  case success(T)
+  case failure(RouteError)
+  case genericFailure(message: String, status: Int)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+    case message
+    case status
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(String.self, forKey: .type)
+
+    switch type {
+    case "success":
+      self = .success(try container.decode(T.self, forKey: .value))
+    case "error":
+      self = .failure(try container.decode(RouteError.self, forKey: .value))
+    default:
+      self = .genericFailure(
+        message: try container.decode(String.self, forKey: .message),
+        status: try container.decode(Int.self, forKey: .status)
+      )
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .success(let value):
+      try container.encode("success", forKey: .type)
+      try container.encode(value, forKey: .value)
+    case .failure(let error):
+      try container.encode("error", forKey: .type)
+      try container.encode(error, forKey: .value)
+    case .genericFailure(let message, let status):
+      try container.encode("failure", forKey: .type)
+      try container.encode(message, forKey: .message)
+      try container.encode(status, forKey: .status)
+    }
+  }
+
+  func encodeResponse(for request: Request) async throws -> Vapor.Response {
+    let response = Vapor.Response(status: .ok)
+    try response.content.encode(self, using: FenCoding.encoder)
+    return response
+  }
+}
+
+struct NoData: Codable, Sendable {
+  init() {}
+
+  init(from decoder: Decoder) throws {}
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.singleValueContainer()
+    try container.encodeNil()
+  }
+}
+
+enum FenCoding {
+  static var encoder: JSONEncoder {
+    let encoder = JSONEncoder()
+    encoder.dateEncodingStrategy = .iso8601
+    encoder.dataEncodingStrategy = .base64
+    return encoder
+  }
+
+  static var decoder: JSONDecoder {
+    let decoder = JSONDecoder()
+    decoder.dateDecodingStrategy = .iso8601withOptionalFractionalSeconds
+    decoder.dataDecodingStrategy = .base64
+    return decoder
+  }
+}
+
+extension ParseStrategy where Self == Date.ISO8601FormatStyle {
+  static var iso8601withFractionalSeconds: Self { .init(includingFractionalSeconds: true) }
+}
+
+extension JSONDecoder.DateDecodingStrategy {
+  static let iso8601withOptionalFractionalSeconds = custom {
+    let string = try $0.singleValueContainer().decode(String.self)
+    do {
+      return try .init(string, strategy: .iso8601withFractionalSeconds)
+    } catch {
+      return try .init(string, strategy: .iso8601)
+    }
+  }
+}
+
+/// Lets `[UUID: T]` dictionaries be coded as JSON objects keyed by UUID strings, rather than as
+/// flat arrays of keys and values.
+@available(macOS 12.3, iOS 15.4, tvOS 15.4, watchOS 8.5, *)
+extension UUID: CodingKeyRepresentable {
+  public var codingKey: CodingKey {
+    UUIDCodingKey(stringValue: self.uuidString)
+  }
+
+  public init?<T: CodingKey>(codingKey: T) {
+    self.init(uuidString: codingKey.stringValue)
+  }
+}
+
+private struct UUIDCodingKey: CodingKey {
+  var stringValue: String
+  var intValue: Int? { nil }
+
+  init(stringValue: String) {
+    self.stringValue = stringValue
+  }
+
+  init?(intValue: Int) {
+    return nil
+  }
+}
+
+/// Decimals are encoded as strings (so no precision is lost), which these overloads take care of
+/// for struct fields and enum values (the only places Fen allows them, since generic code like
+/// `[Decimal]`'s wouldn't pick the overloads up).
+extension KeyedEncodingContainer {
+  mutating func encode(_ value: Decimal, forKey key: Key) throws {
+    try self.encode(value.description, forKey: key)
+  }
+}
+
+extension KeyedDecodingContainer {
+  func decode(_ type: Decimal.Type, forKey key: Key) throws -> Decimal {
+    let string = try self.decode(String.self, forKey: key)
+    guard let value = Decimal(string: string, locale: Locale(identifier: "en_US_POSIX")) else {
+      throw DecodingError.dataCorruptedError(
+        forKey: key,
+        in: self,
+        debugDescription: "Invalid decimal: \(string)"
+      )
+    }
+    return value
+  }
+
+  func decodeIfPresent(_ type: Decimal.Type, forKey key: Key) throws -> Decimal? {
+    guard self.contains(key), try !self.decodeNil(forKey: key) else {
+      return nil
+    }
+    return try self.decode(Decimal.self, forKey: key)
+  }
+}
+
+/// The types a `@header` field can have, which are read from the header's text.
+protocol HeaderValue {
+  init?(headerValue: String)
+}
+
+extension HeaderValue where Self: LosslessStringConvertible {
+  init?(headerValue: String) {
+    self.init(headerValue)
+  }
+}
+
+extension String: HeaderValue {}
+extension Int: HeaderValue {}
+extension Int32: HeaderValue {}
+extension Int64: HeaderValue {}
+extension UInt: HeaderValue {}
+extension Double: HeaderValue {}
+extension Bool: HeaderValue {}
+
+extension UUID: HeaderValue {
+  init?(headerValue: String) {
+    self.init(uuidString: headerValue)
+  }
+}
+
+extension Request {
+  /// Reads a `@header` field, which is `nil` if the header is missing or isn't a valid `T`.
+  func header<T: HeaderValue>(_ name: String, as type: T.Type) -> T? {
+    self.headers.first(name: name).flatMap(T.init(headerValue:))
+  }
+}
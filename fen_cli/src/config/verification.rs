@@ -1,6 +1,11 @@
+use fen_parser::codegen::docs::DocsFormat;
+
 pub struct Info {
     pub client: ClientInfo,
     pub server: ServerInfo,
+    pub openapi: Option<OpenApiInfo>,
+    pub json_schema: Option<JsonSchemaInfo>,
+    pub docs: Option<DocsInfo>,
 }
 
 pub struct ClientInfo {
@@ -13,15 +18,51 @@ pub struct ServerInfo {
     pub output: Output,
 }
 
+pub struct OpenApiInfo {
+    pub path: String,
+    pub title: String,
+    pub version: String,
+}
+
+pub struct JsonSchemaInfo {
+    pub path: String,
+}
+
+pub struct DocsInfo {
+    pub path: String,
+    pub format: DocsFormat,
+}
+
 pub struct Output {
     pub language: Language,
     pub path: String,
+    pub package: Option<String>,
+    /// Whether to leave out streaming routes, for clients that can't read streams yet (rather
+    /// than failing).
+    pub skip_streaming: bool,
 }
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum Language {
     Rust,
     Swift,
+    TypeScript,
+    Kotlin,
+    Python,
+    Go,
+}
+
+impl Language {
+    pub const fn display_name(&self) -> &'static str {
+        match self {
+            Self::Rust => "Rust",
+            Self::Swift => "Swift",
+            Self::TypeScript => "TypeScript",
+            Self::Kotlin => "Kotlin",
+            Self::Python => "Python",
+            Self::Go => "Go",
+        }
+    }
 }
 
 pub fn get_config_info(file_contents: &str) -> Result<Info, std::io::Error> {
@@ -54,19 +95,17 @@ pub fn get_config_info(file_contents: &str) -> Result<Info, std::io::Error> {
 
         let language = verify_string(output, "language")?;
         let path = verify_string(output, "path")?;
+        let package = verify_optional_string(output, "package")?;
+        let skip_streaming = verify_optional_bool(output, "skip_streaming")?.unwrap_or(false);
 
-        let language = match language.as_str() {
-            "rust" => Language::Rust,
-            "swift" => Language::Swift,
-            _ => {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    "Invalid language",
-                ))
-            }
-        };
+        let language = verify_language(&language)?;
 
-        client_outputs.push(Output { language, path });
+        client_outputs.push(Output {
+            language,
+            path,
+            package,
+            skip_streaming,
+        });
     }
 
     let client_endpoint_dev = verify_string(&client, "endpoint_dev")?;
@@ -74,17 +113,9 @@ pub fn get_config_info(file_contents: &str) -> Result<Info, std::io::Error> {
 
     let server_language = verify_string(&server_output, "language")?;
     let server_path = verify_string(&server_output, "path")?;
+    let server_package = verify_optional_string(&server_output, "package")?;
 
-    let server_language = match server_language.as_str() {
-        "rust" => Language::Rust,
-        "swift" => Language::Swift,
-        _ => {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Invalid language",
-            ))
-        }
-    };
+    let server_language = verify_language(&server_language)?;
 
     let client = ClientInfo {
         outputs: client_outputs,
@@ -95,10 +126,44 @@ pub fn get_config_info(file_contents: &str) -> Result<Info, std::io::Error> {
         output: Output {
             language: server_language,
             path: server_path,
+            package: server_package,
+            skip_streaming: false,
         },
     };
 
-    Ok(Info { client, server })
+    let openapi = match verify_optional_table(&table, "openapi")? {
+        Some(openapi) => Some(OpenApiInfo {
+            path: verify_string(&openapi, "path")?,
+            title: verify_optional_string(&openapi, "title")?
+                .unwrap_or_else(|| "Fen API".to_string()),
+            version: verify_optional_string(&openapi, "version")?
+                .unwrap_or_else(|| "1.0.0".to_string()),
+        }),
+        None => None,
+    };
+
+    let json_schema = match verify_optional_table(&table, "json_schema")? {
+        Some(json_schema) => Some(JsonSchemaInfo {
+            path: verify_string(&json_schema, "path")?,
+        }),
+        None => None,
+    };
+
+    let docs = match verify_optional_table(&table, "docs")? {
+        Some(docs) => Some(DocsInfo {
+            path: verify_string(&docs, "path")?,
+            format: verify_docs_format(verify_optional_string(&docs, "format")?.as_deref())?,
+        }),
+        None => None,
+    };
+
+    Ok(Info {
+        client,
+        server,
+        openapi,
+        json_schema,
+        docs,
+    })
 }
 
 fn verify_table(table: &toml::Table, key: &str) -> Result<toml::Table, std::io::Error> {
@@ -122,6 +187,17 @@ fn verify_table(table: &toml::Table, key: &str) -> Result<toml::Table, std::io::
     Ok(value.unwrap().clone())
 }
 
+fn verify_optional_table(
+    table: &toml::Table,
+    key: &str,
+) -> Result<Option<toml::Table>, std::io::Error> {
+    if table.contains_key(key) {
+        verify_table(table, key).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
 fn verify_array(table: &toml::Table, key: &str) -> Result<Vec<toml::Value>, std::io::Error> {
     let value = table.get(key);
     if value.is_none() {
@@ -163,3 +239,69 @@ fn verify_string(table: &toml::Table, key: &str) -> Result<String, std::io::Erro
 
     Ok(value.unwrap().to_string())
 }
+
+fn verify_optional_string(
+    table: &toml::Table,
+    key: &str,
+) -> Result<Option<String>, std::io::Error> {
+    if table.contains_key(key) {
+        verify_string(table, key).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn verify_bool(table: &toml::Table, key: &str) -> Result<bool, std::io::Error> {
+    let value = table.get(key);
+    if value.is_none() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Missing {key} configuration"),
+        ));
+    }
+    let value = value.unwrap();
+
+    let value = value.as_bool();
+    if value.is_none() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{key} configuration is not a boolean"),
+        ));
+    }
+
+    Ok(value.unwrap())
+}
+
+fn verify_optional_bool(table: &toml::Table, key: &str) -> Result<Option<bool>, std::io::Error> {
+    if table.contains_key(key) {
+        verify_bool(table, key).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn verify_language(language: &str) -> Result<Language, std::io::Error> {
+    match language {
+        "rust" => Ok(Language::Rust),
+        "swift" => Ok(Language::Swift),
+        "typescript" => Ok(Language::TypeScript),
+        "kotlin" => Ok(Language::Kotlin),
+        "python" => Ok(Language::Python),
+        "go" => Ok(Language::Go),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid language",
+        )),
+    }
+}
+
+fn verify_docs_format(format: Option<&str>) -> Result<DocsFormat, std::io::Error> {
+    match format {
+        None | Some("markdown") => Ok(DocsFormat::Markdown),
+        Some("html") => Ok(DocsFormat::Html),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Invalid docs format",
+        )),
+    }
+}
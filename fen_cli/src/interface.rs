@@ -1,12 +1,22 @@
 use colored::Colorize;
+use fen_parser::ast::{Field, FileNode, IOType, SharedTypes, Variant};
 
 use crate::{
-    codegen::{client::swift::gen_swift_client, server::rust::gen_rust_server},
+    codegen::{
+        client::{
+            go::gen_go_client, kotlin::gen_kotlin_client, python::gen_python_client,
+            rust::gen_rust_client, swift::gen_swift_client, typescript::gen_typescript_client,
+        },
+        docs::gen_docs,
+        json_schema::gen_json_schemas,
+        openapi::gen_openapi,
+        server::{go::gen_go_server, rust::gen_rust_server, swift::gen_swift_server},
+    },
     config::{
         find_config_dir, get_config_file_contents,
-        verification::{get_config_info, Language},
+        verification::{get_config_info, ClientInfo, Language, ServerInfo},
     },
-    print::{info, success},
+    print::{info, success, warning},
     routes::parse,
     VERSION,
 };
@@ -21,7 +31,7 @@ pub fn run() -> Result<(), std::io::Error> {
     let config_dir = find_config_dir()?;
     let file_contents = get_config_file_contents(&config_dir)?;
     let config_info = get_config_info(&file_contents)?;
-    let routes = parse(config_dir.to_str().unwrap())?;
+    let (routes, shared) = parse(config_dir.to_str().unwrap())?;
 
     info(&format!(
         "Generating client-side code ({})...",
@@ -29,52 +39,253 @@ pub fn run() -> Result<(), std::io::Error> {
             .client
             .outputs
             .iter()
-            .map(|o| match o.language {
-                Language::Rust => "Rust",
-                Language::Swift => "Swift",
-            })
+            .map(|o| o.language.display_name())
             .collect::<Vec<&str>>()
             .join(", ")
     ));
-    for output in config_info.client.outputs {
-        if output.language == Language::Swift {
-            gen_swift_client(
-                &output.path,
-                &config_info.client.endpoint_dev,
-                &config_info.client.endpoint_prod,
-                routes.iter().collect(),
-            )?;
-        } else {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("Unsupported language: {:?}", output.language),
-            ));
-        }
-    }
+    gen_clients(config_info.client, &routes, &shared)?;
     success("Client-side code generated successfully!");
 
     info(&format!(
         "Generating server-side code ({})...",
-        match &config_info.server.output.language {
-            Language::Rust => "Rust",
-            Language::Swift => "Swift",
-        }
+        config_info.server.output.language.display_name()
     ));
-    if config_info.server.output.language == Language::Rust {
-        gen_rust_server(&config_info.server.output.path, routes.iter().collect())?;
-    } else {
+    gen_server(&config_info.server, &routes, &shared)?;
+    success("Server-side code generated successfully!\n");
+
+    if let Some(openapi) = config_info.openapi {
+        info("Generating OpenAPI document...");
+        gen_openapi(
+            &openapi.path,
+            &openapi.title,
+            &openapi.version,
+            &routes.iter().collect::<Vec<_>>(),
+            &shared,
+        )?;
+        success("OpenAPI document generated successfully!\n");
+    }
+
+    if let Some(json_schema) = config_info.json_schema {
+        info("Generating JSON Schemas...");
+        gen_json_schemas(
+            &json_schema.path,
+            &routes.iter().collect::<Vec<_>>(),
+            &shared,
+        )?;
+        success("JSON Schemas generated successfully!\n");
+    }
+
+    if let Some(docs) = config_info.docs {
+        info("Generating documentation...");
+        gen_docs(
+            &docs.path,
+            docs.format,
+            &routes.iter().collect::<Vec<_>>(),
+            &shared,
+        )?;
+        success("Documentation generated successfully!\n");
+    }
+
+    print_deprecations(&routes, &shared);
+
+    success("That's it! Enjoy your typesafe API! 😊");
+
+    println!();
+    Ok(())
+}
+
+fn gen_clients(
+    client: ClientInfo,
+    routes: &[FileNode],
+    shared: &SharedTypes,
+) -> Result<(), std::io::Error> {
+    for output in client.outputs {
+        // only the Swift client can read streams so far, so the others can only leave those
+        // routes out (when they're told to)
+        let (streaming, routes): (Vec<&FileNode>, Vec<&FileNode>) = routes
+            .iter()
+            .partition(|route| route.stream && output.language != Language::Swift);
+        if !streaming.is_empty() {
+            let names = streaming
+                .iter()
+                .map(|route| route.name.as_str())
+                .collect::<Vec<&str>>()
+                .join(", ");
+            if !output.skip_streaming {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "{} clients can't stream output yet (used by {names}); set `skip_streaming = true` on the output to leave those routes out",
+                        output.language.display_name()
+                    ),
+                ));
+            }
+            warning(&format!(
+                "{} clients can't stream output yet, so they skip {names}",
+                output.language.display_name()
+            ));
+        }
+        match output.language {
+            Language::Swift => gen_swift_client(
+                &output.path,
+                &client.endpoint_dev,
+                &client.endpoint_prod,
+                routes,
+                shared,
+            )?,
+            Language::TypeScript => {
+                gen_typescript_client(&output.path, routes, shared)?;
+            }
+            Language::Rust => gen_rust_client(&output.path, routes, shared)?,
+            Language::Kotlin => {
+                gen_kotlin_client(&output.path, output.package.as_deref(), routes, shared)?;
+            }
+            Language::Python => gen_python_client(&output.path, routes, shared)?,
+            Language::Go => {
+                gen_go_client(&output.path, output.package.as_deref(), routes, shared)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn gen_server(
+    server: &ServerInfo,
+    routes: &[FileNode],
+    shared: &SharedTypes,
+) -> Result<(), std::io::Error> {
+    let streaming = routes
+        .iter()
+        .filter(|route| route.stream)
+        .map(|route| route.name.as_str())
+        .collect::<Vec<&str>>();
+    if !streaming.is_empty() && server.output.language != Language::Rust {
         return Err(std::io::Error::new(
             std::io::ErrorKind::InvalidData,
             format!(
-                "Unsupported language: {:?}",
-                config_info.server.output.language
+                "{} servers can't stream output yet (used by {})",
+                server.output.language.display_name(),
+                streaming.join(", ")
             ),
         ));
     }
-    success("Server-side code generated successfully!\n");
+    match server.output.language {
+        Language::Rust => {
+            gen_rust_server(&server.output.path, routes.iter().collect(), shared)?;
+        }
+        Language::Swift => {
+            gen_swift_server(&server.output.path, routes.iter().collect(), shared)?;
+        }
+        Language::Go => {
+            gen_go_server(
+                &server.output.path,
+                server.output.package.as_deref(),
+                routes.iter().collect(),
+                shared,
+            )?;
+        }
+        Language::TypeScript | Language::Kotlin | Language::Python => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Unsupported language: {:?}", server.output.language),
+            ));
+        }
+    }
 
-    success("That's it! Enjoy your typesafe API! 😊");
+    Ok(())
+}
+
+fn print_deprecations(routes: &[FileNode], shared: &SharedTypes) {
+    let deprecations = deprecations(routes, shared);
+    if deprecations.is_empty() {
+        return;
+    }
 
+    warning(&format!(
+        "{} deprecated item{}:",
+        deprecations.len(),
+        if deprecations.len() == 1 { "" } else { "s" }
+    ));
+    for (item, message) in deprecations {
+        println!("  {}: {message}", item.bold());
+    }
     println!();
-    Ok(())
+}
+
+/// Every deprecated route, field, and variant (like `GetTodos.Todo.title`), with its message.
+fn deprecations(routes: &[FileNode], shared: &SharedTypes) -> Vec<(String, String)> {
+    let mut items = vec![];
+    for route in routes {
+        if let Some(message) = &route.deprecated {
+            items.push((route.name.clone(), message.clone()));
+        }
+        for (name, io) in [
+            ("input", &route.input),
+            ("output", &route.output),
+            ("error", &route.error),
+        ] {
+            match io {
+                Some(IOType::Struct(s)) => {
+                    push_deprecations(
+                        &mut items,
+                        &format!("{}.{name}", route.name),
+                        &s.fields,
+                        &[],
+                    );
+                }
+                Some(IOType::Enum(e)) => {
+                    push_deprecations(
+                        &mut items,
+                        &format!("{}.{name}", route.name),
+                        &[],
+                        &e.variants,
+                    );
+                }
+                Some(IOType::Type(_)) | None => {}
+            }
+        }
+        for s in &route.structs {
+            push_deprecations(
+                &mut items,
+                &format!("{}.{}", route.name, s.name),
+                &s.fields,
+                &[],
+            );
+        }
+        for e in &route.enums {
+            push_deprecations(
+                &mut items,
+                &format!("{}.{}", route.name, e.name),
+                &[],
+                &e.variants,
+            );
+        }
+    }
+    for s in &shared.structs {
+        push_deprecations(&mut items, &s.name, &s.fields, &[]);
+    }
+    for e in &shared.enums {
+        push_deprecations(&mut items, &e.name, &[], &e.variants);
+    }
+
+    items.sort();
+    items
+}
+
+fn push_deprecations(
+    items: &mut Vec<(String, String)>,
+    path: &str,
+    fields: &[Field],
+    variants: &[Variant],
+) {
+    let deprecated = fields
+        .iter()
+        .map(|f| (&f.name, &f.deprecated))
+        .chain(variants.iter().map(|v| (&v.name, &v.deprecated)));
+    for (name, message) in deprecated {
+        if let Some(message) = message {
+            items.push((format!("{path}.{name}"), message.clone()));
+        }
+    }
 }
@@ -7,3 +7,7 @@ pub fn info(text: &str) {
 pub fn success(text: &str) {
     println!("{} {}", "✔".green(), text);
 }
+
+pub fn warning(text: &str) {
+    println!("{} {}", "⚠".yellow(), text);
+}
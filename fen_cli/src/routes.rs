@@ -1,14 +1,35 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use fen_parser::Parser;
+use fen_parser::{
+    ast::{FileNode, SharedTypes},
+    Diagnostic, Parser,
+};
 
-pub fn parse(path: &str) -> Result<Vec<fen_parser::ast::FileNode>, std::io::Error> {
-    // get all files in `path` that end with .fen
+/// The name of the (optional) file whose types can be imported by every route.
+const SHARED_FILE_NAME: &str = "shared.fen";
+
+/// Parses every route (and the shared types), failing with all of their problems (rather than
+/// just the first) if any don't parse.
+pub fn parse(path: &str) -> Result<(Vec<FileNode>, SharedTypes), std::io::Error> {
+    let mut problems = vec![];
+
+    // parse the shared types first, so routes can import them
+    let shared_path = Path::new(path).join(SHARED_FILE_NAME);
+    let shared = if shared_path.exists() {
+        let file_contents = std::fs::read_to_string(&shared_path)?;
+        let (shared, diagnostics) = Parser::new(&file_contents).parse_shared_with_recovery();
+        problems.extend(render(&diagnostics, &shared_path, &file_contents));
+        shared
+    } else {
+        SharedTypes::default()
+    };
+
+    // get all (non-shared) files in `path` that end with .fen
     let file_names = std::fs::read_dir(path)?
         .filter_map(|entry| {
             entry.ok().and_then(|e| {
                 let path = e.path();
-                if path.extension()?.to_str()? == "fen" {
+                if path.extension()?.to_str()? == "fen" && path != shared_path {
                     Some(path)
                 } else {
                     None
@@ -21,16 +42,30 @@ pub fn parse(path: &str) -> Result<Vec<fen_parser::ast::FileNode>, std::io::Erro
     let mut routes = vec![];
     for file_name in file_names {
         let file_contents = std::fs::read_to_string(&file_name)?;
-        let mut parser = Parser::new(&file_contents);
-        let ast = parser.parse();
-        if let Err(e) = ast {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Error parsing file",
-            ));
-        }
-        routes.push(ast.unwrap());
+        let mut parser = Parser::new(&file_contents).with_shared_types(&shared);
+        let (ast, diagnostics) = parser.parse_with_recovery();
+        problems.extend(render(&diagnostics, &file_name, &file_contents));
+        routes.push(ast);
+    }
+
+    match problems.len() {
+        0 => Ok((routes, shared)),
+        1 => Err(parse_error(problems.remove(0))),
+        n => Err(parse_error(format!(
+            "{n} problems found\n\n{}",
+            problems.join("\n\n")
+        ))),
     }
+}
+
+/// Shows where each problem is, like a compiler would.
+fn render(diagnostics: &[Diagnostic], path: &Path, file_contents: &str) -> Vec<String> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.render(&path.display().to_string(), file_contents))
+        .collect()
+}
 
-    Ok(routes)
+fn parse_error(message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
 }
@@ -1,12 +1,19 @@
 #![allow(clippy::multiple_crate_versions)]
+use fen_parser::ast::SharedTypes;
+use fen_parser::Parser;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::{
-    CompletionItem, CompletionOptions, CompletionParams, CompletionResponse, Hover, HoverContents,
-    HoverParams, HoverProviderCapability, InitializeParams, InitializeResult, InitializedParams,
-    MarkedString, MessageType, ServerCapabilities,
+    CompletionItem, CompletionOptions, CompletionParams, CompletionResponse, Diagnostic,
+    DiagnosticSeverity, DidChangeTextDocumentParams, DidOpenTextDocumentParams, Hover,
+    HoverContents, HoverParams, HoverProviderCapability, InitializeParams, InitializeResult,
+    InitializedParams, MarkedString, MessageType, Position, Range, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
 };
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
+/// The file whose types every route can import (it sits next to the routes).
+const SHARED_FILE_NAME: &str = "shared.fen";
+
 #[derive(Debug)]
 struct Backend {
     client: Client,
@@ -17,6 +24,9 @@ impl LanguageServer for Backend {
     async fn initialize(&self, _: InitializeParams) -> Result<InitializeResult> {
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
                 completion_provider: Some(CompletionOptions::default()),
                 ..Default::default()
@@ -35,6 +45,24 @@ impl LanguageServer for Backend {
         Ok(())
     }
 
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let document = params.text_document;
+        self.check(document.uri, &document.text, document.version)
+            .await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // with full sync, the last change is the whole document
+        if let Some(change) = params.content_changes.last() {
+            self.check(
+                params.text_document.uri,
+                &change.text,
+                params.text_document.version,
+            )
+            .await;
+        }
+    }
+
     async fn completion(&self, _: CompletionParams) -> Result<Option<CompletionResponse>> {
         Ok(Some(CompletionResponse::Array(vec![
             CompletionItem::new_simple("Hello".to_string(), "Some detail".to_string()),
@@ -51,6 +79,73 @@ impl LanguageServer for Backend {
     }
 }
 
+impl Backend {
+    /// Parses a document (carrying on past errors) and publishes every problem in it.
+    async fn check(&self, uri: Url, text: &str, version: i32) {
+        let path = uri.to_file_path().ok();
+        let is_shared = path.as_ref().is_some_and(|path| {
+            path.file_name()
+                .is_some_and(|name| name == SHARED_FILE_NAME)
+        });
+
+        let diagnostics = if is_shared {
+            Parser::new(text).parse_shared_with_recovery().1
+        } else {
+            let shared = path
+                .and_then(|path| {
+                    std::fs::read_to_string(path.with_file_name(SHARED_FILE_NAME)).ok()
+                })
+                .map_or_else(SharedTypes::default, |shared| {
+                    Parser::new(&shared).parse_shared_with_recovery().0
+                });
+            Parser::new(text)
+                .with_shared_types(&shared)
+                .parse_with_recovery()
+                .1
+        };
+
+        let diagnostics = diagnostics
+            .iter()
+            .map(|diagnostic| lsp_diagnostic(diagnostic, text))
+            .collect();
+        self.client
+            .publish_diagnostics(uri, diagnostics, Some(version))
+            .await;
+    }
+}
+
+fn lsp_diagnostic(diagnostic: &fen_parser::Diagnostic, text: &str) -> Diagnostic {
+    let mut message = diagnostic.error.to_string();
+    if let Some(help) = &diagnostic.help {
+        message.push_str("\nhelp: ");
+        message.push_str(help);
+    }
+
+    Diagnostic {
+        range: Range::new(
+            position(text, diagnostic.span.start),
+            position(text, diagnostic.span.end),
+        ),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("fen".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Turns a byte offset into an LSP position (whose columns count UTF-16 code units).
+fn position(text: &str, offset: usize) -> Position {
+    let before = text.get(..offset).unwrap_or(text);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count();
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(
+        u32::try_from(line).unwrap_or(u32::MAX),
+        u32::try_from(character).unwrap_or(u32::MAX),
+    )
+}
+
 #[tokio::main]
 async fn main() {
     let stdin = tokio::io::stdin();
@@ -1,3 +1,4 @@
+use crate::codegen::name_transforms::pascal_to_kebab;
 use serde::Serialize;
 
 #[derive(Debug, PartialEq, Eq, Serialize, Clone)]
@@ -5,60 +6,468 @@ pub struct FileNode {
     // metadata
     pub name: String,
     pub description: Option<String>,
+    /// Set by `@deprecated("...")`, with what to use instead.
+    pub deprecated: Option<String>,
     pub authed: bool,
+    pub imports: Vec<String>,
+    /// Set by `method: "PUT"`; otherwise see [`FileNode::http_method`].
+    pub method: Option<Method>,
+    /// Set by `path: "/users/{id}"`, where `{id}` comes from the input's `id` field.
+    pub path: Option<String>,
+    pub spans: MetadataSpans,
 
     // i/o
     pub input: Option<IOType>,
     pub output: Option<IOType>,
+    /// Set by `@output stream Event`: the output is sent as a stream of events, rather than as
+    /// one response.
+    pub stream: bool,
+    /// What the route can fail with (besides the failures every route can have, like a 401).
+    pub error: Option<IOType>,
 
     // helper types
     pub structs: Vec<StructDefinition>,
     pub enums: Vec<EnumDefinition>,
 }
 
+impl FileNode {
+    /// The method the route is served with: the one it sets, or POST when it has input (and
+    /// GET when it doesn't).
+    #[must_use]
+    pub const fn http_method(&self) -> Method {
+        match (self.method, &self.input) {
+            (Some(method), _) => method,
+            (None, Some(_)) => Method::Post,
+            (None, None) => Method::Get,
+        }
+    }
+
+    /// The path the route is served at: the one it sets, or its name in kebab-case under
+    /// `/_fen_/`.
+    #[must_use]
+    pub fn http_path(&self) -> String {
+        self.path
+            .clone()
+            .unwrap_or_else(|| format!("/_fen_/{}", pascal_to_kebab(&self.name)))
+    }
+
+    /// The names of the path's parameters (like `id` in `/users/{id}`), in order.
+    #[must_use]
+    pub fn path_params(&self) -> Vec<&str> {
+        self.path.as_deref().map_or_else(Vec::new, path_params)
+    }
+
+    /// Whether the input is sent as the request's body. GET requests don't have one, so their
+    /// input comes from the path, query string, and headers.
+    #[must_use]
+    pub const fn has_body(&self) -> bool {
+        self.input.is_some() && !matches!(self.http_method(), Method::Get)
+    }
+
+    /// The input's fields that are sent in the request's body, as a struct named `Body`, when the
+    /// rest of them come from the path, query string, or headers (otherwise the whole input is
+    /// the body, if there is one).
+    #[must_use]
+    pub fn body_struct(&self) -> Option<StructDefinition> {
+        let Some(IOType::Struct(input)) = &self.input else {
+            return None;
+        };
+        let params = self.path_params();
+        let in_body =
+            |field: &&Field| field.location.is_none() && !params.contains(&field.name.as_str());
+        if !self.has_body() || input.fields.iter().all(|field| in_body(&field)) {
+            return None;
+        }
+
+        Some(StructDefinition {
+            name: "Body".to_string(),
+            description: None,
+            fields: input.fields.iter().filter(in_body).cloned().collect(),
+            ..input.clone()
+        })
+    }
+
+    /// The input's fields marked `@query` or `@header` (whichever `location` is), in order.
+    #[must_use]
+    pub fn fields_in(&self, location: ParamLocation) -> Vec<&Field> {
+        match &self.input {
+            Some(IOType::Struct(s)) => s
+                .fields
+                .iter()
+                .filter(|field| field.location == Some(location))
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// The names of the parameters in `path` (like `id` in `/users/{id}`), in order.
+#[must_use]
+pub fn path_params(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(param, _)| param))
+        .collect()
+}
+
+/// An HTTP method a route can be served with.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    /// Parses a method's name, like `PUT`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "GET" => Some(Self::Get),
+            "POST" => Some(Self::Post),
+            "PUT" => Some(Self::Put),
+            "PATCH" => Some(Self::Patch),
+            "DELETE" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    /// The method's name, like `PUT`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Patch => "PATCH",
+            Self::Delete => "DELETE",
+        }
+    }
+
+    /// The method's name in lowercase (which is how most frameworks name their routing
+    /// functions), like `put`.
+    #[must_use]
+    pub const fn lowercase_name(self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::Post => "post",
+            Self::Put => "put",
+            Self::Patch => "patch",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Where something is in a source file.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, Serialize)]
+pub struct Span {
+    /// The byte offset of the first character.
+    pub start: usize,
+    /// The byte offset just past the last character.
+    pub end: usize,
+    /// The 1-based line of the first character.
+    pub line: usize,
+    /// The 1-based column (in bytes) of the first character.
+    pub column: usize,
+}
+
+/// Where each of a route's metadata entries is written (or the default span, if it isn't).
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, Serialize)]
+pub struct MetadataSpans {
+    /// The route's name (the string literal).
+    pub name: Span,
+    /// The value of `authed`.
+    pub authed: Span,
+    /// The value of `method`.
+    pub method: Span,
+    /// The value of `path`.
+    pub path: Span,
+}
+
+/// Types declared once in `shared.fen` and imported by routes.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone, Default)]
+pub struct SharedTypes {
+    pub structs: Vec<StructDefinition>,
+    pub enums: Vec<EnumDefinition>,
+}
+
+impl SharedTypes {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.structs.is_empty() && self.enums.is_empty()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize)]
 #[serde(tag = "type")]
 pub enum Primitive {
     Int,
+    Int32,
+    Int64,
+    UInt,
     Float,
+    /// Encoded as a string, so no precision is lost.
+    Decimal,
     String,
     Bool,
     Date,
     Uuid,
+    /// Encoded as a base64 string.
+    Bytes,
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+/// A type, with where it's written (which isn't part of its identity, so `Page<User>` is the
+/// same type wherever it's used).
+#[derive(Debug, Serialize, Clone)]
 pub enum Type {
-    Named(String),
-    Optional(Box<Type>),
-    Array(Box<Type>),
-    Primitive(Primitive),
+    Named(String, Span),
+    Optional(Box<Self>, Span),
+    Array(Box<Self>, Span),
+    /// A JSON object; keys are always `String`, `Int`, or `UUID`, and are encoded as strings.
+    Map(Box<Self>, Box<Self>, Span),
+    Primitive(Primitive, Span),
+    /// An instantiation of a generic helper type, like `Page<User>`.
+    Generic(String, Vec<Self>, Span),
+}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Named(a, _), Self::Named(b, _)) => a == b,
+            (Self::Optional(a, _), Self::Optional(b, _))
+            | (Self::Array(a, _), Self::Array(b, _)) => a == b,
+            (Self::Map(ak, av, _), Self::Map(bk, bv, _)) => ak == bk && av == bv,
+            (Self::Primitive(a, _), Self::Primitive(b, _)) => a == b,
+            (Self::Generic(a, a_args, _), Self::Generic(b, b_args, _)) => {
+                a == b && a_args == b_args
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Type {}
+
+impl Type {
+    /// Where the type is written.
+    #[must_use]
+    pub const fn span(&self) -> Span {
+        match self {
+            Self::Named(_, span)
+            | Self::Optional(_, span)
+            | Self::Array(_, span)
+            | Self::Map(_, _, span)
+            | Self::Primitive(_, span)
+            | Self::Generic(_, _, span) => *span,
+        }
+    }
+
+    /// Replaces each of the type parameters in `params` with the matching type in `args`.
+    #[must_use]
+    pub fn substitute(&self, params: &[String], args: &[Self]) -> Self {
+        match self {
+            Self::Named(name, _) => params
+                .iter()
+                .position(|param| param == name)
+                .and_then(|i| args.get(i))
+                .map_or_else(|| self.clone(), Clone::clone),
+            Self::Optional(t, span) => Self::Optional(Box::new(t.substitute(params, args)), *span),
+            Self::Array(t, span) => Self::Array(Box::new(t.substitute(params, args)), *span),
+            Self::Map(k, v, span) => {
+                Self::Map(k.clone(), Box::new(v.substitute(params, args)), *span)
+            }
+            Self::Primitive(..) => self.clone(),
+            Self::Generic(name, generic_args, span) => Self::Generic(
+                name.clone(),
+                generic_args
+                    .iter()
+                    .map(|t| t.substitute(params, args))
+                    .collect(),
+                *span,
+            ),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Clone)]
 pub struct StructDefinition {
     pub name: String,
+    /// Where the struct is named (for an input, output, or error, its `input`, `output`, or `error`).
+    pub span: Span,
+    pub description: Option<String>,
+    /// Type parameters, like the `T` in `Page<T>`.
+    pub generics: Vec<String>,
     pub fields: Vec<Field>,
     pub annotations: Vec<String>,
 }
 
+impl StructDefinition {
+    /// The concrete struct for `args`, named `name` (like `Page<User>`, for `Page<T>`).
+    #[must_use]
+    pub fn instantiate(&self, name: &str, args: &[Type]) -> Self {
+        Self {
+            name: name.to_string(),
+            span: self.span,
+            description: self.description.clone(),
+            generics: vec![],
+            fields: self
+                .fields
+                .iter()
+                .map(|field| Field {
+                    name: field.name.clone(),
+                    span: field.span,
+                    description: field.description.clone(),
+                    deprecated: field.deprecated.clone(),
+                    t: field.t.substitute(&self.generics, args),
+                    default: field.default.clone(),
+                    constraints: field.constraints.clone(),
+                    location: field.location,
+                })
+                .collect(),
+            annotations: self.annotations.clone(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Clone)]
 pub struct Field {
     pub name: String,
+    /// Where the field is named.
+    pub span: Span,
+    pub description: Option<String>,
+    pub deprecated: Option<String>,
     #[serde(rename = "type")]
     pub t: Type,
+    /// Used when a payload leaves the field out, so it can be added without breaking anyone.
+    pub default: Option<DefaultValue>,
+    /// Checks the value has to pass, like `@max(100)` (only allowed on a route's input).
+    pub constraints: Vec<Constraint>,
+    /// Set by `@query` or `@header`, to send the field outside of the body (only allowed on a
+    /// route's input).
+    pub location: Option<ParamLocation>,
+}
+
+impl Field {
+    /// The name of the header a `@header` field is sent as (like `x-client-version`, for
+    /// `x_client_version`).
+    #[must_use]
+    pub fn header_name(&self) -> String {
+        self.name.replace('_', "-")
+    }
+}
+
+/// Where a field of a route's input is sent, when it isn't (just) in the body.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize)]
+pub enum ParamLocation {
+    /// `@query`: in the URL's query string, by its name in camelCase.
+    Query,
+    /// `@header`: as a header (see [`Field::header_name`]).
+    Header,
+}
+
+impl ParamLocation {
+    /// The annotation's name, like `query` for `@query`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Query => "query",
+            Self::Header => "header",
+        }
+    }
+}
+
+/// A field's default value, like the `20` in `limit: Int = 20`.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+pub enum DefaultValue {
+    Bool(bool),
+    /// Kept as written, so it can be emitted as-is.
+    Number(String),
+    String(String),
+    /// A variant (without a value) of the field's enum type.
+    Variant(String),
+}
+
+/// A check on a field's value, written as an annotation above the field.
+#[derive(Debug, PartialEq, Eq, Serialize, Clone)]
+pub enum Constraint {
+    /// `@min(1)`: the smallest number allowed (kept as written).
+    Min(String),
+    /// `@max(100)`: the largest number allowed (kept as written).
+    Max(String),
+    /// `@minLength(1)`: the fewest characters (of a string) or items (of an array) allowed.
+    MinLength(usize),
+    /// `@maxLength(80)`: the most characters (of a string) or items (of an array) allowed.
+    MaxLength(usize),
+    /// `@pattern("^[a-z]+$")`: a regular expression a string has to match.
+    Pattern(String),
+    /// `@nonEmpty`: a string, array, or map with something in it.
+    NonEmpty,
+}
+
+impl Constraint {
+    /// The annotation's name, like `min` for `@min(1)`.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Min(_) => "min",
+            Self::Max(_) => "max",
+            Self::MinLength(_) => "minLength",
+            Self::MaxLength(_) => "maxLength",
+            Self::Pattern(_) => "pattern",
+            Self::NonEmpty => "nonEmpty",
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Serialize, Clone)]
 pub struct EnumDefinition {
     pub name: String,
+    /// Where the enum is named (for an input, output, or error, its `input`, `output`, or `error`).
+    pub span: Span,
+    pub description: Option<String>,
+    /// Type parameters, like the `T` in `Loadable<T>`.
+    pub generics: Vec<String>,
     pub variants: Vec<Variant>,
     pub annotations: Vec<String>,
 }
 
+impl EnumDefinition {
+    /// The concrete enum for `args`, named `name` (like `Loadable<User>`, for `Loadable<T>`).
+    #[must_use]
+    pub fn instantiate(&self, name: &str, args: &[Type]) -> Self {
+        Self {
+            name: name.to_string(),
+            span: self.span,
+            description: self.description.clone(),
+            generics: vec![],
+            variants: self
+                .variants
+                .iter()
+                .map(|variant| Variant {
+                    name: variant.name.clone(),
+                    span: variant.span,
+                    description: variant.description.clone(),
+                    deprecated: variant.deprecated.clone(),
+                    t: variant
+                        .t
+                        .as_ref()
+                        .map(|t| t.substitute(&self.generics, args)),
+                })
+                .collect(),
+            annotations: self.annotations.clone(),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Serialize, Clone)]
 pub struct Variant {
     pub name: String,
+    /// Where the variant is named.
+    pub span: Span,
+    pub description: Option<String>,
+    pub deprecated: Option<String>,
     #[serde(rename = "type")]
     pub t: Option<Type>,
 }
@@ -0,0 +1,905 @@
+use serde_json::{json, Map, Value};
+
+use super::name_transforms::{pascal_to_kebab, snake_to_camel};
+use crate::ast::{
+    DefaultValue, EnumDefinition, FileNode, IOType, ParamLocation, Primitive, SharedTypes,
+    StructDefinition, Type,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    Markdown,
+    Html,
+}
+
+/// The named types a page can link to: its own helper types, plus the shared types.
+struct Types<'a> {
+    structs: &'a [StructDefinition],
+    enums: &'a [EnumDefinition],
+    shared: &'a SharedTypes,
+}
+
+impl Types<'_> {
+    fn find_struct(&self, name: &str) -> Option<&StructDefinition> {
+        self.structs
+            .iter()
+            .chain(&self.shared.structs)
+            .find(|s| s.name == name)
+    }
+
+    fn find_enum(&self, name: &str) -> Option<&EnumDefinition> {
+        self.enums
+            .iter()
+            .chain(&self.shared.enums)
+            .find(|e| e.name == name)
+    }
+}
+
+/// Renders an index page plus one page per route (and a page for the shared types, if there
+/// are any), as `(file name, contents)` pairs.
+#[must_use]
+pub fn docs_pages(
+    routes: &[&FileNode],
+    shared: &SharedTypes,
+    format: DocsFormat,
+) -> Vec<(String, String)> {
+    let mut routes = routes.to_vec();
+    routes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut pages = vec![(
+        index_file(format).to_string(),
+        index_page(&routes, shared, format),
+    )];
+    for route in routes {
+        pages.push((
+            format!("{}.{}", pascal_to_kebab(&route.name), extension(format)),
+            route_page(route, shared, format),
+        ));
+    }
+    if !shared.is_empty() {
+        pages.push((shared_file(format), shared_page(shared, format)));
+    }
+
+    pages
+}
+
+fn index_page(routes: &[&FileNode], shared: &SharedTypes, format: DocsFormat) -> String {
+    let rows = routes
+        .iter()
+        .map(|route| {
+            let file = format!("{}.{}", pascal_to_kebab(&route.name), extension(format));
+            [
+                link(format, &route.name, &file),
+                code(format, route.http_method().name()),
+                code(format, &route.http_path()),
+                text(format, route.description.as_deref().unwrap_or("")),
+            ]
+        })
+        .collect::<Vec<[String; 4]>>();
+
+    let mut sections = vec![
+        heading(format, 1, "API Reference"),
+        table(format, ["Route", "Method", "Path", "Description"], &rows),
+    ];
+    if !shared.is_empty() {
+        sections.push(paragraph(
+            format,
+            &link(format, "Shared types", &shared_file(format)),
+        ));
+    }
+
+    page(format, "API Reference", &sections.join("\n\n"))
+}
+
+fn shared_page(shared: &SharedTypes, format: DocsFormat) -> String {
+    let types = Types {
+        structs: &shared.structs,
+        enums: &shared.enums,
+        shared,
+    };
+
+    let mut sections = vec![heading(format, 1, "Shared Types")];
+    for struct_def in &shared.structs {
+        sections.push(anchored_heading(format, 2, &struct_def.name));
+        sections.push(struct_table(&types, struct_def, format));
+    }
+    for enum_def in &shared.enums {
+        sections.push(anchored_heading(format, 2, &enum_def.name));
+        sections.push(enum_table(&types, enum_def, format));
+    }
+    sections.push(paragraph(
+        format,
+        &link(format, "Back to all routes", index_file(format)),
+    ));
+
+    page(format, "Shared Types", &sections.join("\n\n"))
+}
+
+fn route_page(route: &FileNode, shared: &SharedTypes, format: DocsFormat) -> String {
+    let types = Types {
+        structs: &route.structs,
+        enums: &route.enums,
+        shared,
+    };
+
+    let mut sections = vec![heading(format, 1, &route.name)];
+    if let Some(description) = &route.description {
+        sections.push(paragraph(format, &text(format, description)));
+    }
+
+    sections.push(table(
+        format,
+        ["", ""],
+        &[
+            [
+                text(format, "Method"),
+                code(format, route.http_method().name()),
+            ],
+            [text(format, "Path"), code(format, &route.http_path())],
+            [
+                text(format, "Auth"),
+                if route.authed {
+                    format!(
+                        "{} ({})",
+                        text(format, "Required"),
+                        code(format, "Authorization: Bearer <token>")
+                    )
+                } else {
+                    text(format, "None")
+                },
+            ],
+        ],
+    ));
+
+    // input
+    sections.push(heading(format, 2, "Input"));
+    match &route.input {
+        Some(input) => {
+            sections.push(io_description(&types, input, format));
+            if let Some(params) = params_table(route, format) {
+                sections.push(paragraph(
+                    format,
+                    &text(format, "Some fields are sent outside the body:"),
+                ));
+                sections.push(params);
+            }
+            sections.push(paragraph(format, &text(format, "Example request body:")));
+            sections.push(json_block(format, &io_example(&types, input)));
+        }
+        None => sections.push(paragraph(format, &text(format, "None"))),
+    }
+
+    // output
+    sections.extend(output_sections(&types, route, format));
+
+    // errors
+    if let Some(error) = &route.error {
+        sections.push(heading(format, 2, "Errors"));
+        sections.push(io_description(&types, error, format));
+        sections.push(paragraph(format, &text(format, "Example error response:")));
+        sections.push(json_block(
+            format,
+            &json!({ "type": "error", "value": io_example(&types, error) }),
+        ));
+    }
+
+    // helper types
+    if !route.structs.is_empty() || !route.enums.is_empty() {
+        sections.push(heading(format, 2, "Types"));
+        for struct_def in &route.structs {
+            sections.push(anchored_heading(format, 3, &struct_def.name));
+            sections.push(struct_table(&types, struct_def, format));
+        }
+        for enum_def in &route.enums {
+            sections.push(anchored_heading(format, 3, &enum_def.name));
+            sections.push(enum_table(&types, enum_def, format));
+        }
+    }
+
+    sections.push(paragraph(
+        format,
+        &link(format, "Back to all routes", index_file(format)),
+    ));
+
+    page(format, &route.name, &sections.join("\n\n"))
+}
+
+/// The route's output section. A streaming route's output is described per event, with an
+/// example of the whole stream (since each event is sent as its own line).
+fn output_sections(types: &Types, route: &FileNode, format: DocsFormat) -> Vec<String> {
+    let mut sections = vec![heading(format, 2, "Output")];
+    if route.stream {
+        sections.push(paragraph(
+            format,
+            &format!(
+                "{} ({}), {}",
+                text(
+                    format,
+                    "Sent as a stream of events, one JSON object per line"
+                ),
+                code(format, "application/x-ndjson"),
+                text(format, "each with a value of:"),
+            ),
+        ));
+    }
+    let value = if let Some(output) = &route.output {
+        sections.push(io_description(types, output, format));
+        io_example(types, output)
+    } else {
+        sections.push(paragraph(format, &text(format, "None")));
+        Value::Null
+    };
+    let failure = json!({ "type": "failure", "message": "Something went wrong", "status": 500 });
+    sections.push(paragraph(format, &text(format, "Example response:")));
+    if route.stream {
+        sections.push(lines_block(
+            format,
+            &[
+                json!({ "type": "event", "value": value }),
+                json!({ "type": "event", "value": value }),
+                json!({ "type": "end" }),
+            ],
+        ));
+        sections.push(paragraph(
+            format,
+            &text(
+                format,
+                "A failure ends the stream early (in place of the end line), and is sent as:",
+            ),
+        ));
+    } else {
+        sections.push(json_block(
+            format,
+            &json!({ "type": "success", "value": value }),
+        ));
+        sections.push(paragraph(
+            format,
+            &text(format, "Failures are returned as:"),
+        ));
+    }
+    sections.push(json_block(format, &failure));
+    sections
+}
+
+/// Where each `@query` and `@header` field of the input is sent, if there are any.
+fn params_table(route: &FileNode, format: DocsFormat) -> Option<String> {
+    let rows = route
+        .fields_in(ParamLocation::Query)
+        .into_iter()
+        .chain(route.fields_in(ParamLocation::Header))
+        .map(|field| {
+            let name = snake_to_camel(&field.name);
+            let sent_as = match field.location {
+                Some(ParamLocation::Header) => format!(
+                    "{} {}",
+                    text(format, "Header"),
+                    code(format, &field.header_name())
+                ),
+                _ => format!(
+                    "{} {}",
+                    text(format, "Query parameter"),
+                    code(format, &name)
+                ),
+            };
+            [code(format, &name), sent_as]
+        })
+        .collect::<Vec<[String; 2]>>();
+
+    if rows.is_empty() {
+        None
+    } else {
+        Some(table(format, ["Field", "Sent as"], &rows))
+    }
+}
+
+fn io_description(types: &Types, io: &IOType, format: DocsFormat) -> String {
+    match io {
+        IOType::Type(t) => paragraph(format, &type_label(types, t, format)),
+        IOType::Struct(s) => struct_table(types, s, format),
+        IOType::Enum(e) => enum_table(types, e, format),
+    }
+}
+
+fn struct_table(types: &Types, struct_def: &StructDefinition, format: DocsFormat) -> String {
+    // only show a default column when there's something to put in it
+    if struct_def
+        .fields
+        .iter()
+        .any(|field| field.default.is_some())
+    {
+        let rows = struct_def
+            .fields
+            .iter()
+            .map(|field| {
+                [
+                    code(format, &snake_to_camel(&field.name)),
+                    type_label(types, &field.t, format),
+                    field
+                        .default
+                        .as_ref()
+                        .map_or_else(String::new, |default| code(format, &default_label(default))),
+                ]
+            })
+            .collect::<Vec<[String; 3]>>();
+
+        return table(format, ["Field", "Type", "Default"], &rows);
+    }
+
+    let rows = struct_def
+        .fields
+        .iter()
+        .map(|field| {
+            [
+                code(format, &snake_to_camel(&field.name)),
+                type_label(types, &field.t, format),
+            ]
+        })
+        .collect::<Vec<[String; 2]>>();
+
+    table(format, ["Field", "Type"], &rows)
+}
+
+/// A default value written in Fen syntax.
+fn default_label(default: &DefaultValue) -> String {
+    match default {
+        DefaultValue::Bool(value) => value.to_string(),
+        DefaultValue::Number(value) | DefaultValue::Variant(value) => value.clone(),
+        DefaultValue::String(value) => format!("\"{value}\""),
+    }
+}
+
+fn enum_table(types: &Types, enum_def: &EnumDefinition, format: DocsFormat) -> String {
+    let rows = enum_def
+        .variants
+        .iter()
+        .map(|variant| {
+            [
+                code(format, &snake_to_camel(&variant.name)),
+                variant
+                    .t
+                    .as_ref()
+                    .map_or_else(String::new, |t| type_label(types, t, format)),
+            ]
+        })
+        .collect::<Vec<[String; 2]>>();
+
+    table(format, ["Type", "Value"], &rows)
+}
+
+/// A type written in Fen syntax, with helper and shared types linking to their definitions.
+fn type_label(types: &Types, t: &Type, format: DocsFormat) -> String {
+    match t {
+        Type::Named(name, _) => name_label(types, name, format),
+        Type::Generic(name, args, _) => {
+            let args = args
+                .iter()
+                .map(|t| type_label(types, t, format))
+                .collect::<Vec<String>>()
+                .join(" ");
+            match format {
+                DocsFormat::Markdown => format!("{}\\<{args}\\>", name_label(types, name, format)),
+                DocsFormat::Html => format!("{}&lt;{args}&gt;", name_label(types, name, format)),
+            }
+        }
+        Type::Optional(t, _) => format!("{}?", type_label(types, t, format)),
+        Type::Array(t, _) => match format {
+            DocsFormat::Markdown => format!("\\[{}\\]", type_label(types, t, format)),
+            DocsFormat::Html => format!("[{}]", type_label(types, t, format)),
+        },
+        Type::Map(k, v, _) => format!(
+            "{{{}: {}}}",
+            type_label(types, k, format),
+            type_label(types, v, format)
+        ),
+        Type::Primitive(p, _) => text(
+            format,
+            match p {
+                Primitive::Int => "Int",
+                Primitive::Int32 => "Int32",
+                Primitive::Int64 => "Int64",
+                Primitive::UInt => "UInt",
+                Primitive::Float => "Float",
+                Primitive::Decimal => "Decimal",
+                Primitive::String => "String",
+                Primitive::Bool => "Bool",
+                Primitive::Date => "Date",
+                Primitive::Uuid => "UUID",
+                Primitive::Bytes => "Bytes",
+            },
+        ),
+    }
+}
+
+/// A named type, linking to its definition if it's a helper or shared type (and not, say, a
+/// type parameter).
+fn name_label(types: &Types, name: &str, format: DocsFormat) -> String {
+    let anchor = pascal_to_kebab(name);
+    if is_local(types, name) {
+        link(format, name, &format!("#{anchor}"))
+    } else if types.find_struct(name).is_some() || types.find_enum(name).is_some() {
+        link(format, name, &format!("{}#{anchor}", shared_file(format)))
+    } else {
+        text(format, name)
+    }
+}
+
+fn is_local(types: &Types, name: &str) -> bool {
+    types.structs.iter().any(|s| s.name == name) || types.enums.iter().any(|e| e.name == name)
+}
+
+fn io_example(types: &Types, io: &IOType) -> Value {
+    match io {
+        IOType::Type(t) => type_example(types, t, &mut vec![]),
+        IOType::Struct(s) => struct_example(types, s, &mut vec![]),
+        IOType::Enum(e) => enum_example(types, e, &mut vec![]),
+    }
+}
+
+/// `visiting` holds the named types currently being expanded, so that recursive types
+/// bottom out at `null` instead of looping forever.
+fn type_example(types: &Types, t: &Type, visiting: &mut Vec<String>) -> Value {
+    match t {
+        Type::Named(name, _) => named_example(types, name, &[], visiting),
+        Type::Generic(name, args, _) => named_example(types, name, args, visiting),
+        Type::Optional(t, _) => type_example(types, t, visiting),
+        Type::Array(t, _) => json!([type_example(types, t, visiting)]),
+        Type::Map(k, v, _) => {
+            // keys are always encoded as strings
+            let key = match type_example(types, k, visiting) {
+                Value::String(key) => key,
+                key => key.to_string(),
+            };
+            let mut object = Map::new();
+            object.insert(key, type_example(types, v, visiting));
+            Value::Object(object)
+        }
+        Type::Primitive(p, _) => match p {
+            Primitive::Int | Primitive::Int32 | Primitive::Int64 | Primitive::UInt => json!(0),
+            Primitive::Float => json!(0.0),
+            Primitive::Decimal => json!("0.00"),
+            Primitive::String => json!("string"),
+            Primitive::Bool => json!(true),
+            Primitive::Date => json!("2024-01-01T00:00:00Z"),
+            Primitive::Uuid => json!("00000000-0000-0000-0000-000000000000"),
+            Primitive::Bytes => json!("aGVsbG8="),
+        },
+    }
+}
+
+/// An example of a helper or shared type, with any type parameters replaced by `args`.
+fn named_example(types: &Types, name: &str, args: &[Type], visiting: &mut Vec<String>) -> Value {
+    if visiting.iter().any(|visited| visited == name) {
+        return Value::Null;
+    }
+    visiting.push(name.to_string());
+    let example = if let Some(s) = types.find_struct(name) {
+        struct_example(types, &s.instantiate(name, args), visiting)
+    } else if let Some(e) = types.find_enum(name) {
+        enum_example(types, &e.instantiate(name, args), visiting)
+    } else {
+        Value::Null
+    };
+    visiting.pop();
+    example
+}
+
+fn struct_example(
+    types: &Types,
+    struct_def: &StructDefinition,
+    visiting: &mut Vec<String>,
+) -> Value {
+    let mut object = Map::new();
+    for field in &struct_def.fields {
+        object.insert(
+            snake_to_camel(&field.name),
+            type_example(types, &field.t, visiting),
+        );
+    }
+
+    Value::Object(object)
+}
+
+fn enum_example(types: &Types, enum_def: &EnumDefinition, visiting: &mut Vec<String>) -> Value {
+    // the first variant stands in for the rest
+    enum_def.variants.first().map_or(Value::Null, |variant| {
+        let tag = snake_to_camel(&variant.name);
+        variant.t.as_ref().map_or_else(
+            || json!({ "type": tag }),
+            |t| json!({ "type": tag, "value": type_example(types, t, visiting) }),
+        )
+    })
+}
+
+const fn index_file(format: DocsFormat) -> &'static str {
+    match format {
+        DocsFormat::Markdown => "README.md",
+        DocsFormat::Html => "index.html",
+    }
+}
+
+fn shared_file(format: DocsFormat) -> String {
+    format!("shared.{}", extension(format))
+}
+
+const fn extension(format: DocsFormat) -> &'static str {
+    match format {
+        DocsFormat::Markdown => "md",
+        DocsFormat::Html => "html",
+    }
+}
+
+// format-specific building blocks:
+
+fn page(format: DocsFormat, title: &str, body: &str) -> String {
+    match format {
+        DocsFormat::Markdown => format!("{body}\n"),
+        DocsFormat::Html => format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+body {{ font-family: -apple-system, system-ui, sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; color: #222; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #ddd; padding: 0.25rem 0.75rem; text-align: left; }}
+pre {{ background: #f6f8fa; padding: 1rem; overflow-x: auto; }}
+</style>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+            escape_html(title)
+        ),
+    }
+}
+
+fn heading(format: DocsFormat, level: usize, title: &str) -> String {
+    match format {
+        DocsFormat::Markdown => format!("{} {}", "#".repeat(level), title),
+        DocsFormat::Html => format!("<h{level}>{}</h{level}>", escape_html(title)),
+    }
+}
+
+/// A heading that helper type links can point at.
+fn anchored_heading(format: DocsFormat, level: usize, title: &str) -> String {
+    let id = pascal_to_kebab(title);
+    match format {
+        DocsFormat::Markdown => format!("{} <a id=\"{id}\"></a>{title}", "#".repeat(level)),
+        DocsFormat::Html => format!("<h{level} id=\"{id}\">{}</h{level}>", escape_html(title)),
+    }
+}
+
+fn paragraph(format: DocsFormat, content: &str) -> String {
+    match format {
+        DocsFormat::Markdown => content.to_string(),
+        DocsFormat::Html => format!("<p>{content}</p>"),
+    }
+}
+
+fn text(format: DocsFormat, content: &str) -> String {
+    match format {
+        DocsFormat::Markdown => content.replace('|', "\\|"),
+        DocsFormat::Html => escape_html(content),
+    }
+}
+
+fn code(format: DocsFormat, content: &str) -> String {
+    match format {
+        DocsFormat::Markdown => format!("`{content}`"),
+        DocsFormat::Html => format!("<code>{}</code>", escape_html(content)),
+    }
+}
+
+fn link(format: DocsFormat, label: &str, href: &str) -> String {
+    match format {
+        DocsFormat::Markdown => format!("[{label}]({href})"),
+        DocsFormat::Html => format!("<a href=\"{href}\">{}</a>", escape_html(label)),
+    }
+}
+
+/// A block of newline-delimited JSON (so one compact value per line).
+fn lines_block(format: DocsFormat, values: &[Value]) -> String {
+    let lines = values
+        .iter()
+        .map(|value| serde_json::to_string(value).unwrap_or_default())
+        .collect::<Vec<String>>()
+        .join("\n");
+    match format {
+        DocsFormat::Markdown => format!("```json\n{lines}\n```"),
+        DocsFormat::Html => format!("<pre><code>{}</code></pre>", escape_html(&lines)),
+    }
+}
+
+fn json_block(format: DocsFormat, value: &Value) -> String {
+    let json = serde_json::to_string_pretty(value).unwrap_or_default();
+    match format {
+        DocsFormat::Markdown => format!("```json\n{json}\n```"),
+        DocsFormat::Html => format!("<pre><code>{}</code></pre>", escape_html(&json)),
+    }
+}
+
+fn table<const N: usize>(format: DocsFormat, headers: [&str; N], rows: &[[String; N]]) -> String {
+    match format {
+        DocsFormat::Markdown => {
+            let mut lines = vec![
+                format!("| {} |", headers.join(" | ")),
+                format!("|{}", "---|".repeat(N)),
+            ];
+            for row in rows {
+                lines.push(format!("| {} |", row.join(" | ")));
+            }
+            lines.join("\n")
+        }
+        DocsFormat::Html => {
+            let mut lines = vec!["<table>".to_string()];
+            if headers.iter().any(|header| !header.is_empty()) {
+                let cells = headers
+                    .iter()
+                    .map(|header| format!("<th>{}</th>", escape_html(header)))
+                    .collect::<Vec<String>>();
+                lines.push(format!("<tr>{}</tr>", cells.concat()));
+            }
+            for row in rows {
+                let cells = row
+                    .iter()
+                    .map(|cell| format!("<td>{cell}</td>"))
+                    .collect::<Vec<String>>();
+                lines.push(format!("<tr>{}</tr>", cells.concat()));
+            }
+            lines.push("</table>".to_string());
+            lines.join("\n")
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{docs_pages, DocsFormat};
+    use crate::ast::SharedTypes;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_page(fen_code: &str, format: DocsFormat, page: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let pages = docs_pages(&[&ast], &SharedTypes::default(), format);
+        assert_eq!(pages[1].1.trim(), page);
+    }
+
+    #[test]
+    fn index_links_every_route() {
+        let mut get_todos = Parser::new(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+        );
+        let mut toggle = Parser::new(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+        );
+        let get_todos = get_todos.parse().unwrap();
+        let toggle = toggle.parse().unwrap();
+
+        let pages = docs_pages(
+            &[&toggle, &get_todos],
+            &SharedTypes::default(),
+            DocsFormat::Markdown,
+        );
+        let names = pages
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            names,
+            vec!["README.md", "get-todos.md", "toggle-todo-completion.md"]
+        );
+        assert_eq!(
+            pages[0].1.trim(),
+            r"
+# API Reference
+
+| Route | Method | Path | Description |
+|---|---|---|---|
+| [GetTodos](get-todos.md) | `GET` | `/_fen_/get-todos` | Fetches all todos |
+| [ToggleTodoCompletion](toggle-todo-completion.md) | `POST` | `/_fen_/toggle-todo-completion` | Completes or uncompletes a todo |
+            "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn markdown_route_with_helpers() {
+        expect_page(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            DocsFormat::Markdown,
+            r#"
+# GetTodos
+
+Fetches all todos
+
+|  |  |
+|---|---|
+| Method | `GET` |
+| Path | `/_fen_/get-todos` |
+| Auth | Required (`Authorization: Bearer <token>`) |
+
+## Input
+
+None
+
+## Output
+
+\[[Todo](#todo)\]
+
+Example response:
+
+```json
+{
+  "type": "success",
+  "value": [
+    {
+      "id": "00000000-0000-0000-0000-000000000000",
+      "name": "string",
+      "description": "string",
+      "due": "2024-01-01T00:00:00Z",
+      "isCompleted": true
+    }
+  ]
+}
+```
+
+Failures are returned as:
+
+```json
+{
+  "type": "failure",
+  "message": "Something went wrong",
+  "status": 500
+}
+```
+
+## Types
+
+### <a id="todo"></a>Todo
+
+| Field | Type |
+|---|---|
+| `id` | UUID |
+| `name` | String |
+| `description` | String? |
+| `due` | Date? |
+| `isCompleted` | Bool |
+
+[Back to all routes](README.md)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn html_route_with_primitive_input() {
+        expect_page(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            DocsFormat::Html,
+            r#"
+<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>ToggleTodoCompletion</title>
+<style>
+body { font-family: -apple-system, system-ui, sans-serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ddd; padding: 0.25rem 0.75rem; text-align: left; }
+pre { background: #f6f8fa; padding: 1rem; overflow-x: auto; }
+</style>
+</head>
+<body>
+<h1>ToggleTodoCompletion</h1>
+
+<p>Completes or uncompletes a todo</p>
+
+<table>
+<tr><td>Method</td><td><code>POST</code></td></tr>
+<tr><td>Path</td><td><code>/_fen_/toggle-todo-completion</code></td></tr>
+<tr><td>Auth</td><td>Required (<code>Authorization: Bearer &lt;token&gt;</code>)</td></tr>
+</table>
+
+<h2>Input</h2>
+
+<p>UUID</p>
+
+<p>Example request body:</p>
+
+<pre><code>&quot;00000000-0000-0000-0000-000000000000&quot;</code></pre>
+
+<h2>Output</h2>
+
+<p>None</p>
+
+<p>Example response:</p>
+
+<pre><code>{
+  &quot;type&quot;: &quot;success&quot;,
+  &quot;value&quot;: null
+}</code></pre>
+
+<p>Failures are returned as:</p>
+
+<pre><code>{
+  &quot;type&quot;: &quot;failure&quot;,
+  &quot;message&quot;: &quot;Something went wrong&quot;,
+  &quot;status&quot;: 500
+}</code></pre>
+
+<p><a href="index.html">Back to all routes</a></p>
+</body>
+</html>
+            "#
+            .trim(),
+        );
+    }
+}
@@ -0,0 +1,701 @@
+use serde_json::{json, Map, Value};
+
+use super::name_transforms::snake_to_camel;
+use crate::ast::{
+    Constraint, DefaultValue, EnumDefinition, FileNode, IOType, Primitive, SharedTypes,
+    StructDefinition, Type,
+};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+const DEFS: &str = "#/$defs/";
+
+/// Builds a JSON Schema (draft 2020-12) for a route's input, if it has one.
+#[must_use]
+pub fn input_schema(route: &FileNode, shared: &SharedTypes) -> Option<Value> {
+    route.input.as_ref().map(|input| {
+        document(
+            route,
+            shared,
+            &format!("{}Input", route.name),
+            io_schema(input),
+        )
+    })
+}
+
+/// Builds a JSON Schema (draft 2020-12) for a route's response, which wraps its output (or
+/// `null`, if it has none) in the success/failure envelope, along with its `@error` type if it
+/// has one.
+///
+/// For a streaming route, it's the schema of each line instead (an event, a failure, or the end).
+#[must_use]
+pub fn output_schema(route: &FileNode, shared: &SharedTypes) -> Value {
+    let value = route
+        .output
+        .as_ref()
+        .map_or_else(|| json!({ "type": "null" }), io_schema);
+
+    let mut variants = vec![json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": if route.stream { "event" } else { "success" } },
+            "value": value,
+        },
+        "required": ["type", "value"],
+    })];
+    if let Some(error) = &route.error {
+        variants.push(json!({
+            "type": "object",
+            "properties": {
+                "type": { "const": "error" },
+                "value": io_schema(error),
+            },
+            "required": ["type", "value"],
+        }));
+    }
+    variants.push(json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": "failure" },
+            "message": { "type": "string" },
+            "status": { "type": "integer" },
+        },
+        "required": ["type", "message", "status"],
+    }));
+    if route.stream {
+        variants.push(json!({
+            "type": "object",
+            "properties": {
+                "type": { "const": "end" },
+            },
+            "required": ["type"],
+        }));
+    }
+
+    document(
+        route,
+        shared,
+        &format!("{}Output", route.name),
+        json!({ "oneOf": variants }),
+    )
+}
+
+/// Wraps a root schema with the draft identifier, a title, and the route's helper types
+/// (including the shared types it imports).
+fn document(route: &FileNode, shared: &SharedTypes, title: &str, schema: Value) -> Value {
+    let mut document = Map::new();
+    document.insert("$schema".to_string(), Value::String(DRAFT.to_string()));
+    document.insert("title".to_string(), Value::String(title.to_string()));
+    if let Value::Object(schema) = schema {
+        document.extend(schema);
+    }
+
+    let imported = imported_types(route, shared);
+    let structs = route
+        .structs
+        .iter()
+        .chain(shared.structs.iter().filter(|s| imported.contains(&s.name)))
+        .collect::<Vec<&StructDefinition>>();
+    let enums = route
+        .enums
+        .iter()
+        .chain(shared.enums.iter().filter(|e| imported.contains(&e.name)))
+        .collect::<Vec<&EnumDefinition>>();
+
+    let mut defs = Map::new();
+    let mut types = vec![];
+    types.extend(route.input.iter().flat_map(io_types));
+    types.extend(route.output.iter().flat_map(io_types));
+    types.extend(route.error.iter().flat_map(io_types));
+    // generic types are only defined once instantiated
+    for struct_def in structs.iter().filter(|s| s.generics.is_empty()) {
+        defs.insert(struct_def.name.clone(), struct_schema(struct_def, DEFS));
+        types.extend(struct_def.fields.iter().map(|field| &field.t));
+    }
+    for enum_def in enums.iter().filter(|e| e.generics.is_empty()) {
+        defs.insert(enum_def.name.clone(), enum_schema(enum_def, DEFS));
+        types.extend(enum_def.variants.iter().filter_map(|v| v.t.as_ref()));
+    }
+    add_instance_schemas(&mut defs, &types, &structs, &enums, DEFS);
+    if !defs.is_empty() {
+        document.insert("$defs".to_string(), Value::Object(defs));
+    }
+
+    Value::Object(document)
+}
+
+/// The shared types a route imports, plus any shared types those refer to.
+fn imported_types(route: &FileNode, shared: &SharedTypes) -> Vec<String> {
+    let mut found = vec![];
+    let mut pending = route.imports.clone();
+    while let Some(name) = pending.pop() {
+        if found.contains(&name) {
+            continue;
+        }
+
+        if let Some(struct_def) = shared.structs.iter().find(|s| s.name == name) {
+            for field in &struct_def.fields {
+                pending.extend(named_types(&field.t));
+            }
+        }
+        if let Some(enum_def) = shared.enums.iter().find(|e| e.name == name) {
+            for t in enum_def.variants.iter().filter_map(|v| v.t.as_ref()) {
+                pending.extend(named_types(t));
+            }
+        }
+        found.push(name);
+    }
+
+    found.sort();
+    found
+}
+
+fn named_types(t: &Type) -> Vec<String> {
+    match t {
+        Type::Named(name, _) => vec![name.clone()],
+        Type::Optional(t, _) | Type::Array(t, _) | Type::Map(_, t, _) => named_types(t),
+        Type::Primitive(..) => vec![],
+        Type::Generic(name, args, _) => {
+            let mut names = vec![name.clone()];
+            names.extend(args.iter().flat_map(named_types));
+            names
+        }
+    }
+}
+
+/// The types an input, output, or error refers to.
+pub(crate) fn io_types(io: &IOType) -> Vec<&Type> {
+    match io {
+        IOType::Type(t) => vec![t],
+        IOType::Struct(s) => s.fields.iter().map(|field| &field.t).collect(),
+        IOType::Enum(e) => e.variants.iter().filter_map(|v| v.t.as_ref()).collect(),
+    }
+}
+
+/// JSON Schema has no generics, so each instantiation of a generic type that `types` refer to
+/// (directly, or through other instantiations) gets a definition of its own.
+pub(crate) fn add_instance_schemas(
+    schemas: &mut Map<String, Value>,
+    types: &[&Type],
+    structs: &[&StructDefinition],
+    enums: &[&EnumDefinition],
+    refs: &str,
+) {
+    let mut pending = types
+        .iter()
+        .flat_map(|t| instances(t))
+        .collect::<Vec<(String, Vec<Type>)>>();
+    while let Some((name, args)) = pending.pop() {
+        let instance = instance_name(&name, &args);
+        if schemas.contains_key(&instance) {
+            continue;
+        }
+
+        if let Some(struct_def) = structs.iter().find(|s| s.name == name) {
+            let struct_def = struct_def.instantiate(&instance, &args);
+            for field in &struct_def.fields {
+                pending.extend(instances(&field.t));
+            }
+            schemas.insert(instance, struct_schema(&struct_def, refs));
+        } else if let Some(enum_def) = enums.iter().find(|e| e.name == name) {
+            let enum_def = enum_def.instantiate(&instance, &args);
+            for t in enum_def.variants.iter().filter_map(|v| v.t.as_ref()) {
+                pending.extend(instances(t));
+            }
+            schemas.insert(instance, enum_schema(&enum_def, refs));
+        }
+    }
+}
+
+/// The instantiations of generic types within `t` (like the `Page<User>` in `[Page<User>]`).
+fn instances(t: &Type) -> Vec<(String, Vec<Type>)> {
+    match t {
+        Type::Generic(name, args, _) => {
+            let mut found = vec![(name.clone(), args.clone())];
+            found.extend(args.iter().flat_map(instances));
+            found
+        }
+        Type::Optional(t, _) | Type::Array(t, _) | Type::Map(_, t, _) => instances(t),
+        Type::Named(..) | Type::Primitive(..) => vec![],
+    }
+}
+
+/// The definition name for an instantiation of a generic type (like `PageOfUser`, for
+/// `Page<User>`).
+fn instance_name(name: &str, args: &[Type]) -> String {
+    format!(
+        "{name}Of{}",
+        args.iter()
+            .map(type_name)
+            .collect::<Vec<String>>()
+            .join("And")
+    )
+}
+
+fn type_name(t: &Type) -> String {
+    match t {
+        Type::Named(name, _) => name.clone(),
+        Type::Optional(t, _) => format!("Optional{}", type_name(t)),
+        Type::Array(t, _) => format!("{}List", type_name(t)),
+        Type::Map(k, v, _) => format!("{}To{}Map", type_name(k), type_name(v)),
+        Type::Primitive(p, _) => format!("{p:?}"),
+        Type::Generic(name, args, _) => instance_name(name, args),
+    }
+}
+
+fn io_schema(io: &IOType) -> Value {
+    match io {
+        IOType::Type(t) => type_schema(t, DEFS),
+        IOType::Struct(s) => struct_schema(s, DEFS),
+        IOType::Enum(e) => enum_schema(e, DEFS),
+    }
+}
+
+/// The schema for a struct, with named types referenced as `{refs}{name}`.
+pub(crate) fn struct_schema(struct_def: &StructDefinition, refs: &str) -> Value {
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for field in &struct_def.fields {
+        let name = snake_to_camel(&field.name);
+        let mut schema = type_schema(&field.t, refs);
+        if let Value::Object(schema) = &mut schema {
+            if let Some(default) = &field.default {
+                schema.insert("default".to_string(), default_value(default));
+            }
+            for constraint in &field.constraints {
+                let (keyword, value) = constraint_keyword(&field.t, constraint);
+                // `@nonEmpty` is the loosest minimum, so it never replaces a `@minLength`
+                if *constraint == Constraint::NonEmpty {
+                    schema.entry(keyword).or_insert(value);
+                } else {
+                    schema.insert(keyword.to_string(), value);
+                }
+            }
+        }
+        properties.insert(name.clone(), schema);
+        // missing optionals decode as `null`, and missing defaulted fields as their default
+        if !matches!(field.t, Type::Optional(..)) && field.default.is_none() {
+            required.push(Value::String(name));
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+/// A field's default value, as it's encoded.
+fn default_value(default: &DefaultValue) -> Value {
+    match default {
+        DefaultValue::Bool(value) => Value::Bool(*value),
+        DefaultValue::Number(value) => value.parse::<i64>().map_or_else(
+            |_| json!(value.parse::<f64>().unwrap_or_default()),
+            Value::from,
+        ),
+        DefaultValue::String(value) => Value::String(value.clone()),
+        DefaultValue::Variant(variant) => json!({ "type": snake_to_camel(variant) }),
+    }
+}
+
+/// The keyword (and its value) that expresses a constraint on a field of type `t`.
+fn constraint_keyword(t: &Type, constraint: &Constraint) -> (&'static str, Value) {
+    let t = match t {
+        Type::Optional(t, _) => t,
+        t => t,
+    };
+    let is_string = matches!(*t, Type::Primitive(Primitive::String, _));
+    match constraint {
+        Constraint::Min(n) => ("minimum", number_value(n)),
+        Constraint::Max(n) => ("maximum", number_value(n)),
+        Constraint::MinLength(n) if is_string => ("minLength", json!(n)),
+        Constraint::MinLength(n) => ("minItems", json!(n)),
+        Constraint::MaxLength(n) if is_string => ("maxLength", json!(n)),
+        Constraint::MaxLength(n) => ("maxItems", json!(n)),
+        Constraint::Pattern(pattern) => ("pattern", json!(pattern)),
+        Constraint::NonEmpty if is_string => ("minLength", json!(1)),
+        Constraint::NonEmpty if matches!(t, Type::Map(..)) => ("minProperties", json!(1)),
+        Constraint::NonEmpty => ("minItems", json!(1)),
+    }
+}
+
+/// A number literal (kept as written in the AST) as a JSON number.
+fn number_value(n: &str) -> Value {
+    n.parse::<i64>()
+        .map_or_else(|_| json!(n.parse::<f64>().unwrap_or_default()), Value::from)
+}
+
+/// The schema for an enum: one object per variant, tagged by `type` (holding any `value`).
+pub(crate) fn enum_schema(enum_def: &EnumDefinition, refs: &str) -> Value {
+    let variants = enum_def
+        .variants
+        .iter()
+        .map(|variant| {
+            let tag = json!({ "const": snake_to_camel(&variant.name) });
+            variant.t.as_ref().map_or_else(
+                || {
+                    json!({
+                        "type": "object",
+                        "properties": { "type": tag },
+                        "required": ["type"],
+                    })
+                },
+                |t| {
+                    json!({
+                        "type": "object",
+                        "properties": { "type": tag, "value": type_schema(t, refs) },
+                        "required": ["type", "value"],
+                    })
+                },
+            )
+        })
+        .collect::<Vec<Value>>();
+
+    json!({ "oneOf": variants })
+}
+
+pub(crate) fn type_schema(t: &Type, refs: &str) -> Value {
+    match t {
+        Type::Named(name, _) => json!({ "$ref": format!("{refs}{name}") }),
+        Type::Generic(name, args, _) => {
+            json!({ "$ref": format!("{refs}{}", instance_name(name, args)) })
+        }
+        Type::Optional(t, _) => json!({ "anyOf": [type_schema(t, refs), { "type": "null" }] }),
+        Type::Array(t, _) => json!({ "type": "array", "items": type_schema(t, refs) }),
+        // JSON object keys are always strings, so `Int` and `UUID` keys are constrained by format
+        Type::Map(k, v, _) => match k.as_ref() {
+            Type::Primitive(Primitive::Int, _) => json!({
+                "type": "object",
+                "propertyNames": { "pattern": "^-?[0-9]+$" },
+                "additionalProperties": type_schema(v, refs),
+            }),
+            Type::Primitive(Primitive::Uuid, _) => json!({
+                "type": "object",
+                "propertyNames": { "format": "uuid" },
+                "additionalProperties": type_schema(v, refs),
+            }),
+            _ => json!({ "type": "object", "additionalProperties": type_schema(v, refs) }),
+        },
+        Type::Primitive(p, _) => match p {
+            Primitive::Int | Primitive::Int64 => json!({ "type": "integer", "format": "int64" }),
+            Primitive::Int32 => json!({ "type": "integer", "format": "int32" }),
+            Primitive::UInt => json!({ "type": "integer", "minimum": 0 }),
+            Primitive::Float => json!({ "type": "number", "format": "double" }),
+            Primitive::Decimal => json!({ "type": "string", "pattern": "^-?[0-9]+(\\.[0-9]+)?$" }),
+            Primitive::String => json!({ "type": "string" }),
+            Primitive::Bool => json!({ "type": "boolean" }),
+            Primitive::Date => json!({ "type": "string", "format": "date-time" }),
+            Primitive::Uuid => json!({ "type": "string", "format": "uuid" }),
+            Primitive::Bytes => json!({ "type": "string", "contentEncoding": "base64" }),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_schema, output_schema};
+    use crate::ast::SharedTypes;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn struct_input_with_helpers() {
+        let mut parser = Parser::new(
+            r#"
+name: "EnumTest"
+authed: true
+
+---
+
+@input {
+  ids: [UUID]
+  since: Date?
+  job: Job
+}
+
+---
+
+Job (
+  developer
+  other(String?)
+)
+            "#
+            .trim(),
+        );
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            input_schema(&ast, &SharedTypes::default()),
+            Some(json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "EnumTestInput",
+                "type": "object",
+                "properties": {
+                    "ids": {
+                        "type": "array",
+                        "items": { "type": "string", "format": "uuid" },
+                    },
+                    "since": {
+                        "anyOf": [
+                            { "type": "string", "format": "date-time" },
+                            { "type": "null" },
+                        ],
+                    },
+                    "job": { "$ref": "#/$defs/Job" },
+                },
+                "required": ["ids", "job"],
+                "$defs": {
+                    "Job": {
+                        "oneOf": [
+                            {
+                                "type": "object",
+                                "properties": { "type": { "const": "developer" } },
+                                "required": ["type"],
+                            },
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "type": { "const": "other" },
+                                    "value": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                                },
+                                "required": ["type", "value"],
+                            },
+                        ],
+                    },
+                },
+            })),
+        );
+    }
+
+    #[test]
+    fn no_input_and_primitive_output() {
+        let mut parser = Parser::new(
+            r#"
+name: "GetCount"
+authed: false
+
+---
+
+@output Int
+            "#
+            .trim(),
+        );
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(input_schema(&ast, &SharedTypes::default()), None);
+        assert_eq!(
+            output_schema(&ast, &SharedTypes::default()),
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "GetCountOutput",
+                "oneOf": [
+                    {
+                        "type": "object",
+                        "properties": {
+                            "type": { "const": "success" },
+                            "value": { "type": "integer", "format": "int64" },
+                        },
+                        "required": ["type", "value"],
+                    },
+                    {
+                        "type": "object",
+                        "properties": {
+                            "type": { "const": "failure" },
+                            "message": { "type": "string" },
+                            "status": { "type": "integer" },
+                        },
+                        "required": ["type", "message", "status"],
+                    },
+                ],
+            }),
+        );
+    }
+
+    #[test]
+    fn map_input() {
+        let mut parser = Parser::new(
+            r#"
+name: "SetCounts"
+authed: false
+
+---
+
+@input {UUID: Int}
+            "#
+            .trim(),
+        );
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            input_schema(&ast, &SharedTypes::default()),
+            Some(json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "SetCountsInput",
+                "type": "object",
+                "propertyNames": { "format": "uuid" },
+                "additionalProperties": { "type": "integer", "format": "int64" },
+            })),
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let mut parser = Parser::new(
+            r#"
+name: "SearchTodos"
+authed: false
+
+---
+
+@input {
+  query: String
+  limit: Int = 20
+  sort: SortOrder = newest
+}
+
+---
+
+SortOrder (
+  newest
+  oldest
+)
+            "#
+            .trim(),
+        );
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            input_schema(&ast, &SharedTypes::default()),
+            Some(json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "SearchTodosInput",
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "format": "int64", "default": 20 },
+                    "sort": { "$ref": "#/$defs/SortOrder", "default": { "type": "newest" } },
+                },
+                "required": ["query"],
+                "$defs": {
+                    "SortOrder": {
+                        "oneOf": [
+                            {
+                                "type": "object",
+                                "properties": { "type": { "const": "newest" } },
+                                "required": ["type"],
+                            },
+                            {
+                                "type": "object",
+                                "properties": { "type": { "const": "oldest" } },
+                                "required": ["type"],
+                            },
+                        ],
+                    },
+                },
+            })),
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        let mut parser = Parser::new(
+            r#"
+name: "CreateUser"
+authed: false
+
+---
+
+@input {
+  @nonEmpty
+  @pattern("^[a-z]+$")
+  @maxLength(80)
+  name: String
+  @min(1) @max(100)
+  age: Int?
+  @nonEmpty
+  tags: [String]
+}
+            "#
+            .trim(),
+        );
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            input_schema(&ast, &SharedTypes::default()),
+            Some(json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "title": "CreateUserInput",
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "minLength": 1,
+                        "pattern": "^[a-z]+$",
+                        "maxLength": 80,
+                    },
+                    "age": {
+                        "anyOf": [{ "type": "integer", "format": "int64" }, { "type": "null" }],
+                        "minimum": 1,
+                        "maximum": 100,
+                    },
+                    "tags": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "minItems": 1,
+                    },
+                },
+                "required": ["name", "tags"],
+            })),
+        );
+    }
+
+    #[test]
+    fn generic_instances() {
+        let mut parser = Parser::new(
+            r#"
+name: "GetUsers"
+authed: false
+
+---
+
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+}
+
+User {
+  id: UUID
+}
+            "#
+            .trim(),
+        );
+        let ast = parser.parse().unwrap();
+
+        assert_eq!(
+            output_schema(&ast, &SharedTypes::default())["$defs"],
+            json!({
+                "PageOfUser": {
+                    "type": "object",
+                    "properties": {
+                        "items": {
+                            "type": "array",
+                            "items": { "$ref": "#/$defs/User" },
+                        },
+                    },
+                    "required": ["items"],
+                },
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string", "format": "uuid" },
+                    },
+                    "required": ["id"],
+                },
+            }),
+        );
+    }
+}
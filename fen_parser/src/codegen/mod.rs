@@ -1,11 +1,14 @@
 use crate::ast::{
-    EnumDefinition, Field, FileNode, IOType, Primitive, StructDefinition, Type, Variant,
-};
-use name_transforms::{
-    pascal_to_camel, pascal_to_kebab, pascal_to_snake, snake_to_camel, snake_to_pascal,
+    Constraint, DefaultValue, EnumDefinition, Field, FileNode, IOType, Method, ParamLocation,
+    Primitive, SharedTypes, StructDefinition, Type, Variant,
 };
+use name_transforms::{pascal_to_camel, pascal_to_snake, snake_to_camel, snake_to_pascal};
 
+pub mod docs;
+pub mod json_schema;
 pub mod name_transforms;
+pub mod openapi;
+pub mod rust_router;
 
 pub struct Context {
     pub override_name: Option<String>,
@@ -14,11 +17,33 @@ pub struct Context {
 pub trait GenCode {
     // client
     fn swift_client_code(&self, ctx: &Context) -> String;
+    fn typescript_client_code(&self, ctx: &Context) -> String;
+    fn kotlin_client_code(&self, ctx: &Context) -> String;
+    fn python_client_code(&self, ctx: &Context) -> String;
+    fn go_client_code(&self, ctx: &Context) -> String;
+
+    /// Helper types are shared with the server, so by default this is the same as
+    /// `rust_server_code`.
+    fn rust_client_code(&self, ctx: &Context) -> String {
+        self.rust_server_code(ctx)
+    }
 
     // server
     fn rust_server_code(&self, _ctx: &Context) -> String {
         unimplemented!() // TODO: remove this (should be required)
     }
+
+    /// Types are encoded the same way on both ends, so by default this is the same as
+    /// `swift_client_code`.
+    fn swift_server_code(&self, ctx: &Context) -> String {
+        self.swift_client_code(ctx)
+    }
+
+    /// Types are encoded the same way on both ends, so by default this is the same as
+    /// `go_client_code`.
+    fn go_server_code(&self, ctx: &Context) -> String {
+        self.go_client_code(ctx)
+    }
 }
 
 impl GenCode for FileNode {
@@ -32,6 +57,10 @@ impl GenCode for FileNode {
                 IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Output",
             },
         );
+        let error_type_name = self.error.as_ref().map(|error| match error {
+            IOType::Type(t) => t.swift_client_code(ctx),
+            IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Error",
+        });
 
         // open the extension
         let mut lines = vec!["extension APIClient {".to_string()];
@@ -40,6 +69,9 @@ impl GenCode for FileNode {
         if let Some(description) = &self.description {
             lines.push(format!("  /// {description}"));
         }
+        if let Some(message) = &self.deprecated {
+            lines.push(format!("  {}", swift_deprecated(message)));
+        }
 
         // declare the function
         let mut func_decl = format!("  func {}(", pascal_to_camel(&self.name));
@@ -48,15 +80,28 @@ impl GenCode for FileNode {
         if let Some(input) = &self.input {
             match input {
                 IOType::Type(t) => {
-                    func_decl.push_str(&format!("input: {}", t.swift_client_code(ctx)));
+                    func_decl.push_str("input: ");
+                    func_decl.push_str(&t.swift_client_code(ctx));
                 }
                 IOType::Enum(_) => {
-                    func_decl.push_str(&format!("input: {}", self.name.clone() + "Input"));
+                    func_decl.push_str("input: ");
+                    func_decl.push_str(&(self.name.clone() + "Input"));
                 }
                 IOType::Struct(s) => {
                     let mut args_str = vec![];
-                    for Field { name, t } in &s.fields {
-                        args_str.push(format!("{}: {}", name, t.swift_client_code(ctx)));
+                    for Field {
+                        name, t, default, ..
+                    } in &s.fields
+                    {
+                        args_str.push(format!(
+                            "{}: {}{}",
+                            name,
+                            t.swift_client_code(ctx),
+                            default.as_ref().map_or_else(String::new, |default| format!(
+                                " = {}",
+                                swift_default(default)
+                            ))
+                        ));
                     }
                     func_decl.push_str(&args_str.join(", "));
                 }
@@ -70,63 +115,158 @@ impl GenCode for FileNode {
             func_decl.push_str("sessionToken: String");
         }
 
-        func_decl.push_str(") async throws -> Response<");
-
-        // function return type (derived from output)
-        func_decl.push_str(format!("{return_type_name}> {{").as_str());
+        // function return type (derived from output and error, and streams don't throw until
+        // they're read)
+        let response_type = if self.stream {
+            format!(") -> AsyncThrowingStream<{return_type_name}, Error>")
+        } else {
+            error_type_name.as_ref().map_or_else(
+                || format!(") async throws -> Response<{return_type_name}>"),
+                |error_type_name| {
+                    format!(
+                        ") async throws -> ResponseWithError<{return_type_name}, {error_type_name}>"
+                    )
+                },
+            )
+        };
+        func_decl.push_str(format!("{response_type} {{").as_str());
 
         // add the function declaration to the lines
         lines.push(func_decl);
 
-        // return statement
-        lines.push(format!(
-            "    return try await self.fetcher.{}",
-            if self.input.is_some() {
-                "post(".to_string()
+        // check the input's constraints first, if the client asks for it
+        let validates =
+            matches!(&self.input, Some(IOType::Struct(s)) if has_constraints(&s.fields));
+        if let (true, Some(IOType::Struct(s))) = (validates, &self.input) {
+            lines.push(format!(
+                "    let input = {}",
+                swift_input_payload(&self.name, &s.fields)
+            ));
+            let failure = if self.stream {
+                "AsyncThrowingStream { $0.finish(throwing: StreamError.failure(message: errors.map(\\.description).joined(separator: \"; \"), status: 422)) }".to_string()
             } else {
                 format!(
-                    "get(from: \"/_fen_/{}\"{})",
-                    pascal_to_kebab(&self.name),
-                    if self.authed {
-                        ", sessionToken: sessionToken"
-                    } else {
-                        ", sessionToken: nil"
-                    }
+                    ".{}(message: errors.map(\\.description).joined(separator: \"; \"), status: 422)",
+                    if self.error.is_some() { "genericFailure" } else { "failure" }
                 )
-            }
-        ));
+            };
+            lines.extend([
+                "    if self.validatesInput {".to_string(),
+                "      let errors = input.validate()".to_string(),
+                "      if !errors.isEmpty {".to_string(),
+                format!("        return {failure}"),
+                "      }".to_string(),
+                "    }".to_string(),
+            ]);
+        }
 
-        // return statement body (for post requests)
-        if self.input.is_some() {
-            // add the path
+        // return statement
+        let mut path = format!(
+            "\"{}\"",
+            interpolate_path(self, |param| format!("\\(APIClient.pathSegment({param}))"))
+        );
+        let query = self.fields_in(ParamLocation::Query);
+        if !query.is_empty() {
+            path.push_str(
+                format!(
+                    " + APIClient.queryString([{}])",
+                    query
+                        .iter()
+                        .map(|field| format!(
+                            "(\"{}\", {})",
+                            snake_to_camel(&field.name),
+                            swift_param_text(&field.name, &field.t)
+                        ))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+                .as_str(),
+            );
+        }
+        let headers = self.fields_in(ParamLocation::Header);
+        let headers = if headers.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "headers: [{}], ",
+                headers
+                    .iter()
+                    .map(|field| format!(
+                        "\"{}\": {}",
+                        field.header_name(),
+                        swift_param_text(&field.name, &field.t)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        let session_token = if self.authed {
+            "sessionToken: sessionToken"
+        } else {
+            "sessionToken: nil"
+        };
+        let method = self.http_method();
+        let failing_with = error_type_name
+            .as_ref()
+            .map_or_else(String::new, |name| format!("failingWith: {name}.self, "));
+        // only `request` takes an error type or headers, and streams always go through `stream`
+        let plain = self.error.is_none() && headers.is_empty() && !self.stream;
+        if self.stream {
+            lines.push(format!(
+                "    return self.fetcher.{}",
+                if self.has_body() {
+                    "stream(".to_string()
+                } else {
+                    format!(
+                        "stream(\"{}\", to: {path}, returning: {return_type_name}.self, {headers}{session_token})",
+                        method.name()
+                    )
+                }
+            ));
+        } else {
             lines.push(format!(
-                "      to: \"/_fen_/{}\",",
-                pascal_to_kebab(&self.name)
+                "    return try await self.fetcher.{}",
+                match (method, self.has_body(), plain) {
+                    (Method::Get, _, true) => format!("get(from: {path}, {session_token})"),
+                    (Method::Post, true, true) => "post(".to_string(),
+                    (_, true, _) => "request(".to_string(),
+                    (_, false, _) => format!(
+                        "request(\"{}\", to: {path}, {failing_with}{headers}{session_token})",
+                        method.name()
+                    ),
+                }
             ));
+        }
+
+        // return statement body (for requests with a body)
+        if let (Some(input), true) = (&self.input, self.has_body()) {
+            // add the method and path
+            if method != Method::Post || !plain {
+                lines.push(format!("      \"{}\",", method.name()));
+            }
+            lines.push(format!("      to: {path},"));
 
             // add the input
-            let input_payload = match &self.input.as_ref().unwrap() {
-                IOType::Type(_) | IOType::Enum(_) => "input".to_string(),
-                IOType::Struct(s) => {
-                    let mut pairs = vec![];
-                    for field in &s.fields {
-                        pairs.push(format!("{}: {}", field.name, field.name));
-                    }
-                    format!("{}({})", self.name.clone() + "Input", pairs.join(", "))
-                }
+            let input_payload = match input {
+                IOType::Struct(s) if !validates => swift_input_payload(&self.name, &s.fields),
+                IOType::Type(_) | IOType::Enum(_) | IOType::Struct(_) => "input".to_string(),
             };
             lines.push(format!("      with: {input_payload},"));
 
-            // add the return type
+            // add the return and error types
             lines.push(format!("      returning: {return_type_name}.self,"));
+            if let Some(error_type_name) = &error_type_name {
+                lines.push(format!("      failingWith: {error_type_name}.self,"));
+            }
 
-            // add the session token
-            if self.authed {
-                lines.push("      sessionToken: sessionToken".to_string());
-            } else {
-                lines.push("      sessionToken: nil".to_string());
+            // add the headers
+            if !headers.is_empty() {
+                lines.push(format!("      {}", headers.trim_end_matches(' ')));
             }
 
+            // add the session token
+            lines.push(format!("      {session_token}"));
+
             lines.push("    )".to_string());
         }
 
@@ -139,6 +279,10 @@ impl GenCode for FileNode {
             lines.push(s.swift_client_code(&Context {
                 override_name: Some(self.name.clone() + "Input"),
             }));
+            if validates {
+                lines.push(String::new());
+                lines.extend(swift_validate_extension(&self.name, &s.fields));
+            }
         } else if let Some(IOType::Enum(e)) = &self.input {
             lines.push(String::new());
             lines.push(e.swift_client_code(&Context {
@@ -159,6 +303,19 @@ impl GenCode for FileNode {
             }));
         }
 
+        // if error is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.error {
+            lines.push(String::new());
+            lines.push(s.swift_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.error {
+            lines.push(String::new());
+            lines.push(e.swift_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        }
+
         // generate definitions for helper structs
         for struct_def in &self.structs {
             lines.push(String::new());
@@ -176,40 +333,324 @@ impl GenCode for FileNode {
         }
 
         let code = lines.join("\n");
-        if code.contains("Date") || code.contains("UUID") {
+        if swift_needs_foundation(&code) {
             "import Foundation\n\n".to_string() + &code
         } else {
             code
         }
     }
 
+    #[allow(clippy::too_many_lines)]
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        // helpers:
+        let return_type_name = self.output.as_ref().map_or_else(
+            || "null".to_string(),
+            |output| match output {
+                IOType::Type(t) => t.typescript_client_code(ctx),
+                IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Output",
+            },
+        );
+        let decoder = self.output.as_ref().map_or_else(
+            || "() => null".to_string(),
+            |output| match output {
+                IOType::Type(t) => format!("(json) => {}", typescript_decoder(t, "json")),
+                IOType::Struct(_) | IOType::Enum(_) => {
+                    format!("(json) => decode{}Output(json)", self.name)
+                }
+            },
+        );
+        let (error_type_name, error_decoder) = self
+            .error
+            .as_ref()
+            .map(|error| match error {
+                IOType::Type(t) => (
+                    t.typescript_client_code(ctx),
+                    format!("(json) => {}", typescript_decoder(t, "json")),
+                ),
+                IOType::Struct(_) | IOType::Enum(_) => (
+                    self.name.clone() + "Error",
+                    format!("(json) => decode{}Error(json)", self.name),
+                ),
+            })
+            .unzip();
+        let response_type = error_type_name.map_or_else(
+            || format!("Response<{return_type_name}>"),
+            |error_type_name| format!("Response<{return_type_name}, {error_type_name}>"),
+        );
+
+        let query = self.fields_in(ParamLocation::Query);
+        let headers = self.fields_in(ParamLocation::Header);
+
+        let mut api_imports = vec!["Fetcher"];
+        if !self.path_params().is_empty() {
+            api_imports.push("pathSegment");
+        }
+        if !query.is_empty() {
+            api_imports.push("queryString");
+        }
+        api_imports.push("Response");
+        let mut lines = vec![format!(
+            "import {{ {} }} from \"./api\";",
+            api_imports.join(", ")
+        )];
+        if !self.imports.is_empty() {
+            let names = self
+                .imports
+                .iter()
+                .flat_map(|name| [name.clone(), format!("decode{name}")])
+                .collect::<Vec<String>>();
+            lines.push(format!(
+                "import {{ {} }} from \"./shared\";",
+                names.join(", ")
+            ));
+        }
+        lines.push(String::new());
+
+        // add documentation
+        if let Some(description) = &self.description {
+            lines.push(format!("/** {description} */"));
+        }
+
+        // function arguments (derived from input and auth)
+        let mut args = vec!["fetcher: Fetcher".to_string()];
+        if let Some(input) = &self.input {
+            let input_type_name = match input {
+                IOType::Type(t) => t.typescript_client_code(ctx),
+                IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Input",
+            };
+            args.push(format!("input: {input_type_name}"));
+        }
+        if self.authed {
+            args.push("sessionToken: string".to_string());
+        }
+
+        // declare the function
+        lines.push(format!(
+            "export async function {}({}): Promise<{response_type}> {{",
+            pascal_to_camel(&self.name),
+            args.join(", ")
+        ));
+
+        // return statement
+        let path = if self.path_params().is_empty() {
+            format!("\"{}\"", self.http_path())
+        } else {
+            format!(
+                "`{}`",
+                interpolate_path(self, |param| format!(
+                    "${{pathSegment(input.{})}}",
+                    snake_to_camel(param)
+                ))
+            )
+        };
+        let path = if query.is_empty() {
+            path
+        } else {
+            format!(
+                "{path} + queryString({{ {} }})",
+                query
+                    .iter()
+                    .map(|field| {
+                        let name = snake_to_camel(&field.name);
+                        format!("{name}: input.{name}")
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        // only `request` takes headers, which come after the error decoder
+        let mut extra_args = vec![];
+        if error_decoder.is_some() || !headers.is_empty() {
+            extra_args.push(error_decoder.unwrap_or_else(|| "undefined".to_string()));
+        }
+        if !headers.is_empty() {
+            extra_args.push(format!(
+                "{{ {} }}",
+                headers
+                    .iter()
+                    .map(|field| format!(
+                        "\"{}\": input.{}",
+                        field.header_name(),
+                        snake_to_camel(&field.name)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        let session_token = if self.authed { "sessionToken" } else { "null" };
+        let method = self.http_method();
+        lines.push(match (method, self.has_body(), extra_args.is_empty()) {
+            (Method::Get, _, true) => {
+                format!("  return fetcher.get({path}, {session_token}, {decoder});")
+            }
+            (Method::Post, true, true) => {
+                format!("  return fetcher.post({path}, input, {session_token}, {decoder});")
+            }
+            (_, has_body, _) => format!(
+                "  return fetcher.request(\"{}\", {});",
+                method.name(),
+                [
+                    path,
+                    (if has_body { "input" } else { "undefined" }).to_string(),
+                    session_token.to_string(),
+                    decoder,
+                ]
+                .into_iter()
+                .chain(extra_args)
+                .collect::<Vec<String>>()
+                .join(", ")
+            ),
+        });
+        lines.push("}".to_string());
+
+        // if input is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.input {
+            lines.push(String::new());
+            lines.push(s.typescript_client_code(&Context {
+                override_name: Some(self.name.clone() + "Input"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.input {
+            lines.push(String::new());
+            lines.push(e.typescript_client_code(&Context {
+                override_name: Some(self.name.clone() + "Input"),
+            }));
+        }
+
+        // if output is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.output {
+            lines.push(String::new());
+            lines.push(s.typescript_client_code(&Context {
+                override_name: Some(self.name.clone() + "Output"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.output {
+            lines.push(String::new());
+            lines.push(e.typescript_client_code(&Context {
+                override_name: Some(self.name.clone() + "Output"),
+            }));
+        }
+
+        // if error is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.error {
+            lines.push(String::new());
+            lines.push(s.typescript_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.error {
+            lines.push(String::new());
+            lines.push(e.typescript_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        }
+
+        // generate definitions for helper structs
+        for struct_def in &self.structs {
+            lines.push(String::new());
+            lines.push(struct_def.typescript_client_code(&Context {
+                override_name: None,
+            }));
+        }
+
+        // generate definitions for helper enums
+        for enum_def in &self.enums {
+            lines.push(String::new());
+            lines.push(enum_def.typescript_client_code(&Context {
+                override_name: None,
+            }));
+        }
+
+        lines.join("\n")
+    }
+
     #[allow(clippy::too_many_lines)]
     fn rust_server_code(&self, ctx: &Context) -> String {
         let mut lines: Vec<String> = vec![];
 
-        if self.input.is_some() {
-            let input = self.input.as_ref().unwrap();
+        let validates =
+            matches!(&self.input, Some(IOType::Struct(s)) if has_constraints(&s.fields));
+        if validates {
+            lines.push("use super::ValidationError;".to_string());
+        }
+        if let Some(import) = rust_shared_import(&self.imports) {
+            lines.push(import);
+        }
+        if validates || !self.imports.is_empty() {
+            lines.push(String::new());
+        }
+
+        if let Some(input) = &self.input {
             match input {
                 IOType::Type(t) => lines.push(format!(
                     "pub type {} = {};",
                     "Input",
                     t.rust_server_code(ctx)
                 )),
-                IOType::Struct(s) => lines.push(s.rust_server_code(&Context {
-                    override_name: Some("Input".to_string()),
-                })),
+                IOType::Struct(s) => {
+                    lines.push(s.rust_server_code(&Context {
+                        override_name: Some("Input".to_string()),
+                    }));
+                    if let Some(validate) = rust_validate_impl(&s.fields) {
+                        lines.push(String::new());
+                        lines.push(validate);
+                    }
+                }
                 IOType::Enum(e) => lines.push(e.rust_server_code(&Context {
                     override_name: Some("Input".to_string()),
                 })),
             }
         }
 
+        // the input's fields that come from the path (axum extracts them separately)
+        if let Some(IOType::Struct(s)) = &self.input {
+            let params = self.path_params();
+            if !params.is_empty() {
+                lines.push(String::new());
+                lines.push("#[derive(Deserialize)]".to_string());
+                lines.push("pub struct PathParams {".to_string());
+                for field in s
+                    .fields
+                    .iter()
+                    .filter(|f| params.contains(&f.name.as_str()))
+                {
+                    lines.push(format!(
+                        "    pub {}: {},",
+                        field.name,
+                        field.t.rust_server_code(ctx)
+                    ));
+                }
+                lines.push("}".to_string());
+            }
+
+            // and the ones that come from the query string
+            let query = self.fields_in(ParamLocation::Query);
+            if !query.is_empty() {
+                lines.push(String::new());
+                lines.push("#[derive(Deserialize)]".to_string());
+                lines.push("#[serde(rename_all = \"camelCase\")]".to_string());
+                lines.push("pub struct QueryParams {".to_string());
+                for field in query {
+                    lines.push(format!(
+                        "    pub {}: {},",
+                        field.name,
+                        field.t.rust_server_code(ctx)
+                    ));
+                }
+                lines.push("}".to_string());
+            }
+
+            // and the rest, from the body
+            if let Some(body) = self.body_struct() {
+                lines.push(String::new());
+                lines.push(body.rust_server_code(&Context {
+                    override_name: Some("Body".to_string()),
+                }));
+            }
+        }
+
         if self.input.is_some() && self.output.is_some() {
             lines.push(String::new());
         }
 
-        if self.output.is_some() {
-            let output = self.output.as_ref().unwrap();
+        if let Some(output) = &self.output {
             match output {
                 IOType::Type(t) => lines.push(format!(
                     "pub type {} = {};",
@@ -225,6 +666,21 @@ impl GenCode for FileNode {
             }
         }
 
+        if let Some(error) = &self.error {
+            lines.push(String::new());
+            match error {
+                IOType::Type(t) => {
+                    lines.push(format!("pub type Error = {};", t.rust_server_code(ctx)));
+                }
+                IOType::Struct(s) => lines.push(s.rust_server_code(&Context {
+                    override_name: Some("Error".to_string()),
+                })),
+                IOType::Enum(e) => lines.push(e.rust_server_code(&Context {
+                    override_name: Some("Error".to_string()),
+                })),
+            }
+        }
+
         for struct_def in &self.structs {
             lines.push(String::new());
             lines.push(struct_def.rust_server_code(ctx));
@@ -235,358 +691,8439 @@ impl GenCode for FileNode {
             lines.push(enum_def.rust_server_code(ctx));
         }
 
-        let mut code = lines.join("\n");
+        with_rust_imports(lines.join("\n"))
+    }
 
-        if code.contains("Uuid")
-            || code.contains("DateTime<Utc>")
-            || code.contains("Deserialize")
-            || code.contains("Serialize")
-        {
-            code = "\n".to_string() + &code;
-        }
-        if code.contains("Uuid") {
-            code = "use uuid::Uuid;\n".to_string() + &code;
+    #[allow(clippy::too_many_lines)]
+    fn rust_client_code(&self, ctx: &Context) -> String {
+        let query = self.fields_in(ParamLocation::Query);
+        let headers = self.fields_in(ParamLocation::Header);
+
+        let mut client_imports = vec![];
+        if !self.path_params().is_empty() {
+            client_imports.push("path_segment");
         }
-        if code.contains("Serialize") && code.contains("Deserialize") {
-            code = "use serde::{Deserialize, Serialize};\n".to_string() + &code;
-        } else if code.contains("Serialize") {
-            code = "use serde::Serialize;\n".to_string() + &code;
-        } else if code.contains("Deserialize") {
-            code = "use serde::Deserialize;\n".to_string() + &code;
+        if !query.is_empty() {
+            client_imports.push("query_string");
         }
-        if code.contains("DateTime<Utc>") {
-            code = "use chrono::{DateTime, Utc};\n".to_string() + &code;
+        client_imports.extend(["ApiClient", "Response"]);
+        let mut lines = vec![format!("use super::{{{}}};", client_imports.join(", "))];
+        if let Some(import) = rust_shared_import(&self.imports) {
+            lines.push(import);
         }
 
-        code
-    }
-}
-
-impl GenCode for StructDefinition {
-    fn swift_client_code(&self, ctx: &Context) -> String {
-        let mut lines = vec![];
-        lines.push(format!(
-            "struct {}: Codable, Equatable{} {{",
-            ctx.override_name.as_ref().map_or(&self.name, |n| n),
-            if self.fields.iter().any(|f| f.name == "id") {
-                ", Identifiable"
-            } else {
-                ""
+        if let Some(input) = &self.input {
+            lines.push(String::new());
+            match input {
+                IOType::Type(t) => {
+                    lines.push(format!("pub type Input = {};", t.rust_client_code(ctx)));
+                }
+                IOType::Struct(s) => lines.push(s.rust_client_code(&Context {
+                    override_name: Some("Input".to_string()),
+                })),
+                IOType::Enum(e) => lines.push(e.rust_client_code(&Context {
+                    override_name: Some("Input".to_string()),
+                })),
             }
-        ));
-        for field in &self.fields {
-            lines.push(field.swift_client_code(ctx));
         }
 
-        // if any of the fields are Type::Optional:
-        if self.fields.iter().any(|f| matches!(f.t, Type::Optional(_))) {
-            // coding keys
+        if let Some(output) = &self.output {
             lines.push(String::new());
-            lines.push("  private enum CodingKeys: String, CodingKey {".to_string());
-            for field in &self.fields {
-                lines.push(format!("    case {}", snake_to_camel(&field.name)));
+            match output {
+                IOType::Type(t) => {
+                    lines.push(format!("pub type Output = {};", t.rust_client_code(ctx)));
+                }
+                IOType::Struct(s) => lines.push(s.rust_client_code(&Context {
+                    override_name: Some("Output".to_string()),
+                })),
+                IOType::Enum(e) => lines.push(e.rust_client_code(&Context {
+                    override_name: Some("Output".to_string()),
+                })),
             }
-            lines.push("  }".to_string());
+        }
 
-            // encode
-            lines.push(String::new());
-            lines.push("  func encode(to encoder: Encoder) throws {".to_string());
-            lines.push(
-                "    var container = encoder.container(keyedBy: CodingKeys.self)".to_string(),
-            );
+        if let Some(error) = &self.error {
             lines.push(String::new());
-            for field in &self.fields {
-                if let Type::Optional(_) = &field.t {
-                    lines.push(format!(
-                        "    switch self.{} {{",
-                        snake_to_camel(&field.name)
-                    ));
-                    lines.push("    case let .some(value):".to_string());
-                    lines.push(format!(
-                        "      try container.encode(value, forKey: .{})",
-                        snake_to_camel(&field.name)
-                    ));
-                    lines.push("    case .none:".to_string());
-                    lines.push(format!(
-                        "      try container.encodeNil(forKey: .{})",
-                        snake_to_camel(&field.name)
-                    ));
-                    lines.push("    }".to_string());
-                } else {
-                    lines.push(format!(
-                        "    try container.encode(self.{}, forKey: .{})",
-                        snake_to_camel(&field.name),
-                        snake_to_camel(&field.name)
-                    ));
+            match error {
+                IOType::Type(t) => {
+                    lines.push(format!("pub type Error = {};", t.rust_client_code(ctx)));
                 }
+                IOType::Struct(s) => lines.push(s.rust_client_code(&Context {
+                    override_name: Some("Error".to_string()),
+                })),
+                IOType::Enum(e) => lines.push(e.rust_client_code(&Context {
+                    override_name: Some("Error".to_string()),
+                })),
             }
-            lines.push("  }".to_string());
         }
 
-        lines.push("}".to_string());
-        lines.join("\n")
-    }
+        for struct_def in &self.structs {
+            lines.push(String::new());
+            lines.push(struct_def.rust_client_code(ctx));
+        }
 
-    fn rust_server_code(&self, ctx: &Context) -> String {
-        let mut lines = vec![];
+        for enum_def in &self.enums {
+            lines.push(String::new());
+            lines.push(enum_def.rust_client_code(ctx));
+        }
 
-        lines.push("#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]".to_string());
-        lines.push("#[serde(rename_all = \"camelCase\")]".to_string());
+        // the request method itself
+        lines.push(String::new());
+        lines.push("impl ApiClient {".to_string());
+        if let Some(description) = &self.description {
+            lines.push(format!("    /// {description}"));
+        }
+        if let Some(message) = &self.deprecated {
+            lines.push(format!("    {}", rust_deprecated(message)));
+        }
+        let mut args = vec!["&self".to_string()];
+        if self.input.is_some() {
+            args.push("input: &Input".to_string());
+        }
+        if self.authed {
+            args.push("session_token: &str".to_string());
+        }
         lines.push(format!(
-            "pub struct {} {{",
-            ctx.override_name.as_ref().map_or(&self.name, |name| name)
+            "    pub async fn {}({}) -> Result<Response<{}{}>, reqwest::Error> {{",
+            pascal_to_snake(&self.name),
+            args.join(", "),
+            if self.output.is_some() {
+                "Output"
+            } else {
+                "()"
+            },
+            if self.error.is_some() { ", Error" } else { "" }
         ));
-        for field in &self.fields {
-            lines.push(field.rust_server_code(ctx));
+        let mut path_args = self
+            .path_params()
+            .iter()
+            .map(|param| format!("path_segment(&input.{param})"))
+            .collect::<Vec<String>>();
+        if !query.is_empty() {
+            path_args.push(format!(
+                "query_string(&[{}])",
+                query
+                    .iter()
+                    .map(|field| format!(
+                        "(\"{}\", {})",
+                        snake_to_camel(&field.name),
+                        rust_param_text(&field.name, &field.t)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
         }
+        let path = if path_args.is_empty() {
+            format!("\"{}\"", self.http_path())
+        } else {
+            format!(
+                "&format!(\"{}{}\", {})",
+                interpolate_path(self, |_| "{}".to_string()),
+                if query.is_empty() { "" } else { "{}" },
+                path_args.join(", ")
+            )
+        };
+        let session_token = if self.authed {
+            "Some(session_token)"
+        } else {
+            "None"
+        };
+        let method = self.http_method();
+        let body = if self.has_body() {
+            "Some(input)"
+        } else {
+            "None::<&()>"
+        };
+        lines.push(match (method, self.has_body(), headers.is_empty()) {
+            (Method::Get, _, true) => {
+                format!("        self.get({path}, {session_token}).await")
+            }
+            (Method::Post, true, true) => {
+                format!("        self.post({path}, input, {session_token}).await")
+            }
+            (_, _, true) => format!(
+                "        self.request(reqwest::Method::{}, {path}, {body}, {session_token}).await",
+                method.name(),
+            ),
+            // only `send` takes headers
+            (_, _, false) => format!(
+                "        self.send(reqwest::Method::{}, {path}, {body}, &[{}], {session_token}).await",
+                method.name(),
+                headers
+                    .iter()
+                    .map(|field| format!(
+                        "(\"{}\", {})",
+                        field.header_name(),
+                        rust_param_text(&field.name, &field.t)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        });
+        lines.push("    }".to_string());
         lines.push("}".to_string());
 
-        lines.join("\n")
-    }
-}
-
-impl GenCode for Field {
-    fn swift_client_code(&self, ctx: &Context) -> String {
-        format!(
-            "  var {}: {}",
-            snake_to_camel(&self.name),
-            self.t.swift_client_code(ctx)
-        )
+        with_rust_imports(lines.join("\n"))
     }
 
-    fn rust_server_code(&self, ctx: &Context) -> String {
-        format!("    pub {}: {},", &self.name, self.t.rust_server_code(ctx))
-    }
-}
+    #[allow(clippy::too_many_lines)]
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        // helpers:
+        let return_type_name = self.output.as_ref().map_or_else(
+            || "Unit".to_string(),
+            |output| match output {
+                IOType::Type(t) => t.kotlin_client_code(ctx),
+                IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Output",
+            },
+        );
+        let response_serializer = self.output.as_ref().map_or_else(
+            || "NoDataSerializer".to_string(),
+            |output| match output {
+                IOType::Type(t) => kotlin_serializer(t),
+                IOType::Struct(_) | IOType::Enum(_) => format!("{}Output.serializer()", self.name),
+            },
+        );
+        let (error_type_name, error_serializer) = self
+            .error
+            .as_ref()
+            .map(|error| match error {
+                IOType::Type(t) => (t.kotlin_client_code(ctx), kotlin_serializer(t)),
+                IOType::Struct(_) | IOType::Enum(_) => (
+                    self.name.clone() + "Error",
+                    format!("{}Error.serializer()", self.name),
+                ),
+            })
+            .unzip();
+        let response_type = error_type_name.map_or_else(
+            || format!("Response<{return_type_name}>"),
+            |error_type_name| format!("ResponseWithError<{return_type_name}, {error_type_name}>"),
+        );
+        // routes with an error type go through `request`, which takes its serializer
+        let response_serializer = match error_serializer {
+            Some(error_serializer) => format!("{response_serializer}, {error_serializer}"),
+            None => response_serializer,
+        };
 
-impl GenCode for EnumDefinition {
-    fn swift_client_code(&self, ctx: &Context) -> String {
         let mut lines = vec![];
 
-        let enum_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
-
-        lines.push(format!("enum {enum_name}: Codable, Equatable {{",));
-        for variant in &self.variants {
-            lines.push(variant.swift_client_code(ctx));
+        // add documentation
+        if let Some(description) = &self.description {
+            lines.push(format!("/** {description} */"));
         }
 
-        // coding keys
-        lines.push(String::new());
-        lines.push("  private enum CodingKeys: String, CodingKey {".to_string());
-        lines.push("    case type".to_string());
-        if self.variants.iter().any(|v| v.t.is_some()) {
-            lines.push("    case value".to_string());
+        // function arguments (derived from input and auth)
+        let mut args = vec![];
+        if let Some(input) = &self.input {
+            match input {
+                IOType::Type(t) => args.push(format!("input: {}", t.kotlin_client_code(ctx))),
+                IOType::Enum(_) => args.push(format!("input: {}Input", self.name)),
+                IOType::Struct(s) => {
+                    for field in &s.fields {
+                        args.push(format!(
+                            "{}: {}",
+                            snake_to_camel(&field.name),
+                            field.t.kotlin_client_code(ctx)
+                        ));
+                    }
+                }
+            }
+        }
+        if self.authed {
+            args.push("sessionToken: String".to_string());
         }
-        lines.push("  }".to_string());
 
-        // key types
-        let key_types_name = format!("{enum_name}Type");
-        lines.push(String::new());
+        // declare the function
         lines.push(format!(
-            "  private enum {key_types_name}: String, Codable {{",
+            "suspend fun APIClient.{}({}): {response_type} =",
+            pascal_to_camel(&self.name),
+            args.join(", ")
         ));
-        for variant in &self.variants {
-            lines.push(format!("    case {}", snake_to_camel(&variant.name)));
-        }
-        lines.push("  }".to_string());
 
-        // init from decoder
-        lines.push(String::new());
-        lines.push("  init(from decoder: Decoder) throws {".to_string());
-        lines.push(
-            "    let container = try decoder.container(keyedBy: CodingKeys.self)".to_string(),
+        // function body
+        let mut path = format!(
+            "\"{}\"",
+            interpolate_path(self, |param| format!(
+                "${{APIClient.pathSegment({})}}",
+                snake_to_camel(param)
+            ))
         );
-        lines.push(format!(
-            "    let type = try container.decode({key_types_name}.self, forKey: .type)"
-        ));
-        lines.push(String::new());
-        lines.push("    switch type {".to_string());
-        for variant in &self.variants {
-            lines.push(format!("    case .{}:", snake_to_camel(&variant.name)));
-            match &variant.t {
-                Some(Type::Optional(t)) => {
-                    lines.push(format!(
-                        "      let value = try container.decodeIfPresent({}.self, forKey: .value)",
-                        t.swift_client_code(ctx)
-                    ));
-                    lines.push(format!(
-                        "      self = .{}(value)",
-                        snake_to_camel(&variant.name)
-                    ));
-                }
-                Some(other) => {
-                    lines.push(format!(
-                        "      let value = try container.decode({}.self, forKey: .value)",
-                        other.swift_client_code(ctx)
-                    ));
-                    lines.push(format!(
-                        "      self = .{}(value)",
-                        snake_to_camel(&variant.name)
-                    ));
-                }
-                None => {
-                    lines.push(format!("      self = .{}", snake_to_camel(&variant.name)));
-                }
+        let query = self.fields_in(ParamLocation::Query);
+        if !query.is_empty() {
+            path.push_str(
+                format!(
+                    " + APIClient.queryString({})",
+                    query
+                        .iter()
+                        .map(|field| {
+                            let name = snake_to_camel(&field.name);
+                            format!("\"{name}\" to {name}")
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+                .as_str(),
+            );
+        }
+        let headers = self.fields_in(ParamLocation::Header);
+        // only `request` takes headers (after the session token)
+        let plain = self.error.is_none() && headers.is_empty();
+        let last_args = if headers.is_empty() {
+            (if self.authed { "sessionToken" } else { "null" }).to_string()
+        } else {
+            format!(
+                "{}, headers = mapOf({})",
+                if self.authed { "sessionToken" } else { "null" },
+                headers
+                    .iter()
+                    .map(|field| format!(
+                        "\"{}\" to {}",
+                        field.header_name(),
+                        snake_to_camel(&field.name)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        let method = self.http_method();
+        if let (Some(input), true) = (&self.input, self.has_body()) {
+            let (body, body_serializer) = match input {
+                IOType::Type(t) => ("input".to_string(), kotlin_serializer(t)),
+                IOType::Enum(_) => (
+                    "input".to_string(),
+                    format!("{}Input.serializer()", self.name),
+                ),
+                IOType::Struct(s) => (
+                    format!(
+                        "{}Input({})",
+                        self.name,
+                        s.fields
+                            .iter()
+                            .map(|field| {
+                                let name = snake_to_camel(&field.name);
+                                format!("{name} = {name}")
+                            })
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                    format!("{}Input.serializer()", self.name),
+                ),
             };
+            lines.push(if method == Method::Post && plain {
+                format!(
+                    "    post({path}, {body}, {body_serializer}, {response_serializer}, {last_args})"
+                )
+            } else {
+                format!(
+                    "    request(\"{}\", {path}, {body}, {body_serializer}, {response_serializer}, {last_args})",
+                    method.name()
+                )
+            });
+        } else if method == Method::Get && plain {
+            lines.push(format!(
+                "    get({path}, {response_serializer}, {last_args})"
+            ));
+        } else {
+            lines.push(format!(
+                "    request(\"{}\", {path}, {response_serializer}, {last_args})",
+                method.name()
+            ));
         }
-        lines.push("    }".to_string());
-        lines.push("  }".to_string());
 
-        // encode
-        lines.push(String::new());
-        lines.push("  func encode(to encoder: Encoder) throws {".to_string());
-        lines.push("    var container = encoder.container(keyedBy: CodingKeys.self)".to_string());
-        lines.push(String::new());
-        lines.push("    switch self {".to_string());
-        for variant in &self.variants {
-            if let Some(_) = &variant.t {
-                lines.push(format!(
-                    "    case .{}(let value):",
-                    snake_to_camel(&variant.name)
-                ));
-                lines.push(format!(
-                    "      try container.encode({}.{}, forKey: .type)",
-                    key_types_name,
-                    snake_to_camel(&variant.name)
-                ));
-                lines.push("      try container.encode(value, forKey: .value)".to_string());
-            } else {
-                lines.push(format!("    case .{}:", snake_to_camel(&variant.name)));
-                lines.push(format!(
-                    "      try container.encode({}.{}, forKey: .type)",
-                    key_types_name,
-                    snake_to_camel(&variant.name)
-                ));
-            }
+        // if input is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.input {
+            lines.push(String::new());
+            lines.push(s.kotlin_client_code(&Context {
+                override_name: Some(self.name.clone() + "Input"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.input {
+            lines.push(String::new());
+            lines.push(e.kotlin_client_code(&Context {
+                override_name: Some(self.name.clone() + "Input"),
+            }));
         }
-        lines.push("    }".to_string());
-        lines.push("  }".to_string());
 
-        lines.push("}".to_string());
-        lines.join("\n")
-    }
+        // if output is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.output {
+            lines.push(String::new());
+            lines.push(s.kotlin_client_code(&Context {
+                override_name: Some(self.name.clone() + "Output"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.output {
+            lines.push(String::new());
+            lines.push(e.kotlin_client_code(&Context {
+                override_name: Some(self.name.clone() + "Output"),
+            }));
+        }
 
-    fn rust_server_code(&self, ctx: &Context) -> String {
-        let mut lines = vec![];
+        // if error is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.error {
+            lines.push(String::new());
+            lines.push(s.kotlin_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.error {
+            lines.push(String::new());
+            lines.push(e.kotlin_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        }
 
-        lines.push(format!(
-            "#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq{})]",
-            if self.annotations.is_empty() {
-                ""
-            } else {
-                ", sqlx::Type"
-            }
-        ));
-        lines.push(format!(
-            "#[serde(tag = \"type\", {}rename_all = \"camelCase\")]",
-            if self.variants.iter().any(|v| v.t.is_some()) {
-                "content = \"value\", "
-            } else {
-                ""
-            }
-        ));
-        if self.annotations.contains(&"sqlxType".to_string()) {
-            lines.push(format!(
-                "#[sqlx(type_name = \"{}\", rename_all = \"SCREAMING_SNAKE_CASE\")]",
-                pascal_to_snake(&self.name)
-            ));
+        // generate definitions for helper structs
+        for struct_def in &self.structs {
+            lines.push(String::new());
+            lines.push(struct_def.kotlin_client_code(&Context {
+                override_name: None,
+            }));
         }
-        lines.push(format!(
-            "pub enum {} {{",
-            ctx.override_name.as_ref().map_or(&self.name, |n| n)
-        ));
-        for variant in &self.variants {
-            lines.push(variant.rust_server_code(ctx));
+
+        // generate definitions for helper enums
+        for enum_def in &self.enums {
+            lines.push(String::new());
+            lines.push(enum_def.kotlin_client_code(&Context {
+                override_name: None,
+            }));
         }
-        lines.push("}".to_string());
 
-        lines.join("\n")
+        with_kotlin_imports(&lines.join("\n"))
     }
-}
 
-impl GenCode for Variant {
-    fn swift_client_code(&self, ctx: &Context) -> String {
-        format!(
-            "  case {}{}",
-            snake_to_camel(&self.name),
-            self.t
-                .as_ref()
-                .map_or_else(String::new, |t| format!("({})", t.swift_client_code(ctx)))
-        )
-    }
+    #[allow(clippy::too_many_lines)]
+    fn python_client_code(&self, ctx: &Context) -> String {
+        // helpers:
+        let return_type_name = self.output.as_ref().map_or_else(
+            || "None".to_string(),
+            |output| match output {
+                IOType::Type(t) => t.python_client_code(ctx),
+                IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Output",
+            },
+        );
 
-    fn rust_server_code(&self, ctx: &Context) -> String {
-        format!(
-            "    {}{},",
-            snake_to_pascal(&self.name),
-            self.t
+        let mut lines = vec![];
+
+        // if input is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.input {
+            lines.push(s.python_client_code(&Context {
+                override_name: Some(self.name.clone() + "Input"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.input {
+            lines.push(e.python_client_code(&Context {
+                override_name: Some(self.name.clone() + "Input"),
+            }));
+        }
+
+        // if output is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.output {
+            lines.push(s.python_client_code(&Context {
+                override_name: Some(self.name.clone() + "Output"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.output {
+            lines.push(e.python_client_code(&Context {
+                override_name: Some(self.name.clone() + "Output"),
+            }));
+        }
+
+        // if error is a struct or enum, define it
+        if let Some(IOType::Struct(s)) = &self.error {
+            lines.push(s.python_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        } else if let Some(IOType::Enum(e)) = &self.error {
+            lines.push(e.python_client_code(&Context {
+                override_name: Some(self.name.clone() + "Error"),
+            }));
+        }
+
+        // generate definitions for helper structs
+        for struct_def in &self.structs {
+            lines.push(struct_def.python_client_code(&Context {
+                override_name: None,
+            }));
+        }
+
+        // generate definitions for helper enums
+        for enum_def in &self.enums {
+            lines.push(enum_def.python_client_code(&Context {
+                override_name: None,
+            }));
+        }
+
+        // function arguments (derived from input and auth)
+        let mut args = vec!["client: APIClient".to_string()];
+        if let Some(input) = &self.input {
+            match input {
+                IOType::Type(t) => args.push(format!("input: {}", t.python_client_code(ctx))),
+                IOType::Enum(_) => args.push(format!("input: {}Input", self.name)),
+                IOType::Struct(s) => {
+                    for field in &s.fields {
+                        args.push(format!(
+                            "{}: {}",
+                            field.name,
+                            field.t.python_client_code(ctx)
+                        ));
+                    }
+                }
+            }
+        }
+        if self.authed {
+            args.push("session_token: str".to_string());
+        }
+
+        // declare the function
+        let mut func = vec![format!(
+            "def {}({}) -> {return_type_name}:",
+            pascal_to_snake(&self.name),
+            args.join(", ")
+        )];
+
+        // add documentation
+        if let Some(description) = &self.description {
+            func.push(format!("    \"\"\"{description}\"\"\""));
+        }
+
+        // function body
+        let path = if self.path_params().is_empty() {
+            format!("\"{}\"", self.http_path())
+        } else {
+            format!(
+                "f\"{}\"",
+                interpolate_path(self, |param| format!("{{path_segment({param})}}"))
+            )
+        };
+        let query = self.fields_in(ParamLocation::Query);
+        let path = if query.is_empty() {
+            path
+        } else {
+            format!(
+                "{path} + query_string({{{}}})",
+                query
+                    .iter()
+                    .map(|field| format!("\"{}\": {}", snake_to_camel(&field.name), field.name))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+        let session_token = if self.authed { "session_token" } else { "None" };
+        // the error type and headers go last, since they're the only optional arguments
+        let mut last_args = self.error.as_ref().map_or_else(
+            || session_token.to_string(),
+            |error| {
+                format!(
+                    "{session_token}, error_type={}",
+                    match error {
+                        IOType::Type(t) => t.python_client_code(ctx),
+                        IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Error",
+                    }
+                )
+            },
+        );
+        let headers = self.fields_in(ParamLocation::Header);
+        if !headers.is_empty() {
+            last_args.push_str(
+                format!(
+                    ", headers={{{}}}",
+                    headers
+                        .iter()
+                        .map(|field| format!("\"{}\": {}", field.header_name(), field.name))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+                .as_str(),
+            );
+        }
+        let method = self.http_method();
+        if let (Some(input), true) = (&self.input, self.has_body()) {
+            let (body, body_type) = match input {
+                IOType::Type(t) => ("input".to_string(), t.python_client_code(ctx)),
+                IOType::Enum(_) => ("input".to_string(), format!("{}Input", self.name)),
+                IOType::Struct(s) => (
+                    format!(
+                        "{}Input({})",
+                        self.name,
+                        s.fields
+                            .iter()
+                            .map(|field| format!("{}={}", field.name, field.name))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    ),
+                    format!("{}Input", self.name),
+                ),
+            };
+            func.push(if method == Method::Post && headers.is_empty() {
+                format!(
+                    "    return client.post({path}, {body}, {body_type}, {return_type_name}, {last_args})"
+                )
+            } else {
+                format!(
+                    "    return client.request(\"{}\", {path}, {body}, {body_type}, {return_type_name}, {last_args})",
+                    method.name()
+                )
+            });
+        } else if method == Method::Get && headers.is_empty() {
+            func.push(format!(
+                "    return client.get({path}, {return_type_name}, {last_args})"
+            ));
+        } else {
+            func.push(format!(
+                "    return client.request(\"{}\", {path}, None, None, {return_type_name}, {last_args})",
+                method.name()
+            ));
+        }
+        lines.push(func.join("\n"));
+
+        with_python_imports(&lines.join("\n\n\n"), &self.imports)
+    }
+
+    fn go_client_code(&self, ctx: &Context) -> String {
+        // helpers:
+        let return_type_name = self.output.as_ref().map(|output| match output {
+            IOType::Type(t) => t.go_client_code(ctx),
+            IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Output",
+        });
+
+        let mut lines = go_type_definitions(self, ctx);
+
+        // function arguments (derived from input and auth)
+        let mut args = vec!["ctx context.Context".to_string()];
+        if let Some(input) = &self.input {
+            match input {
+                IOType::Type(t) => args.push(format!("input {}", t.go_client_code(ctx))),
+                IOType::Struct(_) | IOType::Enum(_) => {
+                    args.push(format!("input {}Input", self.name));
+                }
+            }
+        }
+        if self.authed {
+            args.push("sessionToken string".to_string());
+        }
+
+        let mut func = vec![];
+
+        // add documentation
+        if let Some(description) = &self.description {
+            func.push(format!("// {} - {description}", self.name));
+        }
+
+        // declare the method
+        func.push(format!(
+            "func (c *Client) {}({}) {} {{",
+            self.name,
+            args.join(", "),
+            return_type_name
                 .as_ref()
-                .map_or_else(String::new, |t| format!("({})", t.rust_server_code(ctx)))
-        )
+                .map_or_else(|| "error".to_string(), |t| format!("({t}, error)"))
+        ));
+
+        // method body
+        let path = format!(
+            "\"{}\"",
+            interpolate_path(self, |param| format!(
+                "\" + pathSegment(input.{}) + \"",
+                snake_to_pascal(param)
+            ))
+        );
+        let mut path = path.strip_suffix(" + \"\"").unwrap_or(&path).to_string();
+        let query = self.fields_in(ParamLocation::Query);
+        if !query.is_empty() {
+            path.push_str(
+                format!(
+                    " + queryString({})",
+                    query
+                        .iter()
+                        .map(|field| format!("param{}", go_param(field)))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+                .as_str(),
+            );
+        }
+        let headers = self.fields_in(ParamLocation::Header);
+        let session_token = if self.authed { "sessionToken" } else { "\"\"" };
+        let response_type = return_type_name.as_deref().unwrap_or("struct{}");
+        let method = self.http_method();
+        let error_type_name = self.error.as_ref().map(|error| match error {
+            IOType::Type(t) => t.go_client_code(ctx),
+            IOType::Struct(_) | IOType::Enum(_) => self.name.clone() + "Error",
+        });
+        let call = match (method, self.has_body(), error_type_name) {
+            // only `requestWithHeaders` takes headers
+            (_, has_body, error_type) if !headers.is_empty() => format!(
+                "requestWithHeaders[{response_type}, {}](ctx, c, \"{}\", {path}, {}, []param{{{}}}, {session_token})",
+                error_type.as_deref().unwrap_or("json.RawMessage"),
+                method.name(),
+                if has_body { "input" } else { "nil" },
+                headers
+                    .iter()
+                    .map(|field| go_param(field))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            (_, has_body, Some(error_type)) => format!(
+                "requestWithError[{response_type}, {error_type}](ctx, c, \"{}\", {path}, {}, {session_token})",
+                method.name(),
+                if has_body { "input" } else { "nil" }
+            ),
+            (Method::Get, _, None) => {
+                format!("get[{response_type}](ctx, c, {path}, {session_token})")
+            }
+            (Method::Post, true, None) => {
+                format!("post[{response_type}](ctx, c, {path}, input, {session_token})")
+            }
+            (_, has_body, None) => format!(
+                "request[{response_type}](ctx, c, \"{}\", {path}, {}, {session_token})",
+                method.name(),
+                if has_body { "input" } else { "nil" }
+            ),
+        };
+        if return_type_name.is_some() {
+            func.push(format!("\treturn {call}"));
+        } else {
+            func.push(format!("\t_, err := {call}"));
+            func.push("\treturn err".to_string());
+        }
+        func.push("}".to_string());
+        lines.push(func.join("\n"));
+
+        with_go_imports(&lines.join("\n\n"))
     }
-}
 
-impl GenCode for Type {
-    fn swift_client_code(&self, ctx: &Context) -> String {
-        match &self {
-            Self::Named(n) => n.clone(),
-            Self::Optional(t) => format!("{}?", t.swift_client_code(ctx)),
-            Self::Array(t) => format!("[{}]", t.swift_client_code(ctx)),
-            Self::Primitive(p) => p.swift_client_code(ctx),
+    fn go_server_code(&self, ctx: &Context) -> String {
+        let mut lines = go_type_definitions(self, ctx);
+
+        // the method and path the route's handler should be mounted at (path parameters use the
+        // same `{name}` syntax as `http.ServeMux`)
+        lines.push(format!(
+            "const (\n\t{name}Method = \"{}\"\n\t{name}Path   = \"{}\"\n)",
+            self.http_method().name(),
+            self.http_path(),
+            name = self.name,
+        ));
+
+        with_go_imports(&lines.join("\n\n"))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn swift_server_code(&self, ctx: &Context) -> String {
+        let mut lines = vec!["import Vapor".to_string(), String::new()];
+
+        // namespace for the route
+        lines.push(format!("enum {} {{", self.name));
+
+        // input and output types
+        if let Some(input) = &self.input {
+            match input {
+                IOType::Type(t) => {
+                    lines.push(format!("  typealias Input = {}", t.swift_server_code(ctx)));
+                }
+                IOType::Struct(s) => lines.push(indent_swift(&s.swift_server_code(&Context {
+                    override_name: Some("Input".to_string()),
+                }))),
+                IOType::Enum(e) => lines.push(indent_swift(&e.swift_server_code(&Context {
+                    override_name: Some("Input".to_string()),
+                }))),
+            }
+            lines.push(String::new());
+        }
+        if let Some(body) = self.body_struct() {
+            lines.push(indent_swift(&body.swift_server_code(&Context {
+                override_name: Some("Body".to_string()),
+            })));
+            lines.push(String::new());
+        }
+        if let Some(output) = &self.output {
+            match output {
+                IOType::Type(t) => {
+                    lines.push(format!("  typealias Output = {}", t.swift_server_code(ctx)));
+                }
+                IOType::Struct(s) => lines.push(indent_swift(&s.swift_server_code(&Context {
+                    override_name: Some("Output".to_string()),
+                }))),
+                IOType::Enum(e) => lines.push(indent_swift(&e.swift_server_code(&Context {
+                    override_name: Some("Output".to_string()),
+                }))),
+            }
+            lines.push(String::new());
+        }
+        if let Some(error) = &self.error {
+            match error {
+                IOType::Type(t) => {
+                    lines.push(format!("  typealias Error = {}", t.swift_server_code(ctx)));
+                }
+                IOType::Struct(s) => lines.push(indent_swift(&s.swift_server_code(&Context {
+                    override_name: Some("Error".to_string()),
+                }))),
+                IOType::Enum(e) => lines.push(indent_swift(&e.swift_server_code(&Context {
+                    override_name: Some("Error".to_string()),
+                }))),
+            }
+            lines.push(String::new());
+        }
+        let output_type_name = if self.output.is_some() {
+            "Output"
+        } else {
+            "NoData"
+        };
+        let response_type_name = if self.error.is_some() {
+            format!("ResponseWithError<{output_type_name}, Error>")
+        } else {
+            format!("Response<{output_type_name}>")
+        };
+
+        // add documentation
+        if let Some(description) = &self.description {
+            lines.push(format!("  /// {description}"));
+        }
+        if let Some(message) = &self.deprecated {
+            lines.push(format!("  {}", swift_deprecated(message)));
+        }
+
+        // route registration
+        let mut handler_args = vec!["Request"];
+        if self.input.is_some() {
+            handler_args.push("Input");
+        }
+        if self.authed {
+            handler_args.push("String");
+        }
+        lines.push("  static func register(".to_string());
+        lines.push("    on routes: any RoutesBuilder,".to_string());
+        lines.push(format!(
+            "    handler: @escaping @Sendable ({}) async throws -> {response_type_name}",
+            handler_args.join(", ")
+        ));
+        lines.push("  ) {".to_string());
+        // Vapor takes the path as separate components, with parameters written as `:name`
+        let components = self
+            .http_path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                segment
+                    .strip_prefix('{')
+                    .and_then(|segment| segment.strip_suffix('}'))
+                    .map_or_else(|| format!("\"{segment}\""), |param| format!("\":{param}\""))
+            })
+            .collect::<Vec<String>>();
+        lines.push(format!(
+            "    routes.{}({}) {{ request async throws -> {response_type_name} in",
+            self.http_method().lowercase_name(),
+            components.join(", ")
+        ));
+        let mut call_args = vec!["request"];
+        if self.authed {
+            lines.push(
+                "      guard let sessionToken = request.headers.bearerAuthorization?.token else {"
+                    .to_string(),
+            );
+            lines.push(format!(
+                "        return .{}(message: \"Unauthorized\", status: 401)",
+                if self.error.is_some() {
+                    "genericFailure"
+                } else {
+                    "failure"
+                }
+            ));
+            lines.push("      }".to_string());
+        }
+        // a missing optional header is just `nil`, but a missing required one is a bad request
+        for field in self.fields_in(ParamLocation::Header) {
+            let name = snake_to_camel(&field.name);
+            if let Type::Optional(t, _) = &field.t {
+                lines.push(format!(
+                    "      let {name} = request.header(\"{}\", as: {}.self)",
+                    field.header_name(),
+                    t.swift_server_code(ctx)
+                ));
+            } else {
+                lines.extend([
+                    format!(
+                        "      guard let {name} = request.header(\"{}\", as: {}.self) else {{",
+                        field.header_name(),
+                        field.t.swift_server_code(ctx)
+                    ),
+                    format!(
+                        "        return .{}(message: \"Missing or invalid {} header\", status: 400)",
+                        if self.error.is_some() {
+                            "genericFailure"
+                        } else {
+                            "failure"
+                        },
+                        field.header_name()
+                    ),
+                    "      }".to_string(),
+                ]);
+            }
+        }
+        // the input is put together from the path, query string, headers, and body, unless it's
+        // all in the body
+        let path_params = self.path_params();
+        let body = self.body_struct();
+        match &self.input {
+            Some(IOType::Struct(s)) if !self.has_body() || body.is_some() => {
+                if body.is_some() {
+                    lines.push(
+                        "      let body = try request.content.decode(Body.self, using: FenCoding.decoder)"
+                            .to_string(),
+                    );
+                }
+                let args = s
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let name = snake_to_camel(&field.name);
+                        let value = match (field.location, &field.t) {
+                            (Some(ParamLocation::Header), _) => name.clone(),
+                            (Some(ParamLocation::Query), Type::Optional(t, _)) => format!(
+                                "request.query[{}.self, at: \"{name}\"]",
+                                t.swift_server_code(ctx)
+                            ),
+                            (Some(ParamLocation::Query), t) => format!(
+                                "try request.query.get({}.self, at: \"{name}\")",
+                                t.swift_server_code(ctx)
+                            ),
+                            (None, t) if path_params.contains(&field.name.as_str()) => format!(
+                                "try request.parameters.require(\"{}\", as: {}.self)",
+                                field.name,
+                                t.swift_server_code(ctx)
+                            ),
+                            (None, _) => format!("body.{name}"),
+                        };
+                        format!("{name}: {value}")
+                    })
+                    .collect::<Vec<String>>();
+                lines.push(format!("      let input = Input({})", args.join(", ")));
+                call_args.push("input");
+            }
+            Some(_) => {
+                lines.push(
+                    "      let input = try request.content.decode(Input.self, using: FenCoding.decoder)"
+                        .to_string(),
+                );
+                call_args.push("input");
+            }
+            None => {}
+        }
+        if self.authed {
+            call_args.push("sessionToken");
+        }
+        lines.push(format!(
+            "      return try await handler({})",
+            call_args.join(", ")
+        ));
+        lines.push("    }".to_string());
+        lines.push("  }".to_string());
+        lines.push("}".to_string());
+
+        // generate definitions for helper structs
+        for struct_def in &self.structs {
+            lines.push(String::new());
+            lines.push(struct_def.swift_server_code(&Context {
+                override_name: None,
+            }));
+        }
+
+        // generate definitions for helper enums
+        for enum_def in &self.enums {
+            lines.push(String::new());
+            lines.push(enum_def.swift_server_code(&Context {
+                override_name: None,
+            }));
         }
+
+        lines.join("\n")
     }
+}
+
+impl StructDefinition {
+    /// The struct's Swift definition, conforming to `protocols` (the client and server differ
+    /// in how they make it codable).
+    fn swift_code(&self, ctx: &Context, protocols: &[&str]) -> String {
+        let mut lines = doc_comment(self.description.as_deref(), "");
+        let mut conformances = protocols.to_vec();
+        if self.fields.iter().any(|f| f.name == "id") {
+            conformances.push("Identifiable");
+        }
+        lines.push(format!(
+            "struct {}{}: {} {{",
+            ctx.override_name.as_ref().map_or(&self.name, |n| n),
+            swift_generic_params(&self.generics, protocols),
+            conformances.join(", ")
+        ));
+        for field in &self.fields {
+            lines.extend(doc_comment(field.description.as_deref(), "  "));
+            if let Some(message) = &field.deprecated {
+                lines.push(format!("  {}", swift_deprecated(message)));
+            }
+            lines.push(field.swift_client_code(ctx));
+        }
+
+        let has_optionals = self
+            .fields
+            .iter()
+            .any(|f| matches!(f.t, Type::Optional(..)));
+        let has_defaults = self.fields.iter().any(|f| f.default.is_some());
+
+        // defaults need a memberwise init with default arguments, and a decoder that falls
+        // back to them (declaring the decoder would otherwise drop the memberwise init)
+        if has_defaults {
+            lines.push(String::new());
+            lines.extend(swift_memberwise_init(&self.fields, ctx));
+        }
+
+        if has_optionals || has_defaults {
+            // coding keys
+            lines.push(String::new());
+            lines.push("  private enum CodingKeys: String, CodingKey {".to_string());
+            for field in &self.fields {
+                lines.push(format!("    case {}", snake_to_camel(&field.name)));
+            }
+            lines.push("  }".to_string());
+        }
+
+        if has_defaults {
+            lines.push(String::new());
+            lines.extend(swift_decoder_init(&self.fields, ctx));
+        }
+
+        if has_optionals {
+            // encode
+            lines.push(String::new());
+            lines.push("  func encode(to encoder: Encoder) throws {".to_string());
+            lines.push(
+                "    var container = encoder.container(keyedBy: CodingKeys.self)".to_string(),
+            );
+            lines.push(String::new());
+            for field in &self.fields {
+                if let Type::Optional(..) = &field.t {
+                    lines.push(format!(
+                        "    switch self.{} {{",
+                        snake_to_camel(&field.name)
+                    ));
+                    lines.push("    case let .some(value):".to_string());
+                    lines.push(format!(
+                        "      try container.encode(value, forKey: .{})",
+                        snake_to_camel(&field.name)
+                    ));
+                    lines.push("    case .none:".to_string());
+                    lines.push(format!(
+                        "      try container.encodeNil(forKey: .{})",
+                        snake_to_camel(&field.name)
+                    ));
+                    lines.push("    }".to_string());
+                } else {
+                    lines.push(format!(
+                        "    try container.encode(self.{}, forKey: .{})",
+                        snake_to_camel(&field.name),
+                        snake_to_camel(&field.name)
+                    ));
+                }
+            }
+            lines.push("  }".to_string());
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+impl GenCode for StructDefinition {
+    fn swift_client_code(&self, ctx: &Context) -> String {
+        self.swift_code(ctx, &["Codable", "Equatable"])
+    }
+
+    fn swift_server_code(&self, ctx: &Context) -> String {
+        // `Content` already implies `Codable` and lets Vapor decode/encode the type
+        self.swift_code(ctx, &["Content", "Equatable"])
+    }
+
+    fn go_client_code(&self, ctx: &Context) -> String {
+        let struct_name = format!(
+            "{}{}",
+            ctx.override_name.as_ref().map_or(&self.name, |n| n),
+            generic_params(&self.generics, "[", "]", |param| format!("{param} any"))
+        );
+
+        if self.fields.is_empty() {
+            return format!("type {struct_name} struct{{}}");
+        }
+
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                [
+                    snake_to_pascal(&field.name),
+                    field.t.go_client_code(ctx),
+                    format!("`json:\"{}\"`", snake_to_camel(&field.name)),
+                ]
+            })
+            .collect::<Vec<[String; 3]>>();
+
+        let mut lines = vec![format!("type {struct_name} struct {{")];
+        lines.extend(align_go_columns(&fields));
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+
+    fn python_client_code(&self, ctx: &Context) -> String {
+        let struct_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+
+        let mut lines = vec![format!(
+            "class {struct_name}(FenModel{}):",
+            generic_params(&self.generics, ", Generic[", "]", ToString::to_string)
+        )];
+        if self.fields.is_empty() {
+            lines.push("    pass".to_string());
+        }
+        for field in &self.fields {
+            lines.push(field.python_client_code(ctx));
+        }
+
+        lines.join("\n")
+    }
+
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        let struct_name = format!(
+            "{}{}",
+            ctx.override_name.as_ref().map_or(&self.name, |n| n),
+            generic_params(&self.generics, "<", ">", ToString::to_string)
+        );
+
+        let mut lines = vec!["@Serializable".to_string()];
+        if self.fields.is_empty() {
+            // data classes need at least one property
+            lines.push(format!("class {struct_name}"));
+        } else {
+            lines.push(format!("data class {struct_name}("));
+            for field in &self.fields {
+                lines.push(field.kotlin_client_code(ctx));
+            }
+            lines.push(")".to_string());
+        }
+
+        lines.join("\n")
+    }
+
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        let struct_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+        let params = generic_params(&self.generics, "<", ">", ToString::to_string);
+
+        let mut lines = vec![format!("export interface {struct_name}{params} {{")];
+        for field in &self.fields {
+            lines.push(field.typescript_client_code(ctx));
+        }
+        lines.push("}".to_string());
+
+        // decoder
+        lines.push(String::new());
+        lines.push(format!(
+            "export function decode{struct_name}{params}(json: any{}): {struct_name}{params} {{",
+            typescript_decoder_params(&self.generics)
+        ));
+        lines.push("  return {".to_string());
+        for field in &self.fields {
+            let key = snake_to_camel(&field.name);
+            lines.push(format!(
+                "    {key}: {},",
+                typescript_decoder(&field.t, &format!("json.{key}"))
+            ));
+        }
+        lines.push("  };".to_string());
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+
+    fn rust_server_code(&self, ctx: &Context) -> String {
+        let mut lines = doc_comment(self.description.as_deref(), "");
+
+        lines.push("#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]".to_string());
+        lines.push("#[serde(rename_all = \"camelCase\")]".to_string());
+        lines.push(format!(
+            "pub struct {}{} {{",
+            ctx.override_name.as_ref().map_or(&self.name, |name| name),
+            generic_params(&self.generics, "<", ">", ToString::to_string)
+        ));
+        let struct_name = ctx.override_name.as_ref().map_or(&self.name, |name| name);
+        for field in &self.fields {
+            lines.extend(doc_comment(field.description.as_deref(), "    "));
+            if let Some(message) = &field.deprecated {
+                lines.push(format!("    {}", rust_deprecated(message)));
+            }
+            if field.default.is_some() {
+                lines.push(format!(
+                    "    #[serde(default = \"{}\")]",
+                    rust_default_fn_name(struct_name, field)
+                ));
+            }
+            lines.push(field.rust_server_code(ctx));
+        }
+        lines.push("}".to_string());
+
+        for default_fn in self
+            .fields
+            .iter()
+            .filter_map(|field| rust_default_fn(struct_name, field))
+        {
+            lines.push(String::new());
+            lines.push(default_fn);
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl GenCode for Field {
+    fn swift_client_code(&self, ctx: &Context) -> String {
+        format!(
+            "  var {}: {}",
+            snake_to_camel(&self.name),
+            self.t.swift_client_code(ctx)
+        )
+    }
+
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        format!(
+            "  {}: {};",
+            snake_to_camel(&self.name),
+            self.t.typescript_client_code(ctx)
+        )
+    }
+
+    fn python_client_code(&self, ctx: &Context) -> String {
+        format!("    {}: {}", self.name, self.t.python_client_code(ctx))
+    }
+
+    fn go_client_code(&self, ctx: &Context) -> String {
+        format!(
+            "\t{} {} `json:\"{}\"`",
+            snake_to_pascal(&self.name),
+            self.t.go_client_code(ctx),
+            snake_to_camel(&self.name)
+        )
+    }
+
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        format!(
+            "    val {}: {},",
+            snake_to_camel(&self.name),
+            self.t.kotlin_client_code(ctx)
+        )
+    }
+
+    fn rust_server_code(&self, ctx: &Context) -> String {
+        let attribute = rust_serde_with(&self.t)
+            .map_or_else(String::new, |with| format!("    #[serde({with})]\n"));
+        format!(
+            "{attribute}    pub {}: {},",
+            &self.name,
+            self.t.rust_server_code(ctx)
+        )
+    }
+}
+
+impl EnumDefinition {
+    /// The enum's Swift definition, conforming to `protocols` (like the struct's).
+    fn swift_code(&self, ctx: &Context, protocols: &[&str]) -> String {
+        let mut lines = doc_comment(self.description.as_deref(), "");
+
+        let enum_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+
+        lines.push(format!(
+            "enum {enum_name}{}: {} {{",
+            swift_generic_params(&self.generics, protocols),
+            protocols.join(", ")
+        ));
+        for variant in &self.variants {
+            lines.extend(doc_comment(variant.description.as_deref(), "  "));
+            if let Some(message) = &variant.deprecated {
+                lines.push(format!("  {}", swift_deprecated(message)));
+            }
+            lines.push(variant.swift_client_code(ctx));
+        }
+
+        // coding keys
+        lines.push(String::new());
+        lines.push("  private enum CodingKeys: String, CodingKey {".to_string());
+        lines.push("    case type".to_string());
+        if self.variants.iter().any(|v| v.t.is_some()) {
+            lines.push("    case value".to_string());
+        }
+        lines.push("  }".to_string());
+
+        // key types
+        let key_types_name = format!("{enum_name}Type");
+        lines.push(String::new());
+        lines.push(format!(
+            "  private enum {key_types_name}: String, Codable {{",
+        ));
+        for variant in &self.variants {
+            lines.push(format!("    case {}", snake_to_camel(&variant.name)));
+        }
+        lines.push("  }".to_string());
+
+        // init from decoder
+        lines.push(String::new());
+        lines.push("  init(from decoder: Decoder) throws {".to_string());
+        lines.push(
+            "    let container = try decoder.container(keyedBy: CodingKeys.self)".to_string(),
+        );
+        lines.push(format!(
+            "    let type = try container.decode({key_types_name}.self, forKey: .type)"
+        ));
+        lines.push(String::new());
+        lines.push("    switch type {".to_string());
+        for variant in &self.variants {
+            lines.push(format!("    case .{}:", snake_to_camel(&variant.name)));
+            match &variant.t {
+                Some(Type::Optional(t, _)) => {
+                    lines.push(format!(
+                        "      let value = try container.decodeIfPresent({}.self, forKey: .value)",
+                        t.swift_client_code(ctx)
+                    ));
+                    lines.push(format!(
+                        "      self = .{}(value)",
+                        snake_to_camel(&variant.name)
+                    ));
+                }
+                Some(other) => {
+                    lines.push(format!(
+                        "      let value = try container.decode({}.self, forKey: .value)",
+                        other.swift_client_code(ctx)
+                    ));
+                    lines.push(format!(
+                        "      self = .{}(value)",
+                        snake_to_camel(&variant.name)
+                    ));
+                }
+                None => {
+                    lines.push(format!("      self = .{}", snake_to_camel(&variant.name)));
+                }
+            }
+        }
+        lines.push("    }".to_string());
+        lines.push("  }".to_string());
+
+        // encode
+        lines.push(String::new());
+        lines.push("  func encode(to encoder: Encoder) throws {".to_string());
+        lines.push("    var container = encoder.container(keyedBy: CodingKeys.self)".to_string());
+        lines.push(String::new());
+        lines.push("    switch self {".to_string());
+        for variant in &self.variants {
+            if variant.t.is_some() {
+                lines.push(format!(
+                    "    case .{}(let value):",
+                    snake_to_camel(&variant.name)
+                ));
+                lines.push(format!(
+                    "      try container.encode({}.{}, forKey: .type)",
+                    key_types_name,
+                    snake_to_camel(&variant.name)
+                ));
+                lines.push("      try container.encode(value, forKey: .value)".to_string());
+            } else {
+                lines.push(format!("    case .{}:", snake_to_camel(&variant.name)));
+                lines.push(format!(
+                    "      try container.encode({}.{}, forKey: .type)",
+                    key_types_name,
+                    snake_to_camel(&variant.name)
+                ));
+            }
+        }
+        lines.push("    }".to_string());
+        lines.push("  }".to_string());
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+impl GenCode for EnumDefinition {
+    fn swift_client_code(&self, ctx: &Context) -> String {
+        self.swift_code(ctx, &["Codable", "Equatable"])
+    }
+
+    fn swift_server_code(&self, ctx: &Context) -> String {
+        // `Content` already implies `Codable` and lets Vapor decode/encode the type
+        self.swift_code(ctx, &["Content", "Equatable"])
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn go_client_code(&self, ctx: &Context) -> String {
+        let enum_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+        let tag_type = format!("{enum_name}Type");
+
+        // the `type` tag of each variant
+        let tags = self
+            .variants
+            .iter()
+            .map(|variant| {
+                [
+                    format!("{enum_name}{}", snake_to_pascal(&variant.name)),
+                    tag_type.clone(),
+                    format!("= \"{}\"", snake_to_camel(&variant.name)),
+                ]
+            })
+            .collect::<Vec<[String; 3]>>();
+
+        // the enum holds its tag, plus a field for each associated value (only meaningful
+        // when the tag matches)
+        let mut fields = vec![["Type".to_string(), tag_type.clone()]];
+        for variant in &self.variants {
+            if let Some(t) = &variant.t {
+                fields.push([snake_to_pascal(&variant.name), t.go_client_code(ctx)]);
+            }
+        }
+
+        let type_params = generic_params(&self.generics, "[", "]", |param| format!("{param} any"));
+        let type_args = generic_params(&self.generics, "[", "]", ToString::to_string);
+        let mut definition = vec![format!("type {enum_name}{type_params} struct {{")];
+        definition.extend(align_go_columns(&fields));
+        definition.push("}".to_string());
+
+        // encode as `{ "type": ..., "value": ... }`
+        let mut marshal = vec![
+            format!("func (e {enum_name}{type_args}) MarshalJSON() ([]byte, error) {{"),
+            "\tswitch e.Type {".to_string(),
+        ];
+        for variant in &self.variants {
+            if let Some(t) = &variant.t {
+                let value_fields = align_go_columns(&[
+                    [
+                        "Type".to_string(),
+                        tag_type.clone(),
+                        "`json:\"type\"`".to_string(),
+                    ],
+                    [
+                        "Value".to_string(),
+                        t.go_client_code(ctx),
+                        "`json:\"value\"`".to_string(),
+                    ],
+                ]);
+                marshal.push(format!(
+                    "\tcase {enum_name}{}:",
+                    snake_to_pascal(&variant.name)
+                ));
+                marshal.push("\t\treturn json.Marshal(struct {".to_string());
+                for field in value_fields {
+                    marshal.push(format!("\t\t{field}"));
+                }
+                marshal.push(format!(
+                    "\t\t}}{{e.Type, e.{}}})",
+                    snake_to_pascal(&variant.name)
+                ));
+            }
+        }
+        if self.variants.iter().any(|variant| variant.t.is_some()) {
+            marshal.extend([
+                "\tdefault:".to_string(),
+                "\t\treturn json.Marshal(struct {".to_string(),
+                format!("\t\t\tType {tag_type} `json:\"type\"`"),
+                "\t\t}{e.Type})".to_string(),
+                "\t}".to_string(),
+            ]);
+        } else {
+            // no associated values, so every variant is encoded the same way
+            marshal.truncate(1);
+            marshal.extend([
+                "\treturn json.Marshal(struct {".to_string(),
+                format!("\t\tType {tag_type} `json:\"type\"`"),
+                "\t}{e.Type})".to_string(),
+            ]);
+        }
+        marshal.push("}".to_string());
+
+        // decode from `{ "type": ..., "value": ... }`
+        let mut unmarshal = vec![
+            format!("func (e *{enum_name}{type_args}) UnmarshalJSON(data []byte) error {{"),
+            "\tvar raw struct {".to_string(),
+        ];
+        for field in align_go_columns(&[
+            [
+                "Type".to_string(),
+                tag_type.clone(),
+                "`json:\"type\"`".to_string(),
+            ],
+            [
+                "Value".to_string(),
+                "json.RawMessage".to_string(),
+                "`json:\"value\"`".to_string(),
+            ],
+        ]) {
+            unmarshal.push(format!("\t{field}"));
+        }
+        unmarshal.extend([
+            "\t}".to_string(),
+            "\tif err := json.Unmarshal(data, &raw); err != nil {".to_string(),
+            "\t\treturn err".to_string(),
+            "\t}".to_string(),
+            "\te.Type = raw.Type".to_string(),
+            "\tswitch raw.Type {".to_string(),
+        ]);
+        let unit_tags = self
+            .variants
+            .iter()
+            .filter(|variant| variant.t.is_none())
+            .map(|variant| format!("{enum_name}{}", snake_to_pascal(&variant.name)))
+            .collect::<Vec<String>>();
+        if !unit_tags.is_empty() {
+            unmarshal.push(format!("\tcase {}:", unit_tags.join(", ")));
+            unmarshal.push("\t\treturn nil".to_string());
+        }
+        for variant in &self.variants {
+            if variant.t.is_some() {
+                let variant_name = snake_to_pascal(&variant.name);
+                unmarshal.push(format!("\tcase {enum_name}{variant_name}:"));
+                unmarshal.push(format!(
+                    "\t\treturn json.Unmarshal(raw.Value, &e.{variant_name})"
+                ));
+            }
+        }
+        unmarshal.extend([
+            "\tdefault:".to_string(),
+            format!("\t\treturn fmt.Errorf(\"unknown {tag_type} %q\", raw.Type)"),
+            "\t}".to_string(),
+            "}".to_string(),
+        ]);
+
+        let mut tag_lines = vec![format!("type {tag_type} string"), String::new()];
+        tag_lines.push("const (".to_string());
+        tag_lines.extend(align_go_columns(&tags));
+        tag_lines.push(")".to_string());
+
+        [
+            tag_lines.join("\n"),
+            definition.join("\n"),
+            marshal.join("\n"),
+            unmarshal.join("\n"),
+        ]
+        .join("\n\n")
+    }
+
+    fn python_client_code(&self, ctx: &Context) -> String {
+        let enum_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+
+        // one model per variant, tagged by a literal `type` (and holding any `value`)
+        let mut classes = vec![];
+        let mut variant_names = vec![];
+        for variant in &self.variants {
+            let variant_name = format!("{enum_name}{}", snake_to_pascal(&variant.name));
+            // only the variants holding a type parameter are generic
+            let params = used_params(variant.t.as_ref(), &self.generics);
+            classes.push(format!(
+                "class {variant_name}(FenModel{}):\n{}",
+                generic_params(&params, ", Generic[", "]", ToString::to_string),
+                variant.python_client_code(ctx)
+            ));
+            variant_names.push(format!(
+                "{variant_name}{}",
+                generic_params(&params, "[", "]", ToString::to_string)
+            ));
+        }
+
+        // the enum itself is a union discriminated by `type`
+        if variant_names.len() == 1 {
+            classes.push(format!("{enum_name} = {}", variant_names[0]));
+        } else if !self.generics.is_empty() {
+            // a plain alias can't take type arguments, since pydantic models hide their
+            // type parameters from `typing`
+            classes.push(format!(
+                "{enum_name} = TypeAliasType(\"{enum_name}\", Annotated[Union[{}], Field(discriminator=\"type\")], type_params=({},))",
+                variant_names.join(", "),
+                self.generics.join(", ")
+            ));
+        } else {
+            classes.push(format!(
+                "{enum_name} = Annotated[Union[{}], Field(discriminator=\"type\")]",
+                variant_names.join(", ")
+            ));
+        }
+
+        classes.join("\n\n\n")
+    }
+
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        let enum_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+
+        // kotlinx.serialization tags sealed subclasses with `type` by default, and
+        // associated values live in a `value` property, matching the server's tagging
+        let mut lines = vec!["@Serializable".to_string()];
+        lines.push(format!(
+            "sealed class {enum_name}{} {{",
+            generic_params(&self.generics, "<", ">", |param| format!("out {param}"))
+        ));
+        for (i, variant) in self.variants.iter().enumerate() {
+            if i > 0 {
+                lines.push(String::new());
+            }
+            // variants only take the type parameters they hold, and are `Nothing` for the rest
+            let params = used_params(variant.t.as_ref(), &self.generics);
+            let args = generic_params(&self.generics, "<", ">", |param| {
+                if params.contains(&param.to_string()) {
+                    param.to_string()
+                } else {
+                    "Nothing".to_string()
+                }
+            });
+            let variant_code = format!(
+                "{} : {enum_name}{args}()",
+                variant.kotlin_client_code(ctx).replacen(
+                    "(val value",
+                    &format!(
+                        "{}(val value",
+                        generic_params(&params, "<", ">", ToString::to_string)
+                    ),
+                    1
+                )
+            );
+            for line in variant_code.lines() {
+                lines.push(format!("    {line}"));
+            }
+        }
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        let enum_name = ctx.override_name.as_ref().map_or(&self.name, |n| n);
+        let params = generic_params(&self.generics, "<", ">", ToString::to_string);
+
+        // discriminated union, tagged the same way as the server (`type` + `value`)
+        let mut lines = vec![];
+        if self.variants.is_empty() {
+            lines.push(format!("export type {enum_name}{params} = never;"));
+        } else {
+            lines.push(format!("export type {enum_name}{params} ="));
+            for variant in &self.variants {
+                lines.push(variant.typescript_client_code(ctx));
+            }
+            if let Some(last) = lines.last_mut() {
+                last.push(';');
+            }
+        }
+
+        // decoder
+        lines.push(String::new());
+        lines.push(format!(
+            "export function decode{enum_name}{params}(json: any{}): {enum_name}{params} {{",
+            typescript_decoder_params(&self.generics)
+        ));
+        lines.push("  switch (json.type) {".to_string());
+        for variant in &self.variants {
+            let tag = snake_to_camel(&variant.name);
+            lines.push(format!("    case \"{tag}\":"));
+            match &variant.t {
+                Some(t) => lines.push(format!(
+                    "      return {{ type: \"{tag}\", value: {} }};",
+                    typescript_decoder(t, "json.value")
+                )),
+                None => lines.push(format!("      return {{ type: \"{tag}\" }};")),
+            }
+        }
+        lines.push("    default:".to_string());
+        lines.push(format!(
+            "      throw new Error(`Unknown {enum_name} type: ${{json.type}}`);"
+        ));
+        lines.push("  }".to_string());
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+
+    fn rust_server_code(&self, ctx: &Context) -> String {
+        let mut lines = doc_comment(self.description.as_deref(), "");
+
+        lines.push(format!(
+            "#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq{})]",
+            if self.annotations.is_empty() {
+                ""
+            } else {
+                ", sqlx::Type"
+            }
+        ));
+        lines.push(format!(
+            "#[serde(tag = \"type\", {}rename_all = \"camelCase\")]",
+            if self.variants.iter().any(|v| v.t.is_some()) {
+                "content = \"value\", "
+            } else {
+                ""
+            }
+        ));
+        if self.annotations.contains(&"sqlxType".to_string()) {
+            lines.push(format!(
+                "#[sqlx(type_name = \"{}\", rename_all = \"SCREAMING_SNAKE_CASE\")]",
+                pascal_to_snake(&self.name)
+            ));
+        }
+        lines.push(format!(
+            "pub enum {}{} {{",
+            ctx.override_name.as_ref().map_or(&self.name, |n| n),
+            generic_params(&self.generics, "<", ">", ToString::to_string)
+        ));
+        for variant in &self.variants {
+            lines.extend(doc_comment(variant.description.as_deref(), "    "));
+            if let Some(message) = &variant.deprecated {
+                lines.push(format!("    {}", rust_deprecated(message)));
+            }
+            lines.push(variant.rust_server_code(ctx));
+        }
+        lines.push("}".to_string());
+
+        lines.join("\n")
+    }
+
+    fn rust_client_code(&self, ctx: &Context) -> String {
+        // annotations like `@sqlxType` only make sense on the server
+        Self {
+            annotations: vec![],
+            ..self.clone()
+        }
+        .rust_server_code(ctx)
+    }
+}
+
+impl GenCode for Variant {
+    fn swift_client_code(&self, ctx: &Context) -> String {
+        format!(
+            "  case {}{}",
+            snake_to_camel(&self.name),
+            self.t
+                .as_ref()
+                .map_or_else(String::new, |t| format!("({})", t.swift_client_code(ctx)))
+        )
+    }
+
+    fn go_client_code(&self, ctx: &Context) -> String {
+        // enums hold one field per associated value
+        self.t.as_ref().map_or_else(String::new, |t| {
+            format!(
+                "\t{} {}",
+                snake_to_pascal(&self.name),
+                t.go_client_code(ctx)
+            )
+        })
+    }
+
+    fn python_client_code(&self, ctx: &Context) -> String {
+        let tag = snake_to_camel(&self.name);
+        let mut lines = vec![format!("    type: Literal[\"{tag}\"] = \"{tag}\"")];
+        if let Some(t) = &self.t {
+            lines.push(format!("    value: {}", t.python_client_code(ctx)));
+        }
+
+        lines.join("\n")
+    }
+
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        let mut lines = vec![
+            "@Serializable".to_string(),
+            format!("@SerialName(\"{}\")", snake_to_camel(&self.name)),
+        ];
+        match &self.t {
+            Some(t) => lines.push(format!(
+                "data class {}(val value: {})",
+                snake_to_pascal(&self.name),
+                t.kotlin_client_code(ctx)
+            )),
+            None => lines.push(format!("data object {}", snake_to_pascal(&self.name))),
+        }
+
+        lines.join("\n")
+    }
+
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        let tag = snake_to_camel(&self.name);
+        self.t.as_ref().map_or_else(
+            || format!("  | {{ type: \"{tag}\" }}"),
+            |t| {
+                format!(
+                    "  | {{ type: \"{tag}\"; value: {} }}",
+                    t.typescript_client_code(ctx)
+                )
+            },
+        )
+    }
+
+    fn rust_server_code(&self, ctx: &Context) -> String {
+        format!(
+            "    {}{},",
+            snake_to_pascal(&self.name),
+            self.t.as_ref().map_or_else(String::new, |t| {
+                rust_serde_with(t).map_or_else(
+                    || format!("({})", t.rust_server_code(ctx)),
+                    |with| format!("(#[serde({with})] {})", t.rust_server_code(ctx)),
+                )
+            })
+        )
+    }
+}
+
+impl GenCode for Type {
+    fn swift_client_code(&self, ctx: &Context) -> String {
+        match &self {
+            Self::Named(n, _) => n.clone(),
+            Self::Optional(t, _) => format!("{}?", t.swift_client_code(ctx)),
+            Self::Array(t, _) => format!("[{}]", t.swift_client_code(ctx)),
+            Self::Map(k, v, _) => format!(
+                "[{}: {}]",
+                k.swift_client_code(ctx),
+                v.swift_client_code(ctx)
+            ),
+            Self::Primitive(p, _) => p.swift_client_code(ctx),
+            Self::Generic(n, args, _) => format!(
+                "{n}<{}>",
+                args.iter()
+                    .map(|t| t.swift_client_code(ctx))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        match &self {
+            Self::Named(n, _) => n.clone(),
+            Self::Optional(t, _) => format!("{} | null", t.typescript_client_code(ctx)),
+            Self::Array(t, _) => match t.as_ref() {
+                Self::Optional(..) => format!("({})[]", t.typescript_client_code(ctx)),
+                _ => format!("{}[]", t.typescript_client_code(ctx)),
+            },
+            Self::Map(k, v, _) => format!(
+                "Record<{}, {}>",
+                k.typescript_client_code(ctx),
+                v.typescript_client_code(ctx)
+            ),
+            Self::Primitive(p, _) => p.typescript_client_code(ctx),
+            Self::Generic(n, args, _) => format!(
+                "{n}<{}>",
+                args.iter()
+                    .map(|t| t.typescript_client_code(ctx))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn go_client_code(&self, ctx: &Context) -> String {
+        match &self {
+            Self::Named(n, _) => n.clone(),
+            Self::Optional(t, _) => format!("*{}", t.go_client_code(ctx)),
+            Self::Array(t, _) => format!("[]{}", t.go_client_code(ctx)),
+            Self::Map(k, v, _) => {
+                format!("map[{}]{}", k.go_client_code(ctx), v.go_client_code(ctx))
+            }
+            Self::Primitive(p, _) => p.go_client_code(ctx),
+            Self::Generic(n, args, _) => format!(
+                "{n}[{}]",
+                args.iter()
+                    .map(|t| t.go_client_code(ctx))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn python_client_code(&self, ctx: &Context) -> String {
+        match &self {
+            Self::Named(n, _) => n.clone(),
+            Self::Optional(t, _) => format!("Optional[{}]", t.python_client_code(ctx)),
+            Self::Array(t, _) => format!("list[{}]", t.python_client_code(ctx)),
+            Self::Map(k, v, _) => format!(
+                "dict[{}, {}]",
+                k.python_client_code(ctx),
+                v.python_client_code(ctx)
+            ),
+            Self::Primitive(p, _) => p.python_client_code(ctx),
+            Self::Generic(n, args, _) => format!(
+                "{n}[{}]",
+                args.iter()
+                    .map(|t| t.python_client_code(ctx))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        match &self {
+            Self::Named(n, _) => n.clone(),
+            Self::Optional(t, _) => format!("{}?", t.kotlin_client_code(ctx)),
+            Self::Array(t, _) => format!("List<{}>", t.kotlin_client_code(ctx)),
+            Self::Map(k, v, _) => format!(
+                "Map<{}, {}>",
+                k.kotlin_client_code(ctx),
+                v.kotlin_client_code(ctx)
+            ),
+            Self::Primitive(p, _) => p.kotlin_client_code(ctx),
+            Self::Generic(n, args, _) => format!(
+                "{n}<{}>",
+                args.iter()
+                    .map(|t| t.kotlin_client_code(ctx))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn rust_server_code(&self, ctx: &Context) -> String {
+        match &self {
+            Self::Named(n, _) => n.clone(),
+            Self::Optional(t, _) => format!("Option<{}>", t.rust_server_code(ctx)),
+            Self::Array(t, _) => format!("Vec<{}>", t.rust_server_code(ctx)),
+            Self::Map(k, v, _) => format!(
+                "HashMap<{}, {}>",
+                k.rust_server_code(ctx),
+                v.rust_server_code(ctx)
+            ),
+            Self::Primitive(p, _) => p.rust_server_code(ctx),
+            Self::Generic(n, args, _) => format!(
+                "{n}<{}>",
+                args.iter()
+                    .map(|t| t.rust_server_code(ctx))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+impl GenCode for Primitive {
+    fn swift_client_code(&self, _ctx: &Context) -> String {
+        match &self {
+            Self::Int => "Int".to_string(),
+            Self::Int32 => "Int32".to_string(),
+            Self::Int64 => "Int64".to_string(),
+            Self::UInt => "UInt".to_string(),
+            Self::Float => "Double".to_string(),
+            Self::Decimal => "Decimal".to_string(),
+            Self::String => "String".to_string(),
+            Self::Bool => "Bool".to_string(),
+            Self::Date => "Date".to_string(),
+            Self::Uuid => "UUID".to_string(),
+            Self::Bytes => "Data".to_string(),
+        }
+    }
+
+    fn typescript_client_code(&self, _ctx: &Context) -> String {
+        match &self {
+            // integers past 2^53 lose precision (see the language guide)
+            Self::Int | Self::Int32 | Self::Int64 | Self::UInt | Self::Float => {
+                "number".to_string()
+            }
+            // `Bytes` are left base64-encoded
+            Self::Decimal | Self::String | Self::Uuid | Self::Bytes => "string".to_string(),
+            Self::Bool => "boolean".to_string(),
+            Self::Date => "Date".to_string(),
+        }
+    }
+
+    fn go_client_code(&self, _ctx: &Context) -> String {
+        match &self {
+            Self::Int | Self::Int64 => "int64".to_string(),
+            Self::Int32 => "int32".to_string(),
+            Self::UInt => "uint64".to_string(),
+            Self::Float => "float64".to_string(),
+            Self::Decimal => "decimal.Decimal".to_string(),
+            Self::String => "string".to_string(),
+            Self::Bool => "bool".to_string(),
+            Self::Date => "time.Time".to_string(),
+            Self::Uuid => "uuid.UUID".to_string(),
+            // `encoding/json` already encodes byte slices as base64
+            Self::Bytes => "[]byte".to_string(),
+        }
+    }
+
+    fn python_client_code(&self, _ctx: &Context) -> String {
+        match &self {
+            Self::Int | Self::Int32 | Self::Int64 | Self::UInt => "int".to_string(),
+            Self::Float => "float".to_string(),
+            Self::Decimal => "Decimal".to_string(),
+            Self::String => "str".to_string(),
+            Self::Bool => "bool".to_string(),
+            Self::Date => "datetime".to_string(),
+            Self::Uuid => "UUID".to_string(),
+            Self::Bytes => "Bytes".to_string(),
+        }
+    }
+
+    fn kotlin_client_code(&self, _ctx: &Context) -> String {
+        match &self {
+            Self::Int | Self::Int64 => "Long".to_string(),
+            Self::Int32 => "Int".to_string(),
+            Self::UInt => "ULong".to_string(),
+            Self::Float => "Double".to_string(),
+            Self::Decimal => "Decimal".to_string(),
+            Self::String => "String".to_string(),
+            Self::Bool => "Boolean".to_string(),
+            Self::Date => "Instant".to_string(),
+            Self::Uuid => "UUID".to_string(),
+            Self::Bytes => "Bytes".to_string(),
+        }
+    }
+
+    fn rust_server_code(&self, _ctx: &Context) -> String {
+        match &self {
+            Self::Int => "isize".to_string(),
+            Self::Int32 => "i32".to_string(),
+            Self::Int64 => "i64".to_string(),
+            Self::UInt => "usize".to_string(),
+            Self::Float => "f64".to_string(),
+            Self::Decimal => "Decimal".to_string(),
+            Self::String => "String".to_string(),
+            Self::Bool => "bool".to_string(),
+            Self::Date => "DateTime<Utc>".to_string(),
+            Self::Uuid => "Uuid".to_string(),
+            Self::Bytes => "Vec<u8>".to_string(),
+        }
+    }
+}
+
+impl SharedTypes {
+    /// Runs `f` on every shared type definition, joining the results with `separator`.
+    fn definitions(&self, f: impl Fn(&dyn GenCode) -> String, separator: &str) -> String {
+        self.structs
+            .iter()
+            .map(|struct_def| f(struct_def))
+            .chain(self.enums.iter().map(|enum_def| f(enum_def)))
+            .collect::<Vec<String>>()
+            .join(separator)
+    }
+}
+
+impl GenCode for SharedTypes {
+    fn swift_client_code(&self, ctx: &Context) -> String {
+        let code = self.definitions(|def| def.swift_client_code(ctx), "\n\n");
+        if swift_needs_foundation(&code) {
+            "import Foundation\n\n".to_string() + &code
+        } else {
+            code
+        }
+    }
+
+    fn swift_server_code(&self, ctx: &Context) -> String {
+        format!(
+            "import Vapor\n\n{}",
+            self.definitions(|def| def.swift_server_code(ctx), "\n\n")
+        )
+    }
+
+    fn typescript_client_code(&self, ctx: &Context) -> String {
+        self.definitions(|def| def.typescript_client_code(ctx), "\n\n")
+    }
+
+    fn kotlin_client_code(&self, ctx: &Context) -> String {
+        with_kotlin_imports(&self.definitions(|def| def.kotlin_client_code(ctx), "\n\n"))
+    }
+
+    fn python_client_code(&self, ctx: &Context) -> String {
+        with_python_imports(
+            &self.definitions(|def| def.python_client_code(ctx), "\n\n\n"),
+            &[],
+        )
+    }
+
+    fn go_client_code(&self, ctx: &Context) -> String {
+        with_go_imports(&self.definitions(|def| def.go_client_code(ctx), "\n\n"))
+    }
+
+    fn rust_server_code(&self, ctx: &Context) -> String {
+        with_rust_imports(self.definitions(|def| def.rust_server_code(ctx), "\n\n"))
+    }
+
+    fn rust_client_code(&self, ctx: &Context) -> String {
+        with_rust_imports(self.definitions(|def| def.rust_client_code(ctx), "\n\n"))
+    }
+}
+
+/// `///` lines for a description (the same syntax works for both Swift and Rust).
+fn doc_comment(description: Option<&str>, indent: &str) -> Vec<String> {
+    description.map_or_else(Vec::new, |description| {
+        description
+            .lines()
+            .map(|line| format!("{indent}/// {line}").trim_end().to_string())
+            .collect()
+    })
+}
+
+/// A route's path, with each `{param}` swapped for `param(name)`. The rest can go in a string
+/// literal as it is, since the parser only allows characters that are safe in a URL.
+fn interpolate_path(route: &FileNode, param: impl Fn(&str) -> String) -> String {
+    route
+        .http_path()
+        .split('/')
+        .map(|segment| {
+            segment
+                .strip_prefix('{')
+                .and_then(|segment| segment.strip_suffix('}'))
+                .map_or_else(|| segment.to_string(), &param)
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+/// A `@query` or `@header` field's value as an optional string, which is how Swift sends them.
+fn swift_param_text(name: &str, t: &Type) -> String {
+    match t {
+        Type::Primitive(Primitive::String, _) => name.to_string(),
+        Type::Optional(t, _) if matches!(**t, Type::Primitive(Primitive::String, _)) => {
+            name.to_string()
+        }
+        Type::Optional(..) => format!("{name}.map {{ \"\\($0)\" }}"),
+        _ => format!("\"\\({name})\""),
+    }
+}
+
+/// The fields of the `param` that Go sends a `@query` or `@header` field as (with a header's
+/// name, or a query parameter's JSON key).
+fn go_param(field: &Field) -> String {
+    format!(
+        "{{\"{}\", input.{}}}",
+        if field.location == Some(ParamLocation::Header) {
+            field.header_name()
+        } else {
+            snake_to_camel(&field.name)
+        },
+        snake_to_pascal(&field.name)
+    )
+}
+
+/// A `@query` or `@header` field's value as an `Option<String>`, which is how the Rust client
+/// sends them.
+fn rust_param_text(name: &str, t: &Type) -> String {
+    match t {
+        Type::Optional(..) => format!("input.{name}.as_ref().map(ToString::to_string)"),
+        _ => format!("Some(input.{name}.to_string())"),
+    }
+}
+
+fn rust_deprecated(message: &str) -> String {
+    format!("#[deprecated(note = {message:?})]")
+}
+
+fn swift_deprecated(message: &str) -> String {
+    format!("@available(*, deprecated, message: {message:?})")
+}
+
+/// The type parameter list of a generic definition, with each parameter written by `param`
+/// (or nothing, if it isn't generic).
+fn generic_params(
+    generics: &[String],
+    open: &str,
+    close: &str,
+    param: impl Fn(&str) -> String,
+) -> String {
+    if generics.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        "{open}{}{close}",
+        generics
+            .iter()
+            .map(|p| param(p))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+/// Swift type parameters need the same conformances as the type (like `Codable` and
+/// `Equatable`) for the type to have them.
+fn swift_generic_params(generics: &[String], protocols: &[&str]) -> String {
+    generic_params(generics, "<", ">", |param| {
+        format!("{param}: {}", protocols.join(" & "))
+    })
+}
+
+/// A field's default value as a Swift expression.
+fn swift_default(default: &DefaultValue) -> String {
+    match default {
+        DefaultValue::Bool(value) => value.to_string(),
+        DefaultValue::Number(value) => value.clone(),
+        DefaultValue::String(value) => format!("{value:?}"),
+        DefaultValue::Variant(variant) => format!(".{}", snake_to_camel(variant)),
+    }
+}
+
+/// A Swift memberwise init, where fields with defaults (and optionals) can be left out.
+fn swift_memberwise_init(fields: &[Field], ctx: &Context) -> Vec<String> {
+    let params = fields
+        .iter()
+        .map(|field| {
+            let default = match (&field.default, &field.t) {
+                (Some(default), _) => format!(" = {}", swift_default(default)),
+                (None, Type::Optional(..)) => " = nil".to_string(),
+                (None, _) => String::new(),
+            };
+            format!(
+                "{}: {}{default}",
+                snake_to_camel(&field.name),
+                field.t.swift_client_code(ctx)
+            )
+        })
+        .collect::<Vec<String>>();
+
+    let mut lines = vec![format!("  init({}) {{", params.join(", "))];
+    for field in fields {
+        let name = snake_to_camel(&field.name);
+        lines.push(format!("    self.{name} = {name}"));
+    }
+    lines.push("  }".to_string());
+    lines
+}
+
+/// A Swift decoder init that falls back to each field's default when it's missing.
+fn swift_decoder_init(fields: &[Field], ctx: &Context) -> Vec<String> {
+    let mut lines = vec![
+        "  init(from decoder: Decoder) throws {".to_string(),
+        "    let container = try decoder.container(keyedBy: CodingKeys.self)".to_string(),
+    ];
+    for field in fields {
+        let name = snake_to_camel(&field.name);
+        let decoded = match (&field.t, &field.default) {
+            // an explicit `null` is still `nil`, so only a missing key gets the default
+            (Type::Optional(t, _), Some(default)) => format!(
+                "try container.contains(.{name}) ? container.decodeIfPresent({}.self, forKey: .{name}) : {}",
+                t.swift_client_code(ctx),
+                swift_default(default)
+            ),
+            (Type::Optional(t, _), None) => format!(
+                "try container.decodeIfPresent({}.self, forKey: .{name})",
+                t.swift_client_code(ctx)
+            ),
+            (t, Some(default)) => format!(
+                "try container.decodeIfPresent({}.self, forKey: .{name}) ?? {}",
+                t.swift_client_code(ctx),
+                swift_default(default)
+            ),
+            (t, None) => format!(
+                "try container.decode({}.self, forKey: .{name})",
+                t.swift_client_code(ctx)
+            ),
+        };
+        lines.push(format!("    self.{name} = {decoded}"));
+    }
+    lines.push("  }".to_string());
+    lines
+}
+
+/// A generic type's TypeScript decoder takes a decoder for each type parameter.
+fn typescript_decoder_params(generics: &[String]) -> String {
+    generics
+        .iter()
+        .map(|param| format!(", decode{param}: (json: any) => {param}"))
+        .collect::<Vec<String>>()
+        .concat()
+}
+
+/// The type parameters in `generics` that `t` refers to.
+fn used_params(t: Option<&Type>, generics: &[String]) -> Vec<String> {
+    fn mentions(t: &Type, param: &str) -> bool {
+        match t {
+            Type::Named(name, _) => name == param,
+            Type::Optional(t, _) | Type::Array(t, _) | Type::Map(_, t, _) => mentions(t, param),
+            Type::Primitive(..) => false,
+            Type::Generic(_, args, _) => args.iter().any(|arg| mentions(arg, param)),
+        }
+    }
+
+    generics
+        .iter()
+        .filter(|param| t.is_some_and(|t| mentions(t, param)))
+        .cloned()
+        .collect()
+}
+
+/// Builds a route's input struct from the route function's arguments.
+fn swift_input_payload(route_name: &str, fields: &[Field]) -> String {
+    let pairs = fields
+        .iter()
+        .map(|field| format!("{}: {}", snake_to_camel(&field.name), field.name))
+        .collect::<Vec<String>>();
+    format!("{route_name}Input({})", pairs.join(", "))
+}
+
+/// An extension on a route's input with a `validate` method that checks every field's
+/// constraints, so clients can catch bad input before sending it.
+fn swift_validate_extension(route_name: &str, fields: &[Field]) -> Vec<String> {
+    let mut lines = vec![
+        format!("extension {route_name}Input {{"),
+        "  /// The constraints this input breaks (the server checks them too).".to_string(),
+        "  func validate() -> [ValidationError] {".to_string(),
+        "    var errors: [ValidationError] = []".to_string(),
+    ];
+    for field in fields {
+        let name = snake_to_camel(&field.name);
+        // optional fields are only checked when they're present
+        let (t, value, binding) = match &field.t {
+            Type::Optional(t, _) => (&**t, name.clone(), format!("let {name} = self.{name}, ")),
+            t => (t, format!("self.{name}"), String::new()),
+        };
+        for constraint in &field.constraints {
+            let condition = match constraint {
+                Constraint::Min(n) => format!("{value} < {n}"),
+                Constraint::Max(n) => format!("{value} > {n}"),
+                Constraint::MinLength(n) => format!("{value}.count < {n}"),
+                Constraint::MaxLength(n) => format!("{value}.count > {n}"),
+                Constraint::Pattern(pattern) => {
+                    format!("{value}.range(of: {pattern:?}, options: .regularExpression) == nil")
+                }
+                Constraint::NonEmpty => format!("{value}.isEmpty"),
+            };
+            lines.push(format!("    if {binding}{condition} {{"));
+            lines.push(format!(
+                "      errors.append(ValidationError(field: {:?}, message: {:?}))",
+                name,
+                constraint_message(t, constraint)
+            ));
+            lines.push("    }".to_string());
+        }
+    }
+    lines.extend([
+        "    return errors".to_string(),
+        "  }".to_string(),
+        "}".to_string(),
+    ]);
+    lines
+}
+
+/// Whether generated Swift code uses a `Foundation` type.
+fn swift_needs_foundation(code: &str) -> bool {
+    // `Data` must be a whole word, since the client's `NoData` type isn't from `Foundation`
+    code.contains("Date")
+        || code.contains("UUID")
+        || code.contains("Decimal")
+        || code.contains(".regularExpression")
+        || code
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| word == "Data")
+}
+
+/// Indents Swift code by one level so it can be nested in another declaration.
+fn indent_swift(code: &str) -> String {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("  {line}")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Builds a `KSerializer` expression for `t`, used to encode request bodies and
+/// decode response values in the Kotlin client.
+fn kotlin_serializer(t: &Type) -> String {
+    match t {
+        Type::Named(name, _) => format!("{name}.serializer()"),
+        Type::Generic(name, args, _) => format!(
+            "{name}.serializer({})",
+            args.iter()
+                .map(kotlin_serializer)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Type::Optional(inner, _) => format!("{}.nullable", kotlin_serializer(inner)),
+        Type::Array(inner, _) => format!("ListSerializer({})", kotlin_serializer(inner)),
+        Type::Map(key, value, _) => format!(
+            "MapSerializer({}, {})",
+            kotlin_serializer(key),
+            kotlin_serializer(value)
+        ),
+        Type::Primitive(Primitive::Uuid, _) => "UUIDSerializer".to_string(),
+        Type::Primitive(Primitive::Decimal, _) => "DecimalSerializer".to_string(),
+        Type::Primitive(Primitive::Bytes, _) => "BytesSerializer".to_string(),
+        Type::Primitive(p, _) => format!(
+            "{}.serializer()",
+            p.kotlin_client_code(&Context {
+                override_name: None,
+            })
+        ),
+    }
+}
+
+/// Prepends the `import` statements needed by generated Kotlin code.
+fn with_kotlin_imports(code: &str) -> String {
+    let mut imports = vec![];
+    if code.contains("Instant") {
+        imports.push("import kotlinx.datetime.Instant");
+    }
+    if code.contains("@SerialName") {
+        imports.push("import kotlinx.serialization.SerialName");
+    }
+    if code.contains("@Serializable") {
+        imports.push("import kotlinx.serialization.Serializable");
+    }
+    if code.contains("ListSerializer(") {
+        imports.push("import kotlinx.serialization.builtins.ListSerializer");
+    }
+    if code.contains("MapSerializer(") {
+        imports.push("import kotlinx.serialization.builtins.MapSerializer");
+    }
+    if code.contains(".nullable") {
+        imports.push("import kotlinx.serialization.builtins.nullable");
+    }
+    if [
+        "Int", "Long", "ULong", "Double", "String", "Boolean", "Instant",
+    ]
+    .iter()
+    .any(|t| code.contains(&format!("{t}.serializer()")))
+    {
+        imports.push("import kotlinx.serialization.builtins.serializer");
+    }
+
+    if imports.is_empty() {
+        code.to_string()
+    } else {
+        format!("{}\n\n{code}", imports.join("\n"))
+    }
+}
+
+/// Type definitions for a route's input, output, and helpers, shared by the Go client and
+/// server.
+fn go_type_definitions(node: &FileNode, ctx: &Context) -> Vec<String> {
+    let mut definitions = vec![];
+
+    // if input is a struct or enum, define it
+    if let Some(IOType::Struct(s)) = &node.input {
+        definitions.push(s.go_client_code(&Context {
+            override_name: Some(node.name.clone() + "Input"),
+        }));
+    } else if let Some(IOType::Enum(e)) = &node.input {
+        definitions.push(e.go_client_code(&Context {
+            override_name: Some(node.name.clone() + "Input"),
+        }));
+    }
+
+    // if output is a struct or enum, define it
+    if let Some(IOType::Struct(s)) = &node.output {
+        definitions.push(s.go_client_code(&Context {
+            override_name: Some(node.name.clone() + "Output"),
+        }));
+    } else if let Some(IOType::Enum(e)) = &node.output {
+        definitions.push(e.go_client_code(&Context {
+            override_name: Some(node.name.clone() + "Output"),
+        }));
+    }
+
+    // if error is a struct or enum, define it
+    if let Some(IOType::Struct(s)) = &node.error {
+        definitions.push(s.go_client_code(&Context {
+            override_name: Some(node.name.clone() + "Error"),
+        }));
+    } else if let Some(IOType::Enum(e)) = &node.error {
+        definitions.push(e.go_client_code(&Context {
+            override_name: Some(node.name.clone() + "Error"),
+        }));
+    }
+
+    // generate definitions for helper structs
+    for struct_def in &node.structs {
+        definitions.push(struct_def.go_client_code(ctx));
+    }
+
+    // generate definitions for helper enums
+    for enum_def in &node.enums {
+        definitions.push(enum_def.go_client_code(ctx));
+    }
+
+    definitions
+}
+
+/// Pads each column so that rows line up the way `gofmt` would align them.
+fn align_go_columns<const N: usize>(rows: &[[String; N]]) -> Vec<String> {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            let cells = row
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if i == N - 1 {
+                        cell.clone()
+                    } else {
+                        format!("{cell:<width$}", width = widths[i])
+                    }
+                })
+                .collect::<Vec<String>>();
+            format!("\t{}", cells.join(" "))
+        })
+        .collect()
+}
+
+/// Prepends the `import` block needed by generated Go code.
+fn with_go_imports(code: &str) -> String {
+    let mut std_imports = vec![];
+    if code.contains("context.") {
+        std_imports.push("\"context\"");
+    }
+    if code.contains("json.") {
+        std_imports.push("\"encoding/json\"");
+    }
+    if code.contains("fmt.") {
+        std_imports.push("\"fmt\"");
+    }
+    if code.contains("time.") {
+        std_imports.push("\"time\"");
+    }
+
+    let mut groups = vec![];
+    if !std_imports.is_empty() {
+        groups.push(
+            std_imports
+                .iter()
+                .map(|import| format!("\t{import}"))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        );
+    }
+    let mut third_party_imports = vec![];
+    if code.contains("uuid.") {
+        third_party_imports.push("\t\"github.com/google/uuid\"");
+    }
+    if code.contains("decimal.") {
+        third_party_imports.push("\t\"github.com/shopspring/decimal\"");
+    }
+    if !third_party_imports.is_empty() {
+        groups.push(third_party_imports.join("\n"));
+    }
+
+    if groups.is_empty() {
+        return code.to_string();
+    }
+    format!("import (\n{}\n)\n\n{code}", groups.join("\n\n"))
+}
+
+/// Prepends the `import` statements needed by generated Python code.
+fn with_python_imports(code: &str, shared_imports: &[String]) -> String {
+    let mut imports = vec!["from __future__ import annotations".to_string()];
+
+    let mut std_imports = vec![];
+    if code.contains("datetime") {
+        std_imports.push("from datetime import datetime".to_string());
+    }
+    if code.contains("Decimal") {
+        std_imports.push("from decimal import Decimal".to_string());
+    }
+    // the type parameters of generic classes (and aliases) are declared once for the whole file
+    let mut type_vars = [("Generic[", ']'), ("type_params=(", ',')]
+        .into_iter()
+        .flat_map(|(start, end)| {
+            code.match_indices(start)
+                .filter_map(move |(i, _)| code[i + start.len()..].split_once(end))
+        })
+        .flat_map(|(params, _)| params.split(", "))
+        .collect::<Vec<&str>>();
+    type_vars.sort_unstable();
+    type_vars.dedup();
+
+    let mut typing = ["Annotated", "Generic", "Literal", "Optional", "Union"]
+        .into_iter()
+        .filter(|name| code.contains(&format!("{name}[")))
+        .collect::<Vec<&str>>();
+    if !type_vars.is_empty() {
+        typing.push("TypeVar");
+        typing.sort_unstable();
+    }
+    if !typing.is_empty() {
+        std_imports.push(format!("from typing import {}", typing.join(", ")));
+    }
+    if code.contains("UUID") {
+        std_imports.push("from uuid import UUID".to_string());
+    }
+    if !std_imports.is_empty() {
+        imports.push(std_imports.join("\n"));
+    }
+
+    let mut third_party_imports = vec![];
+    if code.contains("Field(") {
+        third_party_imports.push("from pydantic import Field");
+    }
+    if code.contains("TypeAliasType(") {
+        third_party_imports.push("from typing_extensions import TypeAliasType");
+    }
+    if !third_party_imports.is_empty() {
+        imports.push(third_party_imports.join("\n"));
+    }
+    let mut local_imports = vec![];
+    let api_imports = [
+        "APIClient",
+        "Bytes",
+        "FenModel",
+        "path_segment",
+        "query_string",
+    ]
+    .into_iter()
+    .filter(|name| code.contains(name))
+    .collect::<Vec<&str>>();
+    if !api_imports.is_empty() {
+        local_imports.push(format!("from .api import {}", api_imports.join(", ")));
+    }
+    if !shared_imports.is_empty() {
+        local_imports.push(format!("from .shared import {}", shared_imports.join(", ")));
+    }
+    if !local_imports.is_empty() {
+        imports.push(local_imports.join("\n"));
+    }
+
+    let mut header = imports.join("\n\n");
+    if !type_vars.is_empty() {
+        header.push_str("\n\n\n");
+        header.push_str(
+            &type_vars
+                .iter()
+                .map(|param| format!("{param} = TypeVar(\"{param}\")"))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        );
+    }
+
+    format!("{header}\n\n\n{code}")
+}
+
+/// The serde attribute that encodes `Bytes` as base64, using the helpers in the generated
+/// `mod.rs` (the parser only allows `Bytes` directly on fields and enum values).
+fn rust_serde_with(t: &Type) -> Option<&'static str> {
+    match t {
+        Type::Primitive(Primitive::Bytes, _) => Some("with = \"super::fen_base64\""),
+        // a missing optional still decodes as `None`
+        Type::Optional(inner, _) if matches!(**inner, Type::Primitive(Primitive::Bytes, _)) => {
+            Some("default, with = \"super::fen_base64::option\"")
+        }
+        _ => None,
+    }
+}
+
+/// The function that serde calls for a field's default value (serde needs a path, rather
+/// than an expression).
+fn rust_default_fn(struct_name: &str, field: &Field) -> Option<String> {
+    let default = field.default.as_ref()?;
+    let (inner, is_optional) = match &field.t {
+        Type::Optional(t, _) => (&**t, true),
+        t => (t, false),
+    };
+
+    let mut value = match default {
+        DefaultValue::Bool(value) => value.to_string(),
+        DefaultValue::Number(value)
+            if matches!(*inner, Type::Primitive(Primitive::Float, _)) && !value.contains('.') =>
+        {
+            format!("{value}.0")
+        }
+        DefaultValue::Number(value) => value.clone(),
+        DefaultValue::String(value) => format!("{value:?}.to_string()"),
+        DefaultValue::Variant(variant) => format!(
+            "{}::{}",
+            inner.rust_server_code(&Context {
+                override_name: None
+            }),
+            snake_to_pascal(variant)
+        ),
+    };
+    if is_optional {
+        value = format!("Some({value})");
+    }
+
+    Some(
+        [
+            format!(
+                "fn {}() -> {} {{",
+                rust_default_fn_name(struct_name, field),
+                field.t.rust_server_code(&Context {
+                    override_name: None
+                })
+            ),
+            format!("    {value}"),
+            "}".to_string(),
+        ]
+        .join("\n"),
+    )
+}
+
+fn rust_default_fn_name(struct_name: &str, field: &Field) -> String {
+    format!("default_{}_{}", pascal_to_snake(struct_name), field.name)
+}
+
+/// Whether any of a struct's fields have constraints to check.
+fn has_constraints(fields: &[Field]) -> bool {
+    fields.iter().any(|field| !field.constraints.is_empty())
+}
+
+/// An `impl Input` with a `validate` method that checks every field's constraints.
+fn rust_validate_impl(fields: &[Field]) -> Option<String> {
+    if !has_constraints(fields) {
+        return None;
+    }
+
+    let mut lines = vec![];
+    // reading a deprecated field isn't something to warn the server about
+    if fields.iter().any(|field| field.deprecated.is_some()) {
+        lines.push("#[allow(deprecated)]".to_string());
+    }
+    lines.extend([
+        "impl Input {".to_string(),
+        "    /// Checks the input against its constraints, returning every one it breaks."
+            .to_string(),
+        "    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {".to_string(),
+    ]);
+
+    // patterns are only compiled once
+    for field in fields {
+        for constraint in &field.constraints {
+            if let Constraint::Pattern(pattern) = constraint {
+                lines.push(format!(
+                    "        static {}: LazyLock<Regex> =",
+                    rust_pattern_name(field)
+                ));
+                lines.push(format!(
+                    "            LazyLock::new(|| Regex::new({pattern:?}).unwrap());"
+                ));
+            }
+        }
+    }
+
+    lines.push("        let mut errors = vec![];".to_string());
+    for field in fields.iter().filter(|field| !field.constraints.is_empty()) {
+        // optional fields are only checked when they're present
+        let (t, value, indent) = match &field.t {
+            Type::Optional(t, _) => {
+                lines.push(format!(
+                    "        if let Some({name}) = &self.{name} {{",
+                    name = field.name
+                ));
+                (&**t, field.name.clone(), "            ")
+            }
+            t => (t, format!("self.{}", field.name), "        "),
+        };
+        let deref = if value.starts_with("self.") { "" } else { "*" };
+        let reference = if value.starts_with("self.") { "&" } else { "" };
+
+        for constraint in &field.constraints {
+            let condition = match constraint {
+                Constraint::Min(n) => format!("{deref}{value} < {}", rust_number(t, n)),
+                Constraint::Max(n) => format!("{deref}{value} > {}", rust_number(t, n)),
+                Constraint::MinLength(n) => format!("{} < {n}", rust_length(t, &value)),
+                Constraint::MaxLength(n) => format!("{} > {n}", rust_length(t, &value)),
+                Constraint::Pattern(_) => {
+                    format!("!{}.is_match({reference}{value})", rust_pattern_name(field))
+                }
+                Constraint::NonEmpty => format!("{value}.is_empty()"),
+            };
+            lines.push(format!("{indent}if {condition} {{"));
+            lines.push(format!(
+                "{indent}    errors.push(ValidationError::new({:?}, {:?}));",
+                snake_to_camel(&field.name),
+                constraint_message(t, constraint)
+            ));
+            lines.push(format!("{indent}}}"));
+        }
+
+        if indent.len() > 8 {
+            lines.push("        }".to_string());
+        }
+    }
+
+    lines.extend([
+        "        if errors.is_empty() {".to_string(),
+        "            Ok(())".to_string(),
+        "        } else {".to_string(),
+        "            Err(errors)".to_string(),
+        "        }".to_string(),
+        "    }".to_string(),
+        "}".to_string(),
+    ]);
+
+    Some(lines.join("\n"))
+}
+
+fn rust_pattern_name(field: &Field) -> String {
+    format!("{}_PATTERN", field.name.to_uppercase())
+}
+
+/// A number as a Rust literal of `t` (floats need a decimal point).
+fn rust_number(t: &Type, n: &str) -> String {
+    if matches!(*t, Type::Primitive(Primitive::Float, _)) && !n.contains('.') {
+        format!("{n}.0")
+    } else {
+        n.to_string()
+    }
+}
+
+/// The length of a string (in characters) or array.
+fn rust_length(t: &Type, value: &str) -> String {
+    match t {
+        Type::Primitive(Primitive::String, _) => format!("{value}.chars().count()"),
+        _ => format!("{value}.len()"),
+    }
+}
+
+/// Describes how a value breaks a constraint, like "must be at most 100".
+fn constraint_message(t: &Type, constraint: &Constraint) -> String {
+    let units = |n: usize| match (t, n) {
+        (Type::Primitive(Primitive::String, _), 1) => "character",
+        (Type::Primitive(Primitive::String, _), _) => "characters",
+        (_, 1) => "item",
+        (_, _) => "items",
+    };
+    match constraint {
+        Constraint::Min(n) => format!("must be at least {n}"),
+        Constraint::Max(n) => format!("must be at most {n}"),
+        Constraint::MinLength(n) => format!("must have at least {n} {}", units(*n)),
+        Constraint::MaxLength(n) => format!("must have at most {n} {}", units(*n)),
+        Constraint::Pattern(pattern) => format!("must match {pattern}"),
+        Constraint::NonEmpty => "must not be empty".to_string(),
+    }
+}
+
+/// The `use` statement for the shared types a route imports, if any.
+fn rust_shared_import(imports: &[String]) -> Option<String> {
+    match imports {
+        [] => None,
+        [name] => Some(format!("use super::shared::{name};")),
+        names => Some(format!("use super::shared::{{{}}};", names.join(", "))),
+    }
+}
+
+/// Prepends the `use` statements needed by generated Rust code.
+fn with_rust_imports(mut code: String) -> String {
+    if code.contains("Uuid")
+        || code.contains("Decimal")
+        || code.contains("HashMap<")
+        || code.contains("DateTime<Utc>")
+        || code.contains("Deserialize")
+        || code.contains("Serialize")
+    {
+        code = "\n".to_string() + &code;
+    }
+    if code.contains("Uuid") {
+        code = "use uuid::Uuid;\n".to_string() + &code;
+    }
+    if code.contains("LazyLock<") {
+        code = "use std::sync::LazyLock;\n".to_string() + &code;
+    }
+    if code.contains("HashMap<") {
+        code = "use std::collections::HashMap;\n".to_string() + &code;
+    }
+    if code.contains("Serialize") && code.contains("Deserialize") {
+        code = "use serde::{Deserialize, Serialize};\n".to_string() + &code;
+    } else if code.contains("Serialize") {
+        code = "use serde::Serialize;\n".to_string() + &code;
+    } else if code.contains("Deserialize") {
+        code = "use serde::Deserialize;\n".to_string() + &code;
+    }
+    if code.contains("Decimal") {
+        code = "use rust_decimal::Decimal;\n".to_string() + &code;
+    }
+    if code.contains("Regex::new") {
+        code = "use regex::Regex;\n".to_string() + &code;
+    }
+    if code.contains("DateTime<Utc>") {
+        code = "use chrono::{DateTime, Utc};\n".to_string() + &code;
+    }
+
+    code
+}
+
+/// Builds a TypeScript expression that turns the raw JSON in `value` into `t`,
+/// reviving ISO-8601 strings as `Date`s and running the decoders of helper types.
+fn typescript_decoder(t: &Type, value: &str) -> String {
+    match t {
+        // type parameters are decoded by the functions passed to a generic type's decoder
+        Type::Named(name, _) => format!("decode{name}({value})"),
+        Type::Generic(name, args, _) => format!(
+            "decode{name}({value}, {})",
+            args.iter()
+                .map(|arg| format!("(json: any) => {}", typescript_decoder(arg, "json")))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        Type::Optional(inner, _) => {
+            let inner_decoder = typescript_decoder(inner, value);
+            if inner_decoder == value {
+                format!("{value} ?? null")
+            } else {
+                format!("{value} == null ? null : {inner_decoder}")
+            }
+        }
+        Type::Array(inner, _) => {
+            let inner_decoder = typescript_decoder(inner, "item");
+            if inner_decoder == "item" {
+                value.to_string()
+            } else {
+                format!("{value}.map((item: any) => {inner_decoder})")
+            }
+        }
+        Type::Map(_, inner, _) => {
+            let inner_decoder = typescript_decoder(inner, "item");
+            if inner_decoder == "item" {
+                value.to_string()
+            } else {
+                format!(
+                    "Object.fromEntries(Object.entries({value}).map(([key, item]: [string, any]) => [key, {inner_decoder}]))"
+                )
+            }
+        }
+        Type::Primitive(Primitive::Date, _) => format!("new Date({value})"),
+        Type::Primitive(..) => value.to_string(),
+    }
+}
+
+mod swift_client_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_swift(fen_code: &str, swift_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let swift = ast.swift_client_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(swift, swift_code);
+    }
+
+    #[test]
+    fn just_output() {
+        expect_swift(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  /// Fetches all todos
+  func getTodos(sessionToken: String) async throws -> Response<[Todo]> {
+    return try await self.fetcher.get(from: "/_fen_/get-todos", sessionToken: sessionToken)
+  }
+}
+
+struct Todo: Codable, Equatable, Identifiable {
+  var id: UUID
+  var name: String
+  var description: String?
+  var due: Date?
+  var isCompleted: Bool
+
+  private enum CodingKeys: String, CodingKey {
+    case id
+    case name
+    case description
+    case due
+    case isCompleted
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.id, forKey: .id)
+    try container.encode(self.name, forKey: .name)
+    switch self.description {
+    case let .some(value):
+      try container.encode(value, forKey: .description)
+    case .none:
+      try container.encodeNil(forKey: .description)
+    }
+    switch self.due {
+    case let .some(value):
+      try container.encode(value, forKey: .due)
+    case .none:
+      try container.encodeNil(forKey: .due)
+    }
+    try container.encode(self.isCompleted, forKey: .isCompleted)
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn just_input() {
+        expect_swift(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  /// Completes or uncompletes a todo
+  func toggleTodoCompletion(input: UUID, sessionToken: String) async throws -> Response<NoData> {
+    return try await self.fetcher.post(
+      to: "/_fen_/toggle-todo-completion",
+      with: input,
+      returning: NoData.self,
+      sessionToken: sessionToken
+    )
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn struct_for_input_and_output() {
+        expect_swift(
+            r#"
+name: "Test"
+
+---
+
+@input {
+  id: UUID
+  foo: String
+  bar: [Date]?
+}
+
+@output {
+  stuff: [Thing]
+}
+
+---
+
+Thing {
+  type: ThingType
+  happy: Bool
+}
+
+ThingType (
+  first_option
+  second_option
+  third_option
+)
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func test(id: UUID, foo: String, bar: [Date]?) async throws -> Response<TestOutput> {
+    return try await self.fetcher.post(
+      to: "/_fen_/test",
+      with: TestInput(id: id, foo: foo, bar: bar),
+      returning: TestOutput.self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct TestInput: Codable, Equatable, Identifiable {
+  var id: UUID
+  var foo: String
+  var bar: [Date]?
+
+  private enum CodingKeys: String, CodingKey {
+    case id
+    case foo
+    case bar
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.id, forKey: .id)
+    try container.encode(self.foo, forKey: .foo)
+    switch self.bar {
+    case let .some(value):
+      try container.encode(value, forKey: .bar)
+    case .none:
+      try container.encodeNil(forKey: .bar)
+    }
+  }
+}
+
+struct TestOutput: Codable, Equatable {
+  var stuff: [Thing]
+}
+
+struct Thing: Codable, Equatable {
+  var type: ThingType
+  var happy: Bool
+}
+
+enum ThingType: Codable, Equatable {
+  case firstOption
+  case secondOption
+  case thirdOption
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+  }
+
+  private enum ThingTypeType: String, Codable {
+    case firstOption
+    case secondOption
+    case thirdOption
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(ThingTypeType.self, forKey: .type)
+
+    switch type {
+    case .firstOption:
+      self = .firstOption
+    case .secondOption:
+      self = .secondOption
+    case .thirdOption:
+      self = .thirdOption
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .firstOption:
+      try container.encode(ThingTypeType.firstOption, forKey: .type)
+    case .secondOption:
+      try container.encode(ThingTypeType.secondOption, forKey: .type)
+    case .thirdOption:
+      try container.encode(ThingTypeType.thirdOption, forKey: .type)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn struct_for_input_no_output() {
+        expect_swift(
+            r#"
+name: "Test"
+authed: true
+
+---
+
+@input {
+  id: UUID
+  foo: String
+  bar: [Date]?
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func test(id: UUID, foo: String, bar: [Date]?, sessionToken: String) async throws -> Response<NoData> {
+    return try await self.fetcher.post(
+      to: "/_fen_/test",
+      with: TestInput(id: id, foo: foo, bar: bar),
+      returning: NoData.self,
+      sessionToken: sessionToken
+    )
+  }
+}
+
+struct TestInput: Codable, Equatable, Identifiable {
+  var id: UUID
+  var foo: String
+  var bar: [Date]?
+
+  private enum CodingKeys: String, CodingKey {
+    case id
+    case foo
+    case bar
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.id, forKey: .id)
+    try container.encode(self.foo, forKey: .foo)
+    switch self.bar {
+    case let .some(value):
+      try container.encode(value, forKey: .bar)
+    case .none:
+      try container.encodeNil(forKey: .bar)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn input_is_struct_output_is_type() {
+        expect_swift(
+            r#"
+name: "YetAnotherTest"
+
+---
+
+@input {
+  id: UUID
+  foo: String
+}
+
+@output [UUID]
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func yetAnotherTest(id: UUID, foo: String) async throws -> Response<[UUID]> {
+    return try await self.fetcher.post(
+      to: "/_fen_/yet-another-test",
+      with: YetAnotherTestInput(id: id, foo: foo),
+      returning: [UUID].self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct YetAnotherTestInput: Codable, Equatable, Identifiable {
+  var id: UUID
+  var foo: String
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn without_foundation() {
+        expect_swift(
+            r#"
+name: "Test"
+authed: true
+
+---
+
+@output Int
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  func test(sessionToken: String) async throws -> Response<Int> {
+    return try await self.fetcher.get(from: "/_fen_/test", sessionToken: sessionToken)
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn enum_output_with_helpers() {
+        expect_swift(
+            r#"
+name: "EnumTest"
+description: "Just testing out enums"
+authed: true
+
+---
+
+@output (
+  single
+  married(Spouse)
+)
+
+---
+
+Spouse {
+  name: String
+  age: Int
+  has_beard: Bool
+  ocupation: Job
+}
+
+Job (
+  developer
+  construction
+  other(String?)
+)
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  /// Just testing out enums
+  func enumTest(sessionToken: String) async throws -> Response<EnumTestOutput> {
+    return try await self.fetcher.get(from: "/_fen_/enum-test", sessionToken: sessionToken)
+  }
+}
+
+enum EnumTestOutput: Codable, Equatable {
+  case single
+  case married(Spouse)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum EnumTestOutputType: String, Codable {
+    case single
+    case married
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(EnumTestOutputType.self, forKey: .type)
+
+    switch type {
+    case .single:
+      self = .single
+    case .married:
+      let value = try container.decode(Spouse.self, forKey: .value)
+      self = .married(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .single:
+      try container.encode(EnumTestOutputType.single, forKey: .type)
+    case .married(let value):
+      try container.encode(EnumTestOutputType.married, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
+
+struct Spouse: Codable, Equatable {
+  var name: String
+  var age: Int
+  var hasBeard: Bool
+  var ocupation: Job
+}
+
+enum Job: Codable, Equatable {
+  case developer
+  case construction
+  case other(String?)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum JobType: String, Codable {
+    case developer
+    case construction
+    case other
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(JobType.self, forKey: .type)
+
+    switch type {
+    case .developer:
+      self = .developer
+    case .construction:
+      self = .construction
+    case .other:
+      let value = try container.decodeIfPresent(String.self, forKey: .value)
+      self = .other(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .developer:
+      try container.encode(JobType.developer, forKey: .type)
+    case .construction:
+      try container.encode(JobType.construction, forKey: .type)
+    case .other(let value):
+      try container.encode(JobType.other, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn enum_input() {
+        expect_swift(
+            r#"
+name: "AnotherEnumTest"
+description: "Just testing out some more enums"
+
+---
+
+@input (
+  a
+  b(Int)
+)
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  /// Just testing out some more enums
+  func anotherEnumTest(input: AnotherEnumTestInput) async throws -> Response<NoData> {
+    return try await self.fetcher.post(
+      to: "/_fen_/another-enum-test",
+      with: input,
+      returning: NoData.self,
+      sessionToken: nil
+    )
+  }
+}
+
+enum AnotherEnumTestInput: Codable, Equatable {
+  case a
+  case b(Int)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum AnotherEnumTestInputType: String, Codable {
+    case a
+    case b
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(AnotherEnumTestInputType.self, forKey: .type)
+
+    switch type {
+    case .a:
+      self = .a
+    case .b:
+      let value = try container.decode(Int.self, forKey: .value)
+      self = .b(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .a:
+      try container.encode(AnotherEnumTestInputType.a, forKey: .type)
+    case .b(let value):
+      try container.encode(AnotherEnumTestInputType.b, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn maps() {
+        expect_swift(
+            r#"
+name: "GetFlags"
+authed: false
+
+---
+
+@input {
+  locale: String
+  overrides: {UUID: Bool}?
+}
+
+@output {String: Flag}
+
+---
+
+Flag {
+  enabled: Bool
+  since: Date
+  counts: {Int: [Int]}
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func getFlags(locale: String, overrides: [UUID: Bool]?) async throws -> Response<[String: Flag]> {
+    return try await self.fetcher.post(
+      to: "/_fen_/get-flags",
+      with: GetFlagsInput(locale: locale, overrides: overrides),
+      returning: [String: Flag].self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct GetFlagsInput: Codable, Equatable {
+  var locale: String
+  var overrides: [UUID: Bool]?
+
+  private enum CodingKeys: String, CodingKey {
+    case locale
+    case overrides
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.locale, forKey: .locale)
+    switch self.overrides {
+    case let .some(value):
+      try container.encode(value, forKey: .overrides)
+    case .none:
+      try container.encodeNil(forKey: .overrides)
+    }
+  }
+}
+
+struct Flag: Codable, Equatable {
+  var enabled: Bool
+  var since: Date
+  var counts: [Int: [Int]]
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn sized_numbers() {
+        expect_swift(
+            r#"
+name: "GetInvoice"
+authed: false
+
+---
+
+@input Int64
+
+@output {
+  id: Int64
+  line_count: Int32
+  views: UInt
+  total: Decimal
+  discount: Decimal?
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func getInvoice(input: Int64) async throws -> Response<GetInvoiceOutput> {
+    return try await self.fetcher.post(
+      to: "/_fen_/get-invoice",
+      with: input,
+      returning: GetInvoiceOutput.self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct GetInvoiceOutput: Codable, Equatable, Identifiable {
+  var id: Int64
+  var lineCount: Int32
+  var views: UInt
+  var total: Decimal
+  var discount: Decimal?
+
+  private enum CodingKeys: String, CodingKey {
+    case id
+    case lineCount
+    case views
+    case total
+    case discount
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.id, forKey: .id)
+    try container.encode(self.lineCount, forKey: .lineCount)
+    try container.encode(self.views, forKey: .views)
+    try container.encode(self.total, forKey: .total)
+    switch self.discount {
+    case let .some(value):
+      try container.encode(value, forKey: .discount)
+    case .none:
+      try container.encodeNil(forKey: .discount)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        expect_swift(
+            r#"
+name: "GetAvatar"
+authed: false
+
+---
+
+@output {
+  image: Bytes
+  thumbnail: Bytes?
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func getAvatar() async throws -> Response<GetAvatarOutput> {
+    return try await self.fetcher.get(from: "/_fen_/get-avatar", sessionToken: nil)
+  }
+}
+
+struct GetAvatarOutput: Codable, Equatable {
+  var image: Data
+  var thumbnail: Data?
+
+  private enum CodingKeys: String, CodingKey {
+    case image
+    case thumbnail
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.image, forKey: .image)
+    switch self.thumbnail {
+    case let .some(value):
+      try container.encode(value, forKey: .thumbnail)
+    case .none:
+      try container.encodeNil(forKey: .thumbnail)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn generics() {
+        expect_swift(
+            r#"
+name: "GetUsers"
+authed: false
+
+---
+
+@input {
+  cursor: String?
+}
+
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
+}
+
+Loadable<T> (
+  loading
+  loaded(T)
+)
+
+User {
+  id: UUID
+  avatar: Loadable<[String]>
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func getUsers(cursor: String?) async throws -> Response<Page<User>> {
+    return try await self.fetcher.post(
+      to: "/_fen_/get-users",
+      with: GetUsersInput(cursor: cursor),
+      returning: Page<User>.self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct GetUsersInput: Codable, Equatable {
+  var cursor: String?
+
+  private enum CodingKeys: String, CodingKey {
+    case cursor
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self.cursor {
+    case let .some(value):
+      try container.encode(value, forKey: .cursor)
+    case .none:
+      try container.encodeNil(forKey: .cursor)
+    }
+  }
+}
+
+struct Page<T: Codable & Equatable>: Codable, Equatable {
+  var items: [T]
+  var next: String?
+
+  private enum CodingKeys: String, CodingKey {
+    case items
+    case next
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.items, forKey: .items)
+    switch self.next {
+    case let .some(value):
+      try container.encode(value, forKey: .next)
+    case .none:
+      try container.encodeNil(forKey: .next)
+    }
+  }
+}
+
+struct User: Codable, Equatable, Identifiable {
+  var id: UUID
+  var avatar: Loadable<[String]>
+}
+
+enum Loadable<T: Codable & Equatable>: Codable, Equatable {
+  case loading
+  case loaded(T)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum LoadableType: String, Codable {
+    case loading
+    case loaded
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(LoadableType.self, forKey: .type)
+
+    switch type {
+    case .loading:
+      self = .loading
+    case .loaded:
+      let value = try container.decode(T.self, forKey: .value)
+      self = .loaded(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .loading:
+      try container.encode(LoadableType.loading, forKey: .type)
+    case .loaded(let value):
+      try container.encode(LoadableType.loaded, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn defaults() {
+        expect_swift(
+            r#"
+name: "SearchTodos"
+authed: false
+
+---
+
+@input {
+  query: String
+  limit: Int = 20
+  sort: SortOrder = newest
+  tag: String? = "inbox"
+}
+
+@output [String]
+
+---
+
+SortOrder (
+  newest
+  oldest
+)
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  func searchTodos(query: String, limit: Int = 20, sort: SortOrder = .newest, tag: String? = "inbox") async throws -> Response<[String]> {
+    return try await self.fetcher.post(
+      to: "/_fen_/search-todos",
+      with: SearchTodosInput(query: query, limit: limit, sort: sort, tag: tag),
+      returning: [String].self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct SearchTodosInput: Codable, Equatable {
+  var query: String
+  var limit: Int
+  var sort: SortOrder
+  var tag: String?
+
+  init(query: String, limit: Int = 20, sort: SortOrder = .newest, tag: String? = "inbox") {
+    self.query = query
+    self.limit = limit
+    self.sort = sort
+    self.tag = tag
+  }
+
+  private enum CodingKeys: String, CodingKey {
+    case query
+    case limit
+    case sort
+    case tag
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    self.query = try container.decode(String.self, forKey: .query)
+    self.limit = try container.decodeIfPresent(Int.self, forKey: .limit) ?? 20
+    self.sort = try container.decodeIfPresent(SortOrder.self, forKey: .sort) ?? .newest
+    self.tag = try container.contains(.tag) ? container.decodeIfPresent(String.self, forKey: .tag) : "inbox"
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.query, forKey: .query)
+    try container.encode(self.limit, forKey: .limit)
+    try container.encode(self.sort, forKey: .sort)
+    switch self.tag {
+    case let .some(value):
+      try container.encode(value, forKey: .tag)
+    case .none:
+      try container.encodeNil(forKey: .tag)
+    }
+  }
+}
+
+enum SortOrder: Codable, Equatable {
+  case newest
+  case oldest
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+  }
+
+  private enum SortOrderType: String, Codable {
+    case newest
+    case oldest
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(SortOrderType.self, forKey: .type)
+
+    switch type {
+    case .newest:
+      self = .newest
+    case .oldest:
+      self = .oldest
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .newest:
+      try container.encode(SortOrderType.newest, forKey: .type)
+    case .oldest:
+      try container.encode(SortOrderType.oldest, forKey: .type)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        expect_swift(
+            r#"
+name: "CreateUser"
+authed: false
+
+---
+
+@input {
+  @nonEmpty
+  @pattern("^[^@]+@[^@]+$")
+  email: String
+  @maxLength(80)
+  name: String
+  @min(1) @max(100)
+  age: Int?
+}
+
+@output UUID
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func createUser(email: String, name: String, age: Int?) async throws -> Response<UUID> {
+    let input = CreateUserInput(email: email, name: name, age: age)
+    if self.validatesInput {
+      let errors = input.validate()
+      if !errors.isEmpty {
+        return .failure(message: errors.map(\.description).joined(separator: "; "), status: 422)
+      }
+    }
+    return try await self.fetcher.post(
+      to: "/_fen_/create-user",
+      with: input,
+      returning: UUID.self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct CreateUserInput: Codable, Equatable {
+  var email: String
+  var name: String
+  var age: Int?
+
+  private enum CodingKeys: String, CodingKey {
+    case email
+    case name
+    case age
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.email, forKey: .email)
+    try container.encode(self.name, forKey: .name)
+    switch self.age {
+    case let .some(value):
+      try container.encode(value, forKey: .age)
+    case .none:
+      try container.encodeNil(forKey: .age)
+    }
+  }
+}
+
+extension CreateUserInput {
+  /// The constraints this input breaks (the server checks them too).
+  func validate() -> [ValidationError] {
+    var errors: [ValidationError] = []
+    if self.email.isEmpty {
+      errors.append(ValidationError(field: "email", message: "must not be empty"))
+    }
+    if self.email.range(of: "^[^@]+@[^@]+$", options: .regularExpression) == nil {
+      errors.append(ValidationError(field: "email", message: "must match ^[^@]+@[^@]+$"))
+    }
+    if self.name.count > 80 {
+      errors.append(ValidationError(field: "name", message: "must have at most 80 characters"))
+    }
+    if let age = self.age, age < 1 {
+      errors.append(ValidationError(field: "age", message: "must be at least 1"))
+    }
+    if let age = self.age, age > 100 {
+      errors.append(ValidationError(field: "age", message: "must be at most 100"))
+    }
+    return errors
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn doc_comments() {
+        expect_swift(
+            r#"
+name: "GetTasks"
+authed: false
+
+---
+
+@input {
+  /// Only tasks with this status are returned
+  status: Status?
+}
+
+@output [Task]
+
+---
+
+/// A single unit of work
+///
+/// Tasks belong to exactly one project.
+Task {
+  id: UUID
+  /// Shown in the task list
+  title: String
+  status: Status
+}
+
+/// Where a task is in its lifecycle
+@sqlxType
+Status (
+  todo
+  /// Being worked on by someone
+  in_progress
+  done
+)
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func getTasks(status: Status?) async throws -> Response<[Task]> {
+    return try await self.fetcher.post(
+      to: "/_fen_/get-tasks",
+      with: GetTasksInput(status: status),
+      returning: [Task].self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct GetTasksInput: Codable, Equatable {
+  /// Only tasks with this status are returned
+  var status: Status?
+
+  private enum CodingKeys: String, CodingKey {
+    case status
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self.status {
+    case let .some(value):
+      try container.encode(value, forKey: .status)
+    case .none:
+      try container.encodeNil(forKey: .status)
+    }
+  }
+}
+
+/// A single unit of work
+///
+/// Tasks belong to exactly one project.
+struct Task: Codable, Equatable, Identifiable {
+  var id: UUID
+  /// Shown in the task list
+  var title: String
+  var status: Status
+}
+
+/// Where a task is in its lifecycle
+enum Status: Codable, Equatable {
+  case todo
+  /// Being worked on by someone
+  case inProgress
+  case done
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+  }
+
+  private enum StatusType: String, Codable {
+    case todo
+    case inProgress
+    case done
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(StatusType.self, forKey: .type)
+
+    switch type {
+    case .todo:
+      self = .todo
+    case .inProgress:
+      self = .inProgress
+    case .done:
+      self = .done
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .todo:
+      try container.encode(StatusType.todo, forKey: .type)
+    case .inProgress:
+      try container.encode(StatusType.inProgress, forKey: .type)
+    case .done:
+      try container.encode(StatusType.done, forKey: .type)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn deprecations() {
+        expect_swift(
+            r#"
+name: "GetTasks"
+authed: false
+@deprecated("use ListTasks instead")
+
+---
+
+@input {
+  @deprecated("filter by status instead")
+  @nonEmpty
+  owner: String
+}
+
+@output [Task]
+
+---
+
+Task {
+  @deprecated("use name instead")
+  title: String
+  name: String
+  status: Status
+}
+
+Status (
+  todo
+  @deprecated("use todo instead")
+  open
+)
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  @available(*, deprecated, message: "use ListTasks instead")
+  func getTasks(owner: String) async throws -> Response<[Task]> {
+    let input = GetTasksInput(owner: owner)
+    if self.validatesInput {
+      let errors = input.validate()
+      if !errors.isEmpty {
+        return .failure(message: errors.map(\.description).joined(separator: "; "), status: 422)
+      }
+    }
+    return try await self.fetcher.post(
+      to: "/_fen_/get-tasks",
+      with: input,
+      returning: [Task].self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct GetTasksInput: Codable, Equatable {
+  @available(*, deprecated, message: "filter by status instead")
+  var owner: String
+}
+
+extension GetTasksInput {
+  /// The constraints this input breaks (the server checks them too).
+  func validate() -> [ValidationError] {
+    var errors: [ValidationError] = []
+    if self.owner.isEmpty {
+      errors.append(ValidationError(field: "owner", message: "must not be empty"))
+    }
+    return errors
+  }
+}
+
+struct Task: Codable, Equatable {
+  @available(*, deprecated, message: "use name instead")
+  var title: String
+  var name: String
+  var status: Status
+}
+
+enum Status: Codable, Equatable {
+  case todo
+  @available(*, deprecated, message: "use todo instead")
+  case open
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+  }
+
+  private enum StatusType: String, Codable {
+    case todo
+    case open
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(StatusType.self, forKey: .type)
+
+    switch type {
+    case .todo:
+      self = .todo
+    case .open:
+      self = .open
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .todo:
+      try container.encode(StatusType.todo, forKey: .type)
+    case .open:
+      try container.encode(StatusType.open, forKey: .type)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_swift(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  /// Renames a user
+  func updateUser(user_id: UUID, name: String, sessionToken: String) async throws -> Response<User> {
+    return try await self.fetcher.request(
+      "PUT",
+      to: "/users/\(APIClient.pathSegment(user_id))/name",
+      with: UpdateUserInput(userId: user_id, name: name),
+      returning: User.self,
+      sessionToken: sessionToken
+    )
+  }
+}
+
+struct UpdateUserInput: Codable, Equatable {
+  var userId: UUID
+  var name: String
+}
+
+struct User: Codable, Equatable, Identifiable {
+  var id: UUID
+  var name: String
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn get_with_path_params() {
+        expect_swift(
+            r#"
+name: "GetPost"
+method: "GET"
+path: "/users/{user_id}/posts/{slug}"
+authed: false
+
+---
+
+@input {
+  user_id: UUID
+  slug: String
+}
+
+@output String
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  func getPost(user_id: UUID, slug: String) async throws -> Response<String> {
+    return try await self.fetcher.get(from: "/users/\(APIClient.pathSegment(user_id))/posts/\(APIClient.pathSegment(slug))", sessionToken: nil)
+  }
+}
+
+struct GetPostInput: Codable, Equatable {
+  var userId: UUID
+  var slug: String
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_swift(
+            r#"
+name: "ClaimHandle"
+description: "Claims a handle for the current user"
+method: "PUT"
+path: "/handles/{handle}"
+authed: true
+
+---
+
+@input {
+  handle: String
+  display_name: String
+}
+
+@output {
+  id: UUID
+}
+
+@error (
+  not_found
+  taken(Owner)
+)
+
+---
+
+Owner {
+  id: UUID
+  since: Date
+}
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  /// Claims a handle for the current user
+  func claimHandle(handle: String, display_name: String, sessionToken: String) async throws -> ResponseWithError<ClaimHandleOutput, ClaimHandleError> {
+    return try await self.fetcher.request(
+      "PUT",
+      to: "/handles/\(APIClient.pathSegment(handle))",
+      with: ClaimHandleInput(handle: handle, displayName: display_name),
+      returning: ClaimHandleOutput.self,
+      failingWith: ClaimHandleError.self,
+      sessionToken: sessionToken
+    )
+  }
+}
+
+struct ClaimHandleInput: Codable, Equatable {
+  var handle: String
+  var displayName: String
+}
+
+struct ClaimHandleOutput: Codable, Equatable, Identifiable {
+  var id: UUID
+}
+
+enum ClaimHandleError: Codable, Equatable {
+  case notFound
+  case taken(Owner)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum ClaimHandleErrorType: String, Codable {
+    case notFound
+    case taken
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(ClaimHandleErrorType.self, forKey: .type)
+
+    switch type {
+    case .notFound:
+      self = .notFound
+    case .taken:
+      let value = try container.decode(Owner.self, forKey: .value)
+      self = .taken(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .notFound:
+      try container.encode(ClaimHandleErrorType.notFound, forKey: .type)
+    case .taken(let value):
+      try container.encode(ClaimHandleErrorType.taken, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
+
+struct Owner: Codable, Equatable, Identifiable {
+  var id: UUID
+  var since: Date
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn primitive_route_error() {
+        expect_swift(
+            r#"
+name: "Shorten"
+description: "Shortens a link"
+
+---
+
+@input String
+@output Int
+@error String
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  /// Shortens a link
+  func shorten(input: String) async throws -> ResponseWithError<Int, String> {
+    return try await self.fetcher.request(
+      "POST",
+      to: "/_fen_/shorten",
+      with: input,
+      returning: Int.self,
+      failingWith: String.self,
+      sessionToken: nil
+    )
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_swift(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  /// Lists a user's posts
+  func listPosts(user_id: UUID, page: Int?, tag: String?, x_client_version: String, sessionToken: String) async throws -> Response<[String]> {
+    return try await self.fetcher.request("GET", to: "/users/\(APIClient.pathSegment(user_id))/posts" + APIClient.queryString([("page", page.map { "\($0)" }), ("tag", tag)]), headers: ["x-client-version": x_client_version], sessionToken: sessionToken)
+  }
+}
+
+struct ListPostsInput: Codable, Equatable {
+  var userId: UUID
+  var page: Int?
+  var tag: String?
+  var xClientVersion: String
+
+  private enum CodingKeys: String, CodingKey {
+    case userId
+    case page
+    case tag
+    case xClientVersion
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.userId, forKey: .userId)
+    switch self.page {
+    case let .some(value):
+      try container.encode(value, forKey: .page)
+    case .none:
+      try container.encodeNil(forKey: .page)
+    }
+    switch self.tag {
+    case let .some(value):
+      try container.encode(value, forKey: .tag)
+    case .none:
+      try container.encodeNil(forKey: .tag)
+    }
+    try container.encode(self.xClientVersion, forKey: .xClientVersion)
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn post_with_query_and_header_params() {
+        expect_swift(
+            r#"
+name: "Search"
+description: "Searches everything"
+
+---
+
+@input {
+  text: String
+  @query limit: Int
+  @header idempotency_key: String?
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  /// Searches everything
+  func search(text: String, limit: Int, idempotency_key: String?) async throws -> Response<[String]> {
+    return try await self.fetcher.request(
+      "POST",
+      to: "/_fen_/search" + APIClient.queryString([("limit", "\(limit)")]),
+      with: SearchInput(text: text, limit: limit, idempotencyKey: idempotency_key),
+      returning: [String].self,
+      headers: ["idempotency-key": idempotency_key],
+      sessionToken: nil
+    )
+  }
+}
+
+struct SearchInput: Codable, Equatable {
+  var text: String
+  var limit: Int
+  var idempotencyKey: String?
+
+  private enum CodingKeys: String, CodingKey {
+    case text
+    case limit
+    case idempotencyKey
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.text, forKey: .text)
+    try container.encode(self.limit, forKey: .limit)
+    switch self.idempotencyKey {
+    case let .some(value):
+      try container.encode(value, forKey: .idempotencyKey)
+    case .none:
+      try container.encodeNil(forKey: .idempotencyKey)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn streaming_output_with_validated_input() {
+        expect_swift(
+            r#"
+name: "ImportProgress"
+description: "Imports a file, reporting progress as it goes"
+authed: true
+
+---
+
+@input {
+  @min(1) rows: Int
+}
+
+@output stream Progress
+
+---
+
+Progress {
+  done: Int
+  total: Int
+}
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  /// Imports a file, reporting progress as it goes
+  func importProgress(rows: Int, sessionToken: String) -> AsyncThrowingStream<Progress, Error> {
+    let input = ImportProgressInput(rows: rows)
+    if self.validatesInput {
+      let errors = input.validate()
+      if !errors.isEmpty {
+        return AsyncThrowingStream { $0.finish(throwing: StreamError.failure(message: errors.map(\.description).joined(separator: "; "), status: 422)) }
+      }
+    }
+    return self.fetcher.stream(
+      "POST",
+      to: "/_fen_/import-progress",
+      with: input,
+      returning: Progress.self,
+      sessionToken: sessionToken
+    )
+  }
+}
+
+struct ImportProgressInput: Codable, Equatable {
+  var rows: Int
+}
+
+extension ImportProgressInput {
+  /// The constraints this input breaks (the server checks them too).
+  func validate() -> [ValidationError] {
+    var errors: [ValidationError] = []
+    if self.rows < 1 {
+      errors.append(ValidationError(field: "rows", message: "must be at least 1"))
+    }
+    return errors
+  }
+}
+
+struct Progress: Codable, Equatable {
+  var done: Int
+  var total: Int
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn streaming_get_with_query_params() {
+        expect_swift(
+            r#"
+name: "LiveFeed"
+method: "GET"
+path: "/feed"
+
+---
+
+@input {
+  @query topic: String?
+}
+
+@output stream String
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  func liveFeed(topic: String?) -> AsyncThrowingStream<String, Error> {
+    return self.fetcher.stream("GET", to: "/feed" + APIClient.queryString([("topic", topic)]), returning: String.self, sessionToken: nil)
+  }
+}
+
+struct LiveFeedInput: Codable, Equatable {
+  var topic: String?
+
+  private enum CodingKeys: String, CodingKey {
+    case topic
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self.topic {
+    case let .some(value):
+      try container.encode(value, forKey: .topic)
+    case .none:
+      try container.encodeNil(forKey: .topic)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_snake_case_query_and_header() {
+        expect_swift(
+            r#"
+name: "RenameItem"
+description: "Renames an item"
+method: "PUT"
+path: "/items/{item_id}"
+
+---
+
+@input {
+  item_id: UUID
+  new_name: String
+  @query dry_run: Bool?
+  @header x_request_id: String
+}
+
+@output Bool
+            "#
+            .trim(),
+            r#"
+import Foundation
+
+extension APIClient {
+  /// Renames an item
+  func renameItem(item_id: UUID, new_name: String, dry_run: Bool?, x_request_id: String) async throws -> Response<Bool> {
+    return try await self.fetcher.request(
+      "PUT",
+      to: "/items/\(APIClient.pathSegment(item_id))" + APIClient.queryString([("dryRun", dry_run.map { "\($0)" })]),
+      with: RenameItemInput(itemId: item_id, newName: new_name, dryRun: dry_run, xRequestId: x_request_id),
+      returning: Bool.self,
+      headers: ["x-request-id": x_request_id],
+      sessionToken: nil
+    )
+  }
+}
+
+struct RenameItemInput: Codable, Equatable {
+  var itemId: UUID
+  var newName: String
+  var dryRun: Bool?
+  var xRequestId: String
+
+  private enum CodingKeys: String, CodingKey {
+    case itemId
+    case newName
+    case dryRun
+    case xRequestId
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.itemId, forKey: .itemId)
+    try container.encode(self.newName, forKey: .newName)
+    switch self.dryRun {
+    case let .some(value):
+      try container.encode(value, forKey: .dryRun)
+    case .none:
+      try container.encodeNil(forKey: .dryRun)
+    }
+    try container.encode(self.xRequestId, forKey: .xRequestId)
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_value_with_reserved_characters() {
+        expect_swift(
+            r#"
+name: "Search"
+method: "GET"
+path: "/search"
+
+---
+
+@input {
+  @query q: String = "a&b=c+1"
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+extension APIClient {
+  func search(q: String = "a&b=c+1") async throws -> Response<[String]> {
+    return try await self.fetcher.get(from: "/search" + APIClient.queryString([("q", q)]), sessionToken: nil)
+  }
+}
+
+struct SearchInput: Codable, Equatable {
+  var q: String
+
+  init(q: String = "a&b=c+1") {
+    self.q = q
+  }
+
+  private enum CodingKeys: String, CodingKey {
+    case q
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    self.q = try container.decodeIfPresent(String.self, forKey: .q) ?? "a&b=c+1"
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+}
+
+mod rust_server_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_rust(fen_code: &str, rust_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let rust = ast.rust_server_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(rust, rust_code);
+    }
+
+    #[test]
+    fn just_output() {
+        expect_rust(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub type Output = Vec<Todo>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Todo {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub due: Option<DateTime<Utc>>,
+    pub is_completed: bool,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn just_input() {
+        expect_rust(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            r"
+use uuid::Uuid;
+
+pub type Input = Uuid;
+                "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn struct_for_input_and_output() {
+        expect_rust(
+            r#"
+name: "Test"
+
+---
+
+@input {
+  id: UUID
+  foo: String
+  bar: [Date]?
+}
+
+@output {
+  stuff: [Thing]
+}
+
+---
+
+Thing {
+  type: ThingType
+  happy: Bool
+}
+
+ThingType (
+  a
+  b
+  c
+)
+                "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub id: Uuid,
+    pub foo: String,
+    pub bar: Option<Vec<DateTime<Utc>>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Output {
+    pub stuff: Vec<Thing>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Thing {
+    pub type: ThingType,
+    pub happy: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ThingType {
+    A,
+    B,
+    C,
+}
+                "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn struct_for_input_no_output() {
+        expect_rust(
+            r#"
+name: "Test"
+authed: true
+
+---
+
+@input {
+  id: UUID
+  foo: String
+  bar: [Date]?
+}
+                "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub id: Uuid,
+    pub foo: String,
+    pub bar: Option<Vec<DateTime<Utc>>>,
+}
+             "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn input_is_struct_output_is_type() {
+        expect_rust(
+            r#"
+name: "YetAnotherTest"
+
+---
+
+@input {
+  id: UUID
+  foo: String
+}
+
+@output [UUID]
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub id: Uuid,
+    pub foo: String,
+}
+
+pub type Output = Vec<Uuid>;
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn without_foundation() {
+        expect_rust(
+            r#"
+name: "Test"
+authed: true
+
+---
+
+@output Int
+                    "#
+            .trim(),
+            r"
+pub type Output = isize;
+                "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn enum_output_with_helpers() {
+        expect_rust(
+            r#"
+name: "EnumTest"
+description: "Just testing out enums"
+authed: true
+
+---
+
+@output (
+  single
+  married(Spouse)
+)
+
+---
+
+Spouse {
+  name: String
+  age: Int
+  has_beard: Bool
+  ocupation: Job
+}
+
+Job (
+  developer
+  construction
+  other(String?)
+)
+                "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Output {
+    Single,
+    Married(Spouse),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Spouse {
+    pub name: String,
+    pub age: isize,
+    pub has_beard: bool,
+    pub ocupation: Job,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Job {
+    Developer,
+    Construction,
+    Other(Option<String>),
+}
+                "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn enum_input() {
+        expect_rust(
+            r#"
+name: "AnotherEnumTest"
+description: "Just testing out some more enums"
+
+---
+
+@input (
+  a
+  b(Int)
+)
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Input {
+    A,
+    B(isize),
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn sqlx_types() {
+        expect_rust(
+            r#"
+name: "GetRepertoire"
+description: "Get a user's repertoire"
+authed: true
+
+---
+
+@output [Song]
+
+---
+
+Song {
+  id: UUID
+  title: String
+  familiarity: FamiliarityLevel
+}
+
+@sqlxType
+FamiliarityLevel (
+  todo
+  learning
+  playable
+  good
+  mastered
+)
+                "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub type Output = Vec<Song>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Song {
+    pub id: Uuid,
+    pub title: String,
+    pub familiarity: FamiliarityLevel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, sqlx::Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[sqlx(type_name = "familiarity_level", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FamiliarityLevel {
+    Todo,
+    Learning,
+    Playable,
+    Good,
+    Mastered,
+}
+                "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn maps() {
+        expect_rust(
+            r#"
+name: "GetFlags"
+authed: false
+
+---
+
+@input {
+  locale: String
+  overrides: {UUID: Bool}?
+}
+
+@output {String: Flag}
+
+---
+
+Flag {
+  enabled: Bool
+  since: Date
+  counts: {Int: [Int]}
+}
+            "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub locale: String,
+    pub overrides: Option<HashMap<Uuid, bool>>,
+}
+
+pub type Output = HashMap<String, Flag>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Flag {
+    pub enabled: bool,
+    pub since: DateTime<Utc>,
+    pub counts: HashMap<isize, Vec<isize>>,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn sized_numbers() {
+        expect_rust(
+            r#"
+name: "GetInvoice"
+authed: false
+
+---
+
+@input Int64
+
+@output {
+  id: Int64
+  line_count: Int32
+  views: UInt
+  total: Decimal
+  discount: Decimal?
+}
+            "#
+            .trim(),
+            r#"
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub type Input = i64;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Output {
+    pub id: i64,
+    pub line_count: i32,
+    pub views: usize,
+    pub total: Decimal,
+    pub discount: Option<Decimal>,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        expect_rust(
+            r#"
+name: "UploadAvatar"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  image: Bytes
+  thumbnail: Bytes?
+}
+
+@output Attachment
+
+---
+
+Attachment (
+  none
+  signature(Bytes)
+)
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub user_id: Uuid,
+    #[serde(with = "super::fen_base64")]
+    pub image: Vec<u8>,
+    #[serde(default, with = "super::fen_base64::option")]
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+pub type Output = Attachment;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Attachment {
+    None,
+    Signature(#[serde(with = "super::fen_base64")] Vec<u8>),
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn generics() {
+        expect_rust(
+            r#"
+name: "GetUsers"
+authed: false
+
+---
+
+@input {
+  cursor: String?
+}
+
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
+}
+
+Loadable<T> (
+  loading
+  loaded(T)
+)
+
+User {
+  id: UUID
+  avatar: Loadable<[String]>
+}
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub cursor: Option<String>,
+}
+
+pub type Output = Page<User>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: Uuid,
+    pub avatar: Loadable<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Loadable<T> {
+    Loading,
+    Loaded(T),
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        expect_rust(
+            r#"
+name: "SearchTodos"
+authed: false
+
+---
+
+@input {
+  query: String
+  limit: Int = 20
+  sort: SortOrder = newest
+  tag: String? = "inbox"
+}
+
+@output [String]
+
+---
+
+SortOrder (
+  newest
+  oldest
+)
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub query: String,
+    #[serde(default = "default_input_limit")]
+    pub limit: isize,
+    #[serde(default = "default_input_sort")]
+    pub sort: SortOrder,
+    #[serde(default = "default_input_tag")]
+    pub tag: Option<String>,
+}
+
+fn default_input_limit() -> isize {
+    20
+}
+
+fn default_input_sort() -> SortOrder {
+    SortOrder::Newest
+}
+
+fn default_input_tag() -> Option<String> {
+    Some("inbox".to_string())
+}
+
+pub type Output = Vec<String>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SortOrder {
+    Newest,
+    Oldest,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        expect_rust(
+            r#"
+name: "CreateUser"
+authed: false
+
+---
+
+@input {
+  @nonEmpty
+  @pattern("^[^@]+@[^@]+$")
+  email: String
+  @maxLength(80)
+  name: String
+  @min(1) @max(100)
+  age: Int?
+}
+
+@output UUID
+            "#
+            .trim(),
+            r#"
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use super::ValidationError;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub email: String,
+    pub name: String,
+    pub age: Option<isize>,
+}
+
+impl Input {
+    /// Checks the input against its constraints, returning every one it breaks.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        static EMAIL_PATTERN: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new("^[^@]+@[^@]+$").unwrap());
+        let mut errors = vec![];
+        if self.email.is_empty() {
+            errors.push(ValidationError::new("email", "must not be empty"));
+        }
+        if !EMAIL_PATTERN.is_match(&self.email) {
+            errors.push(ValidationError::new("email", "must match ^[^@]+@[^@]+$"));
+        }
+        if self.name.chars().count() > 80 {
+            errors.push(ValidationError::new("name", "must have at most 80 characters"));
+        }
+        if let Some(age) = &self.age {
+            if *age < 1 {
+                errors.push(ValidationError::new("age", "must be at least 1"));
+            }
+            if *age > 100 {
+                errors.push(ValidationError::new("age", "must be at most 100"));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+pub type Output = Uuid;
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn doc_comments() {
+        expect_rust(
+            r#"
+name: "GetTasks"
+authed: false
+
+---
+
+@input {
+  /// Only tasks with this status are returned
+  status: Status?
+}
+
+@output [Task]
+
+---
+
+/// A single unit of work
+///
+/// Tasks belong to exactly one project.
+Task {
+  id: UUID
+  /// Shown in the task list
+  title: String
+  status: Status
+}
+
+/// Where a task is in its lifecycle
+@sqlxType
+Status (
+  todo
+  /// Being worked on by someone
+  in_progress
+  done
+)
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    /// Only tasks with this status are returned
+    pub status: Option<Status>,
+}
+
+pub type Output = Vec<Task>;
+
+/// A single unit of work
+///
+/// Tasks belong to exactly one project.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    pub id: Uuid,
+    /// Shown in the task list
+    pub title: String,
+    pub status: Status,
+}
+
+/// Where a task is in its lifecycle
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, sqlx::Type)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[sqlx(type_name = "status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Status {
+    Todo,
+    /// Being worked on by someone
+    InProgress,
+    Done,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn deprecations() {
+        expect_rust(
+            r#"
+name: "GetTasks"
+authed: false
+@deprecated("use ListTasks instead")
+
+---
+
+@input {
+  @deprecated("filter by status instead")
+  @nonEmpty
+  owner: String
+}
+
+@output [Task]
+
+---
+
+Task {
+  @deprecated("use name instead")
+  title: String
+  name: String
+  status: Status
+}
+
+Status (
+  todo
+  @deprecated("use todo instead")
+  open
+)
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+
+use super::ValidationError;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    #[deprecated(note = "filter by status instead")]
+    pub owner: String,
+}
+
+#[allow(deprecated)]
+impl Input {
+    /// Checks the input against its constraints, returning every one it breaks.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        if self.owner.is_empty() {
+            errors.push(ValidationError::new("owner", "must not be empty"));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+pub type Output = Vec<Task>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    #[deprecated(note = "use name instead")]
+    pub title: String,
+    pub name: String,
+    pub status: Status,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Status {
+    Todo,
+    #[deprecated(note = "use todo instead")]
+    Open,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_rust(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub user_id: Uuid,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct PathParams {
+    pub user_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    pub name: String,
+}
+
+pub type Output = User;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_rust(
+            r#"
+name: "ClaimHandle"
+description: "Claims a handle for the current user"
+method: "PUT"
+path: "/handles/{handle}"
+authed: true
+
+---
+
+@input {
+  handle: String
+  display_name: String
+}
+
+@output {
+  id: UUID
+}
+
+@error (
+  not_found
+  taken(Owner)
+)
+
+---
+
+Owner {
+  id: UUID
+  since: Date
+}
+            "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub handle: String,
+    pub display_name: String,
+}
+
+#[derive(Deserialize)]
+pub struct PathParams {
+    pub handle: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Output {
+    pub id: Uuid,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Error {
+    NotFound,
+    Taken(Owner),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Owner {
+    pub id: Uuid,
+    pub since: DateTime<Utc>,
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_rust(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub user_id: Uuid,
+    pub page: Option<isize>,
+    pub tag: Option<String>,
+    pub x_client_version: String,
+}
+
+#[derive(Deserialize)]
+pub struct PathParams {
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    pub page: Option<isize>,
+    pub tag: Option<String>,
+}
+
+pub type Output = Vec<String>;
+            "#
+            .trim(),
+        );
+    }
+}
+
+mod typescript_client_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_typescript(fen_code: &str, typescript_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let typescript = ast.typescript_client_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(typescript, typescript_code);
+    }
+
+    #[test]
+    fn just_output() {
+        expect_typescript(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+import { Fetcher, Response } from "./api";
+
+/** Fetches all todos */
+export async function getTodos(fetcher: Fetcher, sessionToken: string): Promise<Response<Todo[]>> {
+  return fetcher.get("/_fen_/get-todos", sessionToken, (json) => json.map((item: any) => decodeTodo(item)));
+}
+
+export interface Todo {
+  id: string;
+  name: string;
+  description: string | null;
+  due: Date | null;
+  isCompleted: boolean;
+}
+
+export function decodeTodo(json: any): Todo {
+  return {
+    id: json.id,
+    name: json.name,
+    description: json.description ?? null,
+    due: json.due == null ? null : new Date(json.due),
+    isCompleted: json.isCompleted,
+  };
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn just_input() {
+        expect_typescript(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            r#"
+import { Fetcher, Response } from "./api";
+
+/** Completes or uncompletes a todo */
+export async function toggleTodoCompletion(fetcher: Fetcher, input: string, sessionToken: string): Promise<Response<null>> {
+  return fetcher.post("/_fen_/toggle-todo-completion", input, sessionToken, () => null);
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn enum_output_with_helpers() {
+        expect_typescript(
+            r#"
+name: "EnumTest"
+description: "Just testing out enums"
+authed: true
+
+---
+
+@input {
+  ids: [UUID]
+  since: Date?
+  dates: [Date?]
+}
+
+@output (
+  single
+  married(Spouse)
+)
+
+---
+
+Spouse {
+  name: String
+  age: Int
+  has_beard: Bool
+  ocupation: Job
+  born: [Date]?
+}
+
+Job (
+  developer
+  construction
+  other(String?)
+)
+            "#
+            .trim(),
+            r#"
+import { Fetcher, Response } from "./api";
+
+/** Just testing out enums */
+export async function enumTest(fetcher: Fetcher, input: EnumTestInput, sessionToken: string): Promise<Response<EnumTestOutput>> {
+  return fetcher.post("/_fen_/enum-test", input, sessionToken, (json) => decodeEnumTestOutput(json));
+}
+
+export interface EnumTestInput {
+  ids: string[];
+  since: Date | null;
+  dates: (Date | null)[];
+}
+
+export function decodeEnumTestInput(json: any): EnumTestInput {
+  return {
+    ids: json.ids,
+    since: json.since == null ? null : new Date(json.since),
+    dates: json.dates.map((item: any) => item == null ? null : new Date(item)),
+  };
+}
+
+export type EnumTestOutput =
+  | { type: "single" }
+  | { type: "married"; value: Spouse };
+
+export function decodeEnumTestOutput(json: any): EnumTestOutput {
+  switch (json.type) {
+    case "single":
+      return { type: "single" };
+    case "married":
+      return { type: "married", value: decodeSpouse(json.value) };
+    default:
+      throw new Error(`Unknown EnumTestOutput type: ${json.type}`);
+  }
+}
+
+export interface Spouse {
+  name: string;
+  age: number;
+  hasBeard: boolean;
+  ocupation: Job;
+  born: Date[] | null;
+}
+
+export function decodeSpouse(json: any): Spouse {
+  return {
+    name: json.name,
+    age: json.age,
+    hasBeard: json.hasBeard,
+    ocupation: decodeJob(json.ocupation),
+    born: json.born == null ? null : json.born.map((item: any) => new Date(item)),
+  };
+}
+
+export type Job =
+  | { type: "developer" }
+  | { type: "construction" }
+  | { type: "other"; value: string | null };
+
+export function decodeJob(json: any): Job {
+  switch (json.type) {
+    case "developer":
+      return { type: "developer" };
+    case "construction":
+      return { type: "construction" };
+    case "other":
+      return { type: "other", value: json.value ?? null };
+    default:
+      throw new Error(`Unknown Job type: ${json.type}`);
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn maps() {
+        expect_typescript(
+            r#"
+name: "GetFlags"
+authed: false
+
+---
+
+@input {
+  locale: String
+  overrides: {UUID: Bool}?
+}
+
+@output {String: Flag}
+
+---
+
+Flag {
+  enabled: Bool
+  since: Date
+  counts: {Int: [Int]}
+}
+            "#
+            .trim(),
+            r#"
+import { Fetcher, Response } from "./api";
+
+export async function getFlags(fetcher: Fetcher, input: GetFlagsInput): Promise<Response<Record<string, Flag>>> {
+  return fetcher.post("/_fen_/get-flags", input, null, (json) => Object.fromEntries(Object.entries(json).map(([key, item]: [string, any]) => [key, decodeFlag(item)])));
+}
+
+export interface GetFlagsInput {
+  locale: string;
+  overrides: Record<string, boolean> | null;
+}
+
+export function decodeGetFlagsInput(json: any): GetFlagsInput {
+  return {
+    locale: json.locale,
+    overrides: json.overrides ?? null,
+  };
+}
+
+export interface Flag {
+  enabled: boolean;
+  since: Date;
+  counts: Record<number, number[]>;
+}
+
+export function decodeFlag(json: any): Flag {
+  return {
+    enabled: json.enabled,
+    since: new Date(json.since),
+    counts: json.counts,
+  };
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn generics() {
+        expect_typescript(
+            r#"
+name: "GetUsers"
+authed: false
+
+---
+
+@input {
+  cursor: String?
+}
+
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
+}
+
+Loadable<T> (
+  loading
+  loaded(T)
+)
+
+User {
+  id: UUID
+  avatar: Loadable<[String]>
+}
+            "#
+            .trim(),
+            r#"
+import { Fetcher, Response } from "./api";
+
+export async function getUsers(fetcher: Fetcher, input: GetUsersInput): Promise<Response<Page<User>>> {
+  return fetcher.post("/_fen_/get-users", input, null, (json) => decodePage(json, (json: any) => decodeUser(json)));
+}
+
+export interface GetUsersInput {
+  cursor: string | null;
+}
+
+export function decodeGetUsersInput(json: any): GetUsersInput {
+  return {
+    cursor: json.cursor ?? null,
+  };
+}
+
+export interface Page<T> {
+  items: T[];
+  next: string | null;
+}
+
+export function decodePage<T>(json: any, decodeT: (json: any) => T): Page<T> {
+  return {
+    items: json.items.map((item: any) => decodeT(item)),
+    next: json.next ?? null,
+  };
+}
+
+export interface User {
+  id: string;
+  avatar: Loadable<string[]>;
+}
+
+export function decodeUser(json: any): User {
+  return {
+    id: json.id,
+    avatar: decodeLoadable(json.avatar, (json: any) => json),
+  };
+}
+
+export type Loadable<T> =
+  | { type: "loading" }
+  | { type: "loaded"; value: T };
+
+export function decodeLoadable<T>(json: any, decodeT: (json: any) => T): Loadable<T> {
+  switch (json.type) {
+    case "loading":
+      return { type: "loading" };
+    case "loaded":
+      return { type: "loaded", value: decodeT(json.value) };
+    default:
+      throw new Error(`Unknown Loadable type: ${json.type}`);
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_typescript(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+import { Fetcher, pathSegment, Response } from "./api";
+
+/** Renames a user */
+export async function updateUser(fetcher: Fetcher, input: UpdateUserInput, sessionToken: string): Promise<Response<User>> {
+  return fetcher.request("PUT", `/users/${pathSegment(input.userId)}/name`, input, sessionToken, (json) => decodeUser(json));
+}
+
+export interface UpdateUserInput {
+  userId: string;
+  name: string;
+}
+
+export function decodeUpdateUserInput(json: any): UpdateUserInput {
+  return {
+    userId: json.userId,
+    name: json.name,
+  };
+}
+
+export interface User {
+  id: string;
+  name: string;
+}
+
+export function decodeUser(json: any): User {
+  return {
+    id: json.id,
+    name: json.name,
+  };
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn delete_without_input() {
+        expect_typescript(
+            r#"
+name: "ClearCache"
+method: "DELETE"
+path: "/cache"
+authed: true
+---
+
+@output Int
+            "#
+            .trim(),
+            r#"
+import { Fetcher, Response } from "./api";
+
+export async function clearCache(fetcher: Fetcher, sessionToken: string): Promise<Response<number>> {
+  return fetcher.request("DELETE", "/cache", undefined, sessionToken, (json) => json);
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_typescript(
+            r#"
+name: "ClaimHandle"
+description: "Claims a handle for the current user"
+method: "PUT"
+path: "/handles/{handle}"
+authed: true
+
+---
+
+@input {
+  handle: String
+  display_name: String
+}
+
+@output {
+  id: UUID
+}
+
+@error (
+  not_found
+  taken(Owner)
+)
+
+---
+
+Owner {
+  id: UUID
+  since: Date
+}
+            "#
+            .trim(),
+            r#"
+import { Fetcher, pathSegment, Response } from "./api";
+
+/** Claims a handle for the current user */
+export async function claimHandle(fetcher: Fetcher, input: ClaimHandleInput, sessionToken: string): Promise<Response<ClaimHandleOutput, ClaimHandleError>> {
+  return fetcher.request("PUT", `/handles/${pathSegment(input.handle)}`, input, sessionToken, (json) => decodeClaimHandleOutput(json), (json) => decodeClaimHandleError(json));
+}
+
+export interface ClaimHandleInput {
+  handle: string;
+  displayName: string;
+}
+
+export function decodeClaimHandleInput(json: any): ClaimHandleInput {
+  return {
+    handle: json.handle,
+    displayName: json.displayName,
+  };
+}
+
+export interface ClaimHandleOutput {
+  id: string;
+}
+
+export function decodeClaimHandleOutput(json: any): ClaimHandleOutput {
+  return {
+    id: json.id,
+  };
+}
+
+export type ClaimHandleError =
+  | { type: "notFound" }
+  | { type: "taken"; value: Owner };
+
+export function decodeClaimHandleError(json: any): ClaimHandleError {
+  switch (json.type) {
+    case "notFound":
+      return { type: "notFound" };
+    case "taken":
+      return { type: "taken", value: decodeOwner(json.value) };
+    default:
+      throw new Error(`Unknown ClaimHandleError type: ${json.type}`);
+  }
+}
+
+export interface Owner {
+  id: string;
+  since: Date;
+}
+
+export function decodeOwner(json: any): Owner {
+  return {
+    id: json.id,
+    since: new Date(json.since),
+  };
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_typescript(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+import { Fetcher, pathSegment, queryString, Response } from "./api";
+
+/** Lists a user's posts */
+export async function listPosts(fetcher: Fetcher, input: ListPostsInput, sessionToken: string): Promise<Response<string[]>> {
+  return fetcher.request("GET", `/users/${pathSegment(input.userId)}/posts` + queryString({ page: input.page, tag: input.tag }), undefined, sessionToken, (json) => json, undefined, { "x-client-version": input.xClientVersion });
+}
+
+export interface ListPostsInput {
+  userId: string;
+  page: number | null;
+  tag: string | null;
+  xClientVersion: string;
+}
+
+export function decodeListPostsInput(json: any): ListPostsInput {
+  return {
+    userId: json.userId,
+    page: json.page ?? null,
+    tag: json.tag ?? null,
+    xClientVersion: json.xClientVersion,
+  };
+}
+            "#
+            .trim(),
+        );
+    }
+}
+
+mod rust_client_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_rust(fen_code: &str, rust_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let rust = ast.rust_client_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(rust, rust_code);
+    }
+
+    #[test]
+    fn just_output() {
+        expect_rust(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ApiClient, Response};
+
+pub type Output = Vec<Todo>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Todo {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub due: Option<DateTime<Utc>>,
+    pub is_completed: bool,
+}
+
+impl ApiClient {
+    /// Fetches all todos
+    pub async fn get_todos(&self, session_token: &str) -> Result<Response<Output>, reqwest::Error> {
+        self.get("/_fen_/get-todos", Some(session_token)).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn just_input() {
+        expect_rust(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            r#"
+use uuid::Uuid;
+
+use super::{ApiClient, Response};
+
+pub type Input = Uuid;
+
+impl ApiClient {
+    /// Completes or uncompletes a todo
+    pub async fn toggle_todo_completion(&self, input: &Input, session_token: &str) -> Result<Response<()>, reqwest::Error> {
+        self.post("/_fen_/toggle-todo-completion", input, Some(session_token)).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn struct_for_input_and_output() {
+        expect_rust(
+            r#"
+name: "Test"
+
+---
+
+@input {
+  id: UUID
+  foo: String
+  bar: [Date]?
+}
+
+@output {
+  stuff: [Thing]
+}
+
+---
+
+Thing {
+  type: ThingType
+  happy: Bool
+}
+
+ThingType (
+  first_option
+  second_option
+)
+            "#
+            .trim(),
+            r#"
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ApiClient, Response};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub id: Uuid,
+    pub foo: String,
+    pub bar: Option<Vec<DateTime<Utc>>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Output {
+    pub stuff: Vec<Thing>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Thing {
+    pub type: ThingType,
+    pub happy: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ThingType {
+    FirstOption,
+    SecondOption,
+}
+
+impl ApiClient {
+    pub async fn test(&self, input: &Input) -> Result<Response<Output>, reqwest::Error> {
+        self.post("/_fen_/test", input, None).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn sqlx_types_are_server_only() {
+        expect_rust(
+            r#"
+name: "GetRepertoire"
+description: "Get a user's repertoire"
+authed: true
+
+---
+
+@output [Song]
+
+---
+
+Song {
+  id: UUID
+  title: String
+  familiarity: FamiliarityLevel
+}
+
+@sqlxType
+FamiliarityLevel (
+  todo
+  learning
+  mastered
+)
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{ApiClient, Response};
+
+pub type Output = Vec<Song>;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Song {
+    pub id: Uuid,
+    pub title: String,
+    pub familiarity: FamiliarityLevel,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FamiliarityLevel {
+    Todo,
+    Learning,
+    Mastered,
+}
+
+impl ApiClient {
+    /// Get a user's repertoire
+    pub async fn get_repertoire(&self, session_token: &str) -> Result<Response<Output>, reqwest::Error> {
+        self.get("/_fen_/get-repertoire", Some(session_token)).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_rust(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{path_segment, ApiClient, Response};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub user_id: Uuid,
+    pub name: String,
+}
+
+pub type Output = User;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl ApiClient {
+    /// Renames a user
+    pub async fn update_user(&self, input: &Input, session_token: &str) -> Result<Response<Output>, reqwest::Error> {
+        self.request(reqwest::Method::PUT, &format!("/users/{}/name", path_segment(&input.user_id)), Some(input), Some(session_token)).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn get_with_path_params() {
+        expect_rust(
+            r#"
+name: "GetPost"
+method: "GET"
+path: "/users/{user_id}/posts/{slug}"
+authed: false
+
+---
+
+@input {
+  user_id: UUID
+  slug: String
+}
+
+@output String
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{path_segment, ApiClient, Response};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub user_id: Uuid,
+    pub slug: String,
+}
+
+pub type Output = String;
+
+impl ApiClient {
+    pub async fn get_post(&self, input: &Input) -> Result<Response<Output>, reqwest::Error> {
+        self.get(&format!("/users/{}/posts/{}", path_segment(&input.user_id), path_segment(&input.slug)), None).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_rust(
+            r#"
+name: "GetQuota"
+description: "Gets the remaining quota"
+
+---
+
+@output {
+  count: Int
+}
+
+@error {
+  reason: String
+  retry_after: Int?
+}
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+
+use super::{ApiClient, Response};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Output {
+    pub count: isize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Error {
+    pub reason: String,
+    pub retry_after: Option<isize>,
+}
+
+impl ApiClient {
+    /// Gets the remaining quota
+    pub async fn get_quota(&self) -> Result<Response<Output, Error>, reqwest::Error> {
+        self.get("/_fen_/get-quota", None).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_rust(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{path_segment, query_string, ApiClient, Response};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub user_id: Uuid,
+    pub page: Option<isize>,
+    pub tag: Option<String>,
+    pub x_client_version: String,
+}
+
+pub type Output = Vec<String>;
+
+impl ApiClient {
+    /// Lists a user's posts
+    pub async fn list_posts(&self, input: &Input, session_token: &str) -> Result<Response<Output>, reqwest::Error> {
+        self.send(reqwest::Method::GET, &format!("/users/{}/posts{}", path_segment(&input.user_id), query_string(&[("page", input.page.as_ref().map(ToString::to_string)), ("tag", input.tag.as_ref().map(ToString::to_string))])), None::<&()>, &[("x-client-version", Some(input.x_client_version.to_string()))], Some(session_token)).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn post_with_query_and_header_params() {
+        expect_rust(
+            r#"
+name: "Search"
+description: "Searches everything"
+
+---
+
+@input {
+  text: String
+  @query limit: Int
+  @header idempotency_key: String?
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+use serde::{Deserialize, Serialize};
+
+use super::{query_string, ApiClient, Response};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub text: String,
+    pub limit: isize,
+    pub idempotency_key: Option<String>,
+}
+
+pub type Output = Vec<String>;
+
+impl ApiClient {
+    /// Searches everything
+    pub async fn search(&self, input: &Input) -> Result<Response<Output>, reqwest::Error> {
+        self.send(reqwest::Method::POST, &format!("/_fen_/search{}", query_string(&[("limit", Some(input.limit.to_string()))])), Some(input), &[("idempotency-key", input.idempotency_key.as_ref().map(ToString::to_string))], None).await
+    }
+}
+            "#
+            .trim(),
+        );
+    }
+}
+
+mod swift_server_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_swift(fen_code: &str, swift_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let swift = ast.swift_server_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(swift, swift_code);
+    }
+
+    #[test]
+    fn just_output() {
+        expect_swift(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum GetTodos {
+  typealias Output = [Todo]
+
+  /// Fetches all todos
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, String) async throws -> Response<Output>
+  ) {
+    routes.get("_fen_", "get-todos") { request async throws -> Response<Output> in
+      guard let sessionToken = request.headers.bearerAuthorization?.token else {
+        return .failure(message: "Unauthorized", status: 401)
+      }
+      return try await handler(request, sessionToken)
+    }
+  }
+}
+
+struct Todo: Content, Equatable, Identifiable {
+  var id: UUID
+  var name: String
+  var description: String?
+  var due: Date?
+  var isCompleted: Bool
+
+  private enum CodingKeys: String, CodingKey {
+    case id
+    case name
+    case description
+    case due
+    case isCompleted
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.id, forKey: .id)
+    try container.encode(self.name, forKey: .name)
+    switch self.description {
+    case let .some(value):
+      try container.encode(value, forKey: .description)
+    case .none:
+      try container.encodeNil(forKey: .description)
+    }
+    switch self.due {
+    case let .some(value):
+      try container.encode(value, forKey: .due)
+    case .none:
+      try container.encodeNil(forKey: .due)
+    }
+    try container.encode(self.isCompleted, forKey: .isCompleted)
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn just_input() {
+        expect_swift(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum ToggleTodoCompletion {
+  typealias Input = UUID
+
+  /// Completes or uncompletes a todo
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, Input, String) async throws -> Response<NoData>
+  ) {
+    routes.post("_fen_", "toggle-todo-completion") { request async throws -> Response<NoData> in
+      guard let sessionToken = request.headers.bearerAuthorization?.token else {
+        return .failure(message: "Unauthorized", status: 401)
+      }
+      let input = try request.content.decode(Input.self, using: FenCoding.decoder)
+      return try await handler(request, input, sessionToken)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn enum_output_with_helpers() {
+        expect_swift(
+            r#"
+name: "EnumTest"
+description: "Just testing out enums"
+authed: true
+
+---
+
+@input {
+  ids: [UUID]
+  since: Date?
+  dates: [Date?]
+}
+
+@output (
+  single
+  married(Spouse)
+)
+
+---
+
+Spouse {
+  name: String
+  age: Int
+  has_beard: Bool
+  ocupation: Job
+  born: [Date]?
+}
+
+Job (
+  developer
+  construction
+  other(String?)
+)
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum EnumTest {
+  struct Input: Content, Equatable {
+    var ids: [UUID]
+    var since: Date?
+    var dates: [Date?]
+
+    private enum CodingKeys: String, CodingKey {
+      case ids
+      case since
+      case dates
+    }
+
+    func encode(to encoder: Encoder) throws {
+      var container = encoder.container(keyedBy: CodingKeys.self)
+
+      try container.encode(self.ids, forKey: .ids)
+      switch self.since {
+      case let .some(value):
+        try container.encode(value, forKey: .since)
+      case .none:
+        try container.encodeNil(forKey: .since)
+      }
+      try container.encode(self.dates, forKey: .dates)
+    }
+  }
+
+  enum Output: Content, Equatable {
+    case single
+    case married(Spouse)
+
+    private enum CodingKeys: String, CodingKey {
+      case type
+      case value
+    }
+
+    private enum OutputType: String, Codable {
+      case single
+      case married
+    }
+
+    init(from decoder: Decoder) throws {
+      let container = try decoder.container(keyedBy: CodingKeys.self)
+      let type = try container.decode(OutputType.self, forKey: .type)
+
+      switch type {
+      case .single:
+        self = .single
+      case .married:
+        let value = try container.decode(Spouse.self, forKey: .value)
+        self = .married(value)
+      }
+    }
+
+    func encode(to encoder: Encoder) throws {
+      var container = encoder.container(keyedBy: CodingKeys.self)
+
+      switch self {
+      case .single:
+        try container.encode(OutputType.single, forKey: .type)
+      case .married(let value):
+        try container.encode(OutputType.married, forKey: .type)
+        try container.encode(value, forKey: .value)
+      }
+    }
+  }
+
+  /// Just testing out enums
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, Input, String) async throws -> Response<Output>
+  ) {
+    routes.post("_fen_", "enum-test") { request async throws -> Response<Output> in
+      guard let sessionToken = request.headers.bearerAuthorization?.token else {
+        return .failure(message: "Unauthorized", status: 401)
+      }
+      let input = try request.content.decode(Input.self, using: FenCoding.decoder)
+      return try await handler(request, input, sessionToken)
+    }
+  }
+}
+
+struct Spouse: Content, Equatable {
+  var name: String
+  var age: Int
+  var hasBeard: Bool
+  var ocupation: Job
+  var born: [Date]?
+
+  private enum CodingKeys: String, CodingKey {
+    case name
+    case age
+    case hasBeard
+    case ocupation
+    case born
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.name, forKey: .name)
+    try container.encode(self.age, forKey: .age)
+    try container.encode(self.hasBeard, forKey: .hasBeard)
+    try container.encode(self.ocupation, forKey: .ocupation)
+    switch self.born {
+    case let .some(value):
+      try container.encode(value, forKey: .born)
+    case .none:
+      try container.encodeNil(forKey: .born)
+    }
+  }
+}
+
+enum Job: Content, Equatable {
+  case developer
+  case construction
+  case other(String?)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum JobType: String, Codable {
+    case developer
+    case construction
+    case other
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(JobType.self, forKey: .type)
+
+    switch type {
+    case .developer:
+      self = .developer
+    case .construction:
+      self = .construction
+    case .other:
+      let value = try container.decodeIfPresent(String.self, forKey: .value)
+      self = .other(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .developer:
+      try container.encode(JobType.developer, forKey: .type)
+    case .construction:
+      try container.encode(JobType.construction, forKey: .type)
+    case .other(let value):
+      try container.encode(JobType.other, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_swift(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum UpdateUser {
+  struct Input: Content, Equatable {
+    var userId: UUID
+    var name: String
+  }
+
+  struct Body: Content, Equatable {
+    var name: String
+  }
+
+  typealias Output = User
+
+  /// Renames a user
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, Input, String) async throws -> Response<Output>
+  ) {
+    routes.put("users", ":user_id", "name") { request async throws -> Response<Output> in
+      guard let sessionToken = request.headers.bearerAuthorization?.token else {
+        return .failure(message: "Unauthorized", status: 401)
+      }
+      let body = try request.content.decode(Body.self, using: FenCoding.decoder)
+      let input = Input(userId: try request.parameters.require("user_id", as: UUID.self), name: body.name)
+      return try await handler(request, input, sessionToken)
+    }
+  }
+}
+
+struct User: Content, Equatable, Identifiable {
+  var id: UUID
+  var name: String
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn get_with_path_params() {
+        expect_swift(
+            r#"
+name: "GetPost"
+method: "GET"
+path: "/users/{user_id}/posts/{slug}"
+authed: false
+
+---
+
+@input {
+  user_id: UUID
+  slug: String
+}
+
+@output String
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum GetPost {
+  struct Input: Content, Equatable {
+    var userId: UUID
+    var slug: String
+  }
+
+  typealias Output = String
+
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, Input) async throws -> Response<Output>
+  ) {
+    routes.get("users", ":user_id", "posts", ":slug") { request async throws -> Response<Output> in
+      let input = Input(userId: try request.parameters.require("user_id", as: UUID.self), slug: try request.parameters.require("slug", as: String.self))
+      return try await handler(request, input)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_swift(
+            r#"
+name: "GetQuota"
+description: "Gets the remaining quota"
+
+---
+
+@output {
+  count: Int
+}
+
+@error {
+  reason: String
+  retry_after: Int?
+}
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum GetQuota {
+  struct Output: Content, Equatable {
+    var count: Int
+  }
+
+  struct Error: Content, Equatable {
+    var reason: String
+    var retryAfter: Int?
+
+    private enum CodingKeys: String, CodingKey {
+      case reason
+      case retryAfter
+    }
+
+    func encode(to encoder: Encoder) throws {
+      var container = encoder.container(keyedBy: CodingKeys.self)
+
+      try container.encode(self.reason, forKey: .reason)
+      switch self.retryAfter {
+      case let .some(value):
+        try container.encode(value, forKey: .retryAfter)
+      case .none:
+        try container.encodeNil(forKey: .retryAfter)
+      }
+    }
+  }
+
+  /// Gets the remaining quota
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request) async throws -> ResponseWithError<Output, Error>
+  ) {
+    routes.get("_fen_", "get-quota") { request async throws -> ResponseWithError<Output, Error> in
+      return try await handler(request)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_swift(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum ListPosts {
+  struct Input: Content, Equatable {
+    var userId: UUID
+    var page: Int?
+    var tag: String?
+    var xClientVersion: String
+
+    private enum CodingKeys: String, CodingKey {
+      case userId
+      case page
+      case tag
+      case xClientVersion
+    }
+
+    func encode(to encoder: Encoder) throws {
+      var container = encoder.container(keyedBy: CodingKeys.self)
+
+      try container.encode(self.userId, forKey: .userId)
+      switch self.page {
+      case let .some(value):
+        try container.encode(value, forKey: .page)
+      case .none:
+        try container.encodeNil(forKey: .page)
+      }
+      switch self.tag {
+      case let .some(value):
+        try container.encode(value, forKey: .tag)
+      case .none:
+        try container.encodeNil(forKey: .tag)
+      }
+      try container.encode(self.xClientVersion, forKey: .xClientVersion)
+    }
+  }
+
+  typealias Output = [String]
+
+  /// Lists a user's posts
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, Input, String) async throws -> Response<Output>
+  ) {
+    routes.get("users", ":user_id", "posts") { request async throws -> Response<Output> in
+      guard let sessionToken = request.headers.bearerAuthorization?.token else {
+        return .failure(message: "Unauthorized", status: 401)
+      }
+      guard let xClientVersion = request.header("x-client-version", as: String.self) else {
+        return .failure(message: "Missing or invalid x-client-version header", status: 400)
+      }
+      let input = Input(userId: try request.parameters.require("user_id", as: UUID.self), page: request.query[Int.self, at: "page"], tag: request.query[String.self, at: "tag"], xClientVersion: xClientVersion)
+      return try await handler(request, input, sessionToken)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn generic_helper_types_conform_to_content() {
+        expect_swift(
+            r#"
+name: "ListTodos"
+authed: false
+
+---
+
+@input Status
+
+@output Page<Todo>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
+}
+
+Todo {
+  id: UUID
+  status: Status
+}
+
+Status (
+  open
+  done
+)
+            "#
+            .trim(),
+            r#"
+import Vapor
+
+enum ListTodos {
+  typealias Input = Status
+
+  typealias Output = Page<Todo>
+
+  static func register(
+    on routes: any RoutesBuilder,
+    handler: @escaping @Sendable (Request, Input) async throws -> Response<Output>
+  ) {
+    routes.post("_fen_", "list-todos") { request async throws -> Response<Output> in
+      let input = try request.content.decode(Input.self, using: FenCoding.decoder)
+      return try await handler(request, input)
+    }
+  }
+}
+
+struct Page<T: Content & Equatable>: Content, Equatable {
+  var items: [T]
+  var next: String?
+
+  private enum CodingKeys: String, CodingKey {
+    case items
+    case next
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.items, forKey: .items)
+    switch self.next {
+    case let .some(value):
+      try container.encode(value, forKey: .next)
+    case .none:
+      try container.encodeNil(forKey: .next)
+    }
+  }
+}
+
+struct Todo: Content, Equatable, Identifiable {
+  var id: UUID
+  var status: Status
+}
+
+enum Status: Content, Equatable {
+  case open
+  case done
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+  }
+
+  private enum StatusType: String, Codable {
+    case open
+    case done
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(StatusType.self, forKey: .type)
+
+    switch type {
+    case .open:
+      self = .open
+    case .done:
+      self = .done
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .open:
+      try container.encode(StatusType.open, forKey: .type)
+    case .done:
+      try container.encode(StatusType.done, forKey: .type)
+    }
+  }
+}
+            "#
+            .trim(),
+        );
+    }
+}
+
+mod kotlin_client_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_kotlin(fen_code: &str, kotlin_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let kotlin = ast.kotlin_client_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(kotlin, kotlin_code);
+    }
+
+    #[test]
+    fn just_output() {
+        expect_kotlin(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.builtins.ListSerializer
+
+/** Fetches all todos */
+suspend fun APIClient.getTodos(sessionToken: String): Response<List<Todo>> =
+    get("/_fen_/get-todos", ListSerializer(Todo.serializer()), sessionToken)
+
+@Serializable
+data class Todo(
+    val id: UUID,
+    val name: String,
+    val description: String?,
+    val due: Instant?,
+    val isCompleted: Boolean,
+)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn just_input() {
+        expect_kotlin(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
+
+---
+
+@input UUID
+            "#
+            .trim(),
+            r#"
+/** Completes or uncompletes a todo */
+suspend fun APIClient.toggleTodoCompletion(input: UUID, sessionToken: String): Response<Unit> =
+    post("/_fen_/toggle-todo-completion", input, UUIDSerializer, NoDataSerializer, sessionToken)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn struct_for_input_and_output() {
+        expect_kotlin(
+            r#"
+name: "Test"
+
+---
+
+@input {
+  id: UUID
+  foo: String
+  bar: [Date]?
+}
+
+@output {
+  stuff: [Thing]
+}
+
+---
+
+Thing {
+  type: ThingType
+  happy: Bool
+}
+
+ThingType (
+  first_option
+  second_option
+)
+            "#
+            .trim(),
+            r#"
+import kotlinx.datetime.Instant
+import kotlinx.serialization.SerialName
+import kotlinx.serialization.Serializable
+
+suspend fun APIClient.test(id: UUID, foo: String, bar: List<Instant>?): Response<TestOutput> =
+    post("/_fen_/test", TestInput(id = id, foo = foo, bar = bar), TestInput.serializer(), TestOutput.serializer(), null)
+
+@Serializable
+data class TestInput(
+    val id: UUID,
+    val foo: String,
+    val bar: List<Instant>?,
+)
+
+@Serializable
+data class TestOutput(
+    val stuff: List<Thing>,
+)
+
+@Serializable
+data class Thing(
+    val type: ThingType,
+    val happy: Boolean,
+)
+
+@Serializable
+sealed class ThingType {
+    @Serializable
+    @SerialName("firstOption")
+    data object FirstOption : ThingType()
+
+    @Serializable
+    @SerialName("secondOption")
+    data object SecondOption : ThingType()
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn enum_output_with_helpers() {
+        expect_kotlin(
+            r#"
+name: "EnumTest"
+description: "Just testing out enums"
+authed: true
+
+---
+
+@input {
+  ids: [UUID]
+  since: Date?
+  dates: [Date?]
+}
+
+@output (
+  single
+  married(Spouse)
+)
+
+---
+
+Spouse {
+  name: String
+  age: Int
+  has_beard: Bool
+  ocupation: Job
+  born: [Date]?
+}
+
+Job (
+  developer
+  construction
+  other(String?)
+)
+            "#
+            .trim(),
+            r#"
+import kotlinx.datetime.Instant
+import kotlinx.serialization.SerialName
+import kotlinx.serialization.Serializable
+
+/** Just testing out enums */
+suspend fun APIClient.enumTest(ids: List<UUID>, since: Instant?, dates: List<Instant?>, sessionToken: String): Response<EnumTestOutput> =
+    post("/_fen_/enum-test", EnumTestInput(ids = ids, since = since, dates = dates), EnumTestInput.serializer(), EnumTestOutput.serializer(), sessionToken)
+
+@Serializable
+data class EnumTestInput(
+    val ids: List<UUID>,
+    val since: Instant?,
+    val dates: List<Instant?>,
+)
+
+@Serializable
+sealed class EnumTestOutput {
+    @Serializable
+    @SerialName("single")
+    data object Single : EnumTestOutput()
+
+    @Serializable
+    @SerialName("married")
+    data class Married(val value: Spouse) : EnumTestOutput()
+}
+
+@Serializable
+data class Spouse(
+    val name: String,
+    val age: Long,
+    val hasBeard: Boolean,
+    val ocupation: Job,
+    val born: List<Instant>?,
+)
+
+@Serializable
+sealed class Job {
+    @Serializable
+    @SerialName("developer")
+    data object Developer : Job()
+
+    @Serializable
+    @SerialName("construction")
+    data object Construction : Job()
+
+    @Serializable
+    @SerialName("other")
+    data class Other(val value: String?) : Job()
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn maps() {
+        expect_kotlin(
+            r#"
+name: "GetFlags"
+authed: false
+
+---
+
+@input {
+  locale: String
+  overrides: {UUID: Bool}?
+}
+
+@output {String: Flag}
+
+---
+
+Flag {
+  enabled: Bool
+  since: Date
+  counts: {Int: [Int]}
+}
+            "#
+            .trim(),
+            r#"
+import kotlinx.datetime.Instant
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.builtins.MapSerializer
+import kotlinx.serialization.builtins.serializer
+
+suspend fun APIClient.getFlags(locale: String, overrides: Map<UUID, Boolean>?): Response<Map<String, Flag>> =
+    post("/_fen_/get-flags", GetFlagsInput(locale = locale, overrides = overrides), GetFlagsInput.serializer(), MapSerializer(String.serializer(), Flag.serializer()), null)
+
+@Serializable
+data class GetFlagsInput(
+    val locale: String,
+    val overrides: Map<UUID, Boolean>?,
+)
+
+@Serializable
+data class Flag(
+    val enabled: Boolean,
+    val since: Instant,
+    val counts: Map<Long, List<Long>>,
+)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        expect_kotlin(
+            r#"
+name: "UploadAvatar"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  image: Bytes
+  thumbnail: Bytes?
+}
+
+@output Attachment
+
+---
+
+Attachment (
+  none
+  signature(Bytes)
+)
+            "#
+            .trim(),
+            r#"
+import kotlinx.serialization.SerialName
+import kotlinx.serialization.Serializable
+
+suspend fun APIClient.uploadAvatar(userId: UUID, image: Bytes, thumbnail: Bytes?, sessionToken: String): Response<Attachment> =
+    post("/_fen_/upload-avatar", UploadAvatarInput(userId = userId, image = image, thumbnail = thumbnail), UploadAvatarInput.serializer(), Attachment.serializer(), sessionToken)
+
+@Serializable
+data class UploadAvatarInput(
+    val userId: UUID,
+    val image: Bytes,
+    val thumbnail: Bytes?,
+)
+
+@Serializable
+sealed class Attachment {
+    @Serializable
+    @SerialName("none")
+    data object None : Attachment()
+
+    @Serializable
+    @SerialName("signature")
+    data class Signature(val value: Bytes) : Attachment()
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn generics() {
+        expect_kotlin(
+            r#"
+name: "GetUsers"
+authed: false
+
+---
+
+@input {
+  cursor: String?
+}
+
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
+}
+
+Loadable<T> (
+  loading
+  loaded(T)
+)
+
+User {
+  id: UUID
+  avatar: Loadable<[String]>
+}
+            "#
+            .trim(),
+            r#"
+import kotlinx.serialization.SerialName
+import kotlinx.serialization.Serializable
+
+suspend fun APIClient.getUsers(cursor: String?): Response<Page<User>> =
+    post("/_fen_/get-users", GetUsersInput(cursor = cursor), GetUsersInput.serializer(), Page.serializer(User.serializer()), null)
+
+@Serializable
+data class GetUsersInput(
+    val cursor: String?,
+)
+
+@Serializable
+data class Page<T>(
+    val items: List<T>,
+    val next: String?,
+)
+
+@Serializable
+data class User(
+    val id: UUID,
+    val avatar: Loadable<List<String>>,
+)
+
+@Serializable
+sealed class Loadable<out T> {
+    @Serializable
+    @SerialName("loading")
+    data object Loading : Loadable<Nothing>()
+
+    @Serializable
+    @SerialName("loaded")
+    data class Loaded<T>(val value: T) : Loadable<T>()
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_kotlin(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+import kotlinx.serialization.Serializable
+
+/** Renames a user */
+suspend fun APIClient.updateUser(userId: UUID, name: String, sessionToken: String): Response<User> =
+    request("PUT", "/users/${APIClient.pathSegment(userId)}/name", UpdateUserInput(userId = userId, name = name), UpdateUserInput.serializer(), User.serializer(), sessionToken)
+
+@Serializable
+data class UpdateUserInput(
+    val userId: UUID,
+    val name: String,
+)
+
+@Serializable
+data class User(
+    val id: UUID,
+    val name: String,
+)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_kotlin(
+            r#"
+name: "GetQuota"
+description: "Gets the remaining quota"
+
+---
+
+@output {
+  count: Int
+}
+
+@error {
+  reason: String
+  retry_after: Int?
+}
+            "#
+            .trim(),
+            r#"
+import kotlinx.serialization.Serializable
+
+/** Gets the remaining quota */
+suspend fun APIClient.getQuota(): ResponseWithError<GetQuotaOutput, GetQuotaError> =
+    request("GET", "/_fen_/get-quota", GetQuotaOutput.serializer(), GetQuotaError.serializer(), null)
+
+@Serializable
+data class GetQuotaOutput(
+    val count: Long,
+)
+
+@Serializable
+data class GetQuotaError(
+    val reason: String,
+    val retryAfter: Long?,
+)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_kotlin(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
 
-    fn rust_server_code(&self, ctx: &Context) -> String {
-        match &self {
-            Self::Named(n) => n.clone(),
-            Self::Optional(t) => format!("Option<{}>", t.rust_server_code(ctx)),
-            Self::Array(t) => format!("Vec<{}>", t.rust_server_code(ctx)),
-            Self::Primitive(p) => p.rust_server_code(ctx),
-        }
-    }
-}
+---
 
-impl GenCode for Primitive {
-    fn swift_client_code(&self, _ctx: &Context) -> String {
-        match &self {
-            Self::Int => "Int".to_string(),
-            Self::Float => "Double".to_string(),
-            Self::String => "String".to_string(),
-            Self::Bool => "Bool".to_string(),
-            Self::Date => "Date".to_string(),
-            Self::Uuid => "UUID".to_string(),
-        }
-    }
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
 
-    fn rust_server_code(&self, _ctx: &Context) -> String {
-        match &self {
-            Self::Int => "isize".to_string(),
-            Self::Float => "f64".to_string(),
-            Self::String => "String".to_string(),
-            Self::Bool => "bool".to_string(),
-            Self::Date => "DateTime<Utc>".to_string(),
-            Self::Uuid => "Uuid".to_string(),
-        }
+@output [String]
+            "#
+            .trim(),
+            r#"
+import kotlinx.serialization.Serializable
+import kotlinx.serialization.builtins.ListSerializer
+import kotlinx.serialization.builtins.serializer
+
+/** Lists a user's posts */
+suspend fun APIClient.listPosts(userId: UUID, page: Long?, tag: String?, xClientVersion: String, sessionToken: String): Response<List<String>> =
+    request("GET", "/users/${APIClient.pathSegment(userId)}/posts" + APIClient.queryString("page" to page, "tag" to tag), ListSerializer(String.serializer()), sessionToken, headers = mapOf("x-client-version" to xClientVersion))
+
+@Serializable
+data class ListPostsInput(
+    val userId: UUID,
+    val page: Long?,
+    val tag: String?,
+    val xClientVersion: String,
+)
+            "#
+            .trim(),
+        );
     }
 }
 
-mod swift_client_tests {
+mod python_client_tests {
     use super::{Context, GenCode};
     use crate::Parser;
     use pretty_assertions::assert_eq;
 
-    fn expect_swift(fen_code: &str, swift_code: &str) {
+    fn expect_python_client(fen_code: &str, python_client_code: &str) {
         let mut parser = Parser::new(fen_code);
         let ast = parser.parse().unwrap();
-        let swift = ast.swift_client_code(&Context {
+        let python_client = ast.python_client_code(&Context {
             override_name: None,
         });
-        assert_eq!(swift, swift_code);
+        assert_eq!(python_client, python_client_code);
     }
 
     #[test]
-    fn just_output() {
-        expect_swift(
+    fn enum_with_helpers() {
+        expect_python_client(
+            r#"
+name: "EnumTest"
+description: "Just testing out enums"
+authed: true
+
+---
+
+@input {
+  ids: [UUID]
+  since: Date?
+  dates: [Date?]
+}
+
+@output (
+  single
+  married(Spouse)
+)
+
+---
+
+Spouse {
+  name: String
+  age: Int
+  has_beard: Bool
+  ocupation: Job
+  born: [Date]?
+}
+
+Job (
+  developer
+  construction
+  other(String?)
+)
+            "#
+            .trim(),
+            r#"
+from __future__ import annotations
+
+from datetime import datetime
+from typing import Annotated, Literal, Optional, Union
+from uuid import UUID
+
+from pydantic import Field
+
+from .api import APIClient, FenModel
+
+
+class EnumTestInput(FenModel):
+    ids: list[UUID]
+    since: Optional[datetime]
+    dates: list[Optional[datetime]]
+
+
+class EnumTestOutputSingle(FenModel):
+    type: Literal["single"] = "single"
+
+
+class EnumTestOutputMarried(FenModel):
+    type: Literal["married"] = "married"
+    value: Spouse
+
+
+EnumTestOutput = Annotated[Union[EnumTestOutputSingle, EnumTestOutputMarried], Field(discriminator="type")]
+
+
+class Spouse(FenModel):
+    name: str
+    age: int
+    has_beard: bool
+    ocupation: Job
+    born: Optional[list[datetime]]
+
+
+class JobDeveloper(FenModel):
+    type: Literal["developer"] = "developer"
+
+
+class JobConstruction(FenModel):
+    type: Literal["construction"] = "construction"
+
+
+class JobOther(FenModel):
+    type: Literal["other"] = "other"
+    value: Optional[str]
+
+
+Job = Annotated[Union[JobDeveloper, JobConstruction, JobOther], Field(discriminator="type")]
+
+
+def enum_test(client: APIClient, ids: list[UUID], since: Optional[datetime], dates: list[Optional[datetime]], session_token: str) -> EnumTestOutput:
+    """Just testing out enums"""
+    return client.post("/_fen_/enum-test", EnumTestInput(ids=ids, since=since, dates=dates), EnumTestInput, EnumTestOutput, session_token)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn array_output_authed() {
+        expect_python_client(
             r#"
 name: "GetTodos"
 description: "Fetches all todos"
@@ -608,58 +9145,34 @@ Todo {
             "#
             .trim(),
             r#"
-import Foundation
+from __future__ import annotations
 
-extension APIClient {
-  /// Fetches all todos
-  func getTodos(sessionToken: String) async throws -> Response<[Todo]> {
-    return try await self.fetcher.get(from: "/_fen_/get-todos", sessionToken: sessionToken)
-  }
-}
+from datetime import datetime
+from typing import Optional
+from uuid import UUID
 
-struct Todo: Codable, Equatable, Identifiable {
-  var id: UUID
-  var name: String
-  var description: String?
-  var due: Date?
-  var isCompleted: Bool
+from .api import APIClient, FenModel
 
-  private enum CodingKeys: String, CodingKey {
-    case id
-    case name
-    case description
-    case due
-    case isCompleted
-  }
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
+class Todo(FenModel):
+    id: UUID
+    name: str
+    description: Optional[str]
+    due: Optional[datetime]
+    is_completed: bool
 
-    try container.encode(self.id, forKey: .id)
-    try container.encode(self.name, forKey: .name)
-    switch self.description {
-    case let .some(value):
-      try container.encode(value, forKey: .description)
-    case .none:
-      try container.encodeNil(forKey: .description)
-    }
-    switch self.due {
-    case let .some(value):
-      try container.encode(value, forKey: .due)
-    case .none:
-      try container.encodeNil(forKey: .due)
-    }
-    try container.encode(self.isCompleted, forKey: .isCompleted)
-  }
-}
+
+def get_todos(client: APIClient, session_token: str) -> list[Todo]:
+    """Fetches all todos"""
+    return client.get("/_fen_/get-todos", list[Todo], session_token)
             "#
             .trim(),
         );
     }
 
     #[test]
-    fn just_input() {
-        expect_swift(
+    fn primitive_input_no_output() {
+        expect_python_client(
             r#"
 name: "ToggleTodoCompletion"
 description: "Completes or uncompletes a todo"
@@ -671,28 +9184,24 @@ authed: true
             "#
             .trim(),
             r#"
-import Foundation
+from __future__ import annotations
 
-extension APIClient {
-  /// Completes or uncompletes a todo
-  func toggleTodoCompletion(input: UUID, sessionToken: String) async throws -> Response<NoData> {
-    return try await self.fetcher.post(
-      to: "/_fen_/toggle-todo-completion",
-      with: input,
-      returning: NoData.self,
-      sessionToken: sessionToken
-    )
-  }
-}
+from uuid import UUID
+
+from .api import APIClient
+
+
+def toggle_todo_completion(client: APIClient, input: UUID, session_token: str) -> None:
+    """Completes or uncompletes a todo"""
+    return client.post("/_fen_/toggle-todo-completion", input, UUID, None, session_token)
             "#
             .trim(),
         );
     }
 
-    #[allow(clippy::too_many_lines)]
     #[test]
-    fn struct_for_input_and_output() {
-        expect_swift(
+    fn struct_input_output() {
+        expect_python_client(
             r#"
 name: "Test"
 
@@ -718,230 +9227,438 @@ Thing {
 ThingType (
   first_option
   second_option
-  third_option
 )
             "#
             .trim(),
             r#"
-import Foundation
+from __future__ import annotations
 
-extension APIClient {
-  func test(id: UUID, foo: String, bar: [Date]?) async throws -> Response<TestOutput> {
-    return try await self.fetcher.post(
-      to: "/_fen_/test",
-      with: TestInput(id: id, foo: foo, bar: bar),
-      returning: TestOutput.self,
-      sessionToken: nil
-    )
-  }
+from datetime import datetime
+from typing import Annotated, Literal, Optional, Union
+from uuid import UUID
+
+from pydantic import Field
+
+from .api import APIClient, FenModel
+
+
+class TestInput(FenModel):
+    id: UUID
+    foo: str
+    bar: Optional[list[datetime]]
+
+
+class TestOutput(FenModel):
+    stuff: list[Thing]
+
+
+class Thing(FenModel):
+    type: ThingType
+    happy: bool
+
+
+class ThingTypeFirstOption(FenModel):
+    type: Literal["firstOption"] = "firstOption"
+
+
+class ThingTypeSecondOption(FenModel):
+    type: Literal["secondOption"] = "secondOption"
+
+
+ThingType = Annotated[Union[ThingTypeFirstOption, ThingTypeSecondOption], Field(discriminator="type")]
+
+
+def test(client: APIClient, id: UUID, foo: str, bar: Optional[list[datetime]]) -> TestOutput:
+    return client.post("/_fen_/test", TestInput(id=id, foo=foo, bar=bar), TestInput, TestOutput, None)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn sized_numbers() {
+        expect_python_client(
+            r#"
+name: "GetInvoice"
+authed: false
+
+---
+
+@input Int64
+
+@output {
+  id: Int64
+  line_count: Int32
+  views: UInt
+  total: Decimal
+  discount: Decimal?
 }
+            "#
+            .trim(),
+            r#"
+from __future__ import annotations
 
-struct TestInput: Codable, Equatable, Identifiable {
-  var id: UUID
-  var foo: String
-  var bar: [Date]?
+from decimal import Decimal
+from typing import Optional
 
-  private enum CodingKeys: String, CodingKey {
-    case id
-    case foo
-    case bar
-  }
+from .api import APIClient, FenModel
+
+
+class GetInvoiceOutput(FenModel):
+    id: int
+    line_count: int
+    views: int
+    total: Decimal
+    discount: Optional[Decimal]
+
+
+def get_invoice(client: APIClient, input: int) -> GetInvoiceOutput:
+    return client.post("/_fen_/get-invoice", input, int, GetInvoiceOutput, None)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        expect_python_client(
+            r#"
+name: "UploadAvatar"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  image: Bytes
+  thumbnail: Bytes?
+}
+
+@output Attachment
+
+---
+
+Attachment (
+  none
+  signature(Bytes)
+)
+            "#
+            .trim(),
+            r#"
+from __future__ import annotations
+
+from typing import Annotated, Literal, Optional, Union
+from uuid import UUID
+
+from pydantic import Field
+
+from .api import APIClient, Bytes, FenModel
+
+
+class UploadAvatarInput(FenModel):
+    user_id: UUID
+    image: Bytes
+    thumbnail: Optional[Bytes]
+
+
+class AttachmentNone(FenModel):
+    type: Literal["none"] = "none"
+
+
+class AttachmentSignature(FenModel):
+    type: Literal["signature"] = "signature"
+    value: Bytes
+
+
+Attachment = Annotated[Union[AttachmentNone, AttachmentSignature], Field(discriminator="type")]
+
+
+def upload_avatar(client: APIClient, user_id: UUID, image: Bytes, thumbnail: Optional[Bytes], session_token: str) -> Attachment:
+    return client.post("/_fen_/upload-avatar", UploadAvatarInput(user_id=user_id, image=image, thumbnail=thumbnail), UploadAvatarInput, Attachment, session_token)
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn generics() {
+        expect_python_client(
+            r#"
+name: "GetUsers"
+authed: false
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
+---
 
-    try container.encode(self.id, forKey: .id)
-    try container.encode(self.foo, forKey: .foo)
-    switch self.bar {
-    case let .some(value):
-      try container.encode(value, forKey: .bar)
-    case .none:
-      try container.encodeNil(forKey: .bar)
-    }
-  }
+@input {
+  cursor: String?
 }
 
-struct TestOutput: Codable, Equatable {
-  var stuff: [Thing]
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
 }
 
-struct Thing: Codable, Equatable {
-  var type: ThingType
-  var happy: Bool
+Loadable<T> (
+  loading
+  loaded(T)
+)
+
+User {
+  id: UUID
+  avatar: Loadable<[String]>
 }
+            "#
+            .trim(),
+            r#"
+from __future__ import annotations
 
-enum ThingType: Codable, Equatable {
-  case firstOption
-  case secondOption
-  case thirdOption
+from typing import Annotated, Generic, Literal, Optional, TypeVar, Union
+from uuid import UUID
 
-  private enum CodingKeys: String, CodingKey {
-    case type
-  }
+from pydantic import Field
+from typing_extensions import TypeAliasType
 
-  private enum ThingTypeType: String, Codable {
-    case firstOption
-    case secondOption
-    case thirdOption
-  }
+from .api import APIClient, FenModel
 
-  init(from decoder: Decoder) throws {
-    let container = try decoder.container(keyedBy: CodingKeys.self)
-    let type = try container.decode(ThingTypeType.self, forKey: .type)
 
-    switch type {
-    case .firstOption:
-      self = .firstOption
-    case .secondOption:
-      self = .secondOption
-    case .thirdOption:
-      self = .thirdOption
-    }
-  }
+T = TypeVar("T")
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
 
-    switch self {
-    case .firstOption:
-      try container.encode(ThingTypeType.firstOption, forKey: .type)
-    case .secondOption:
-      try container.encode(ThingTypeType.secondOption, forKey: .type)
-    case .thirdOption:
-      try container.encode(ThingTypeType.thirdOption, forKey: .type)
-    }
-  }
-}
+class GetUsersInput(FenModel):
+    cursor: Optional[str]
+
+
+class Page(FenModel, Generic[T]):
+    items: list[T]
+    next: Optional[str]
+
+
+class User(FenModel):
+    id: UUID
+    avatar: Loadable[list[str]]
+
+
+class LoadableLoading(FenModel):
+    type: Literal["loading"] = "loading"
+
+
+class LoadableLoaded(FenModel, Generic[T]):
+    type: Literal["loaded"] = "loaded"
+    value: T
+
+
+Loadable = TypeAliasType("Loadable", Annotated[Union[LoadableLoading, LoadableLoaded[T]], Field(discriminator="type")], type_params=(T,))
+
+
+def get_users(client: APIClient, cursor: Optional[str]) -> Page[User]:
+    return client.post("/_fen_/get-users", GetUsersInput(cursor=cursor), GetUsersInput, Page[User], None)
             "#
             .trim(),
         );
     }
 
     #[test]
-    fn struct_for_input_no_output() {
-        expect_swift(
+    fn put_with_path_params() {
+        expect_python_client(
             r#"
-name: "Test"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
 authed: true
 
 ---
 
 @input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
   id: UUID
-  foo: String
-  bar: [Date]?
+  name: String
 }
             "#
             .trim(),
             r#"
-import Foundation
+from __future__ import annotations
 
-extension APIClient {
-  func test(id: UUID, foo: String, bar: [Date]?, sessionToken: String) async throws -> Response<NoData> {
-    return try await self.fetcher.post(
-      to: "/_fen_/test",
-      with: TestInput(id: id, foo: foo, bar: bar),
-      returning: NoData.self,
-      sessionToken: sessionToken
-    )
-  }
-}
+from uuid import UUID
 
-struct TestInput: Codable, Equatable, Identifiable {
-  var id: UUID
-  var foo: String
-  var bar: [Date]?
+from .api import APIClient, FenModel, path_segment
 
-  private enum CodingKeys: String, CodingKey {
-    case id
-    case foo
-    case bar
-  }
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
+class UpdateUserInput(FenModel):
+    user_id: UUID
+    name: str
 
-    try container.encode(self.id, forKey: .id)
-    try container.encode(self.foo, forKey: .foo)
-    switch self.bar {
-    case let .some(value):
-      try container.encode(value, forKey: .bar)
-    case .none:
-      try container.encodeNil(forKey: .bar)
-    }
-  }
-}
+
+class User(FenModel):
+    id: UUID
+    name: str
+
+
+def update_user(client: APIClient, user_id: UUID, name: str, session_token: str) -> User:
+    """Renames a user"""
+    return client.request("PUT", f"/users/{path_segment(user_id)}/name", UpdateUserInput(user_id=user_id, name=name), UpdateUserInput, User, session_token)
             "#
             .trim(),
         );
     }
 
     #[test]
-    fn input_is_struct_output_is_type() {
-        expect_swift(
+    fn route_errors() {
+        expect_python_client(
             r#"
-name: "YetAnotherTest"
+name: "ClaimHandle"
+description: "Claims a handle for the current user"
+method: "PUT"
+path: "/handles/{handle}"
+authed: true
 
 ---
 
 @input {
+  handle: String
+  display_name: String
+}
+
+@output {
   id: UUID
-  foo: String
 }
 
-@output [UUID]
+@error (
+  not_found
+  taken(Owner)
+)
+
+---
+
+Owner {
+  id: UUID
+  since: Date
+}
             "#
             .trim(),
             r#"
-import Foundation
+from __future__ import annotations
 
-extension APIClient {
-  func yetAnotherTest(id: UUID, foo: String) async throws -> Response<[UUID]> {
-    return try await self.fetcher.post(
-      to: "/_fen_/yet-another-test",
-      with: YetAnotherTestInput(id: id, foo: foo),
-      returning: [UUID].self,
-      sessionToken: nil
-    )
-  }
-}
+from datetime import datetime
+from typing import Annotated, Literal, Union
+from uuid import UUID
 
-struct YetAnotherTestInput: Codable, Equatable, Identifiable {
-  var id: UUID
-  var foo: String
-}
+from pydantic import Field
+
+from .api import APIClient, FenModel, path_segment
+
+
+class ClaimHandleInput(FenModel):
+    handle: str
+    display_name: str
+
+
+class ClaimHandleOutput(FenModel):
+    id: UUID
+
+
+class ClaimHandleErrorNotFound(FenModel):
+    type: Literal["notFound"] = "notFound"
+
+
+class ClaimHandleErrorTaken(FenModel):
+    type: Literal["taken"] = "taken"
+    value: Owner
+
+
+ClaimHandleError = Annotated[Union[ClaimHandleErrorNotFound, ClaimHandleErrorTaken], Field(discriminator="type")]
+
+
+class Owner(FenModel):
+    id: UUID
+    since: datetime
+
+
+def claim_handle(client: APIClient, handle: str, display_name: str, session_token: str) -> ClaimHandleOutput:
+    """Claims a handle for the current user"""
+    return client.request("PUT", f"/handles/{path_segment(handle)}", ClaimHandleInput(handle=handle, display_name=display_name), ClaimHandleInput, ClaimHandleOutput, session_token, error_type=ClaimHandleError)
             "#
             .trim(),
         );
     }
 
     #[test]
-    fn without_foundation() {
-        expect_swift(
+    fn query_and_header_params() {
+        expect_python_client(
             r#"
-name: "Test"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
 authed: true
 
 ---
 
-@output Int
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
             "#
             .trim(),
             r#"
-extension APIClient {
-  func test(sessionToken: String) async throws -> Response<Int> {
-    return try await self.fetcher.get(from: "/_fen_/test", sessionToken: sessionToken)
-  }
-}
+from __future__ import annotations
+
+from typing import Optional
+from uuid import UUID
+
+from .api import APIClient, FenModel, path_segment, query_string
+
+
+class ListPostsInput(FenModel):
+    user_id: UUID
+    page: Optional[int]
+    tag: Optional[str]
+    x_client_version: str
+
+
+def list_posts(client: APIClient, user_id: UUID, page: Optional[int], tag: Optional[str], x_client_version: str, session_token: str) -> list[str]:
+    """Lists a user's posts"""
+    return client.request("GET", f"/users/{path_segment(user_id)}/posts" + query_string({"page": page, "tag": tag}), None, None, list[str], session_token, headers={"x-client-version": x_client_version})
             "#
             .trim(),
         );
     }
+}
+
+mod go_client_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_go_client(fen_code: &str, go_client_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let go_client = ast.go_client_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(go_client, go_client_code);
+    }
 
     #[allow(clippy::too_many_lines)]
     #[test]
-    fn enum_output_with_helpers() {
-        expect_swift(
+    fn enum_with_helpers() {
+        expect_go_client(
             r#"
 name: "EnumTest"
 description: "Just testing out enums"
@@ -949,6 +9666,12 @@ authed: true
 
 ---
 
+@input {
+  ids: [UUID]
+  since: Date?
+  dates: [Date?]
+}
+
 @output (
   single
   married(Spouse)
@@ -961,6 +9684,7 @@ Spouse {
   age: Int
   has_beard: Bool
   ocupation: Job
+  born: [Date]?
 }
 
 Job (
@@ -971,104 +9695,123 @@ Job (
             "#
             .trim(),
             r#"
-extension APIClient {
-  /// Just testing out enums
-  func enumTest(sessionToken: String) async throws -> Response<EnumTestOutput> {
-    return try await self.fetcher.get(from: "/_fen_/enum-test", sessionToken: sessionToken)
-  }
-}
-
-enum EnumTestOutput: Codable, Equatable {
-  case single
-  case married(Spouse)
-
-  private enum CodingKeys: String, CodingKey {
-    case type
-    case value
-  }
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"time"
 
-  private enum EnumTestOutputType: String, Codable {
-    case single
-    case married
-  }
+	"github.com/google/uuid"
+)
 
-  init(from decoder: Decoder) throws {
-    let container = try decoder.container(keyedBy: CodingKeys.self)
-    let type = try container.decode(EnumTestOutputType.self, forKey: .type)
+type EnumTestInput struct {
+	Ids   []uuid.UUID  `json:"ids"`
+	Since *time.Time   `json:"since"`
+	Dates []*time.Time `json:"dates"`
+}
 
-    switch type {
-    case .single:
-      self = .single
-    case .married:
-      let value = try container.decode(Spouse.self, forKey: .value)
-      self = .married(value)
-    }
-  }
+type EnumTestOutputType string
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
+const (
+	EnumTestOutputSingle  EnumTestOutputType = "single"
+	EnumTestOutputMarried EnumTestOutputType = "married"
+)
 
-    switch self {
-    case .single:
-      try container.encode(EnumTestOutputType.single, forKey: .type)
-    case .married(let value):
-      try container.encode(EnumTestOutputType.married, forKey: .type)
-      try container.encode(value, forKey: .value)
-    }
-  }
+type EnumTestOutput struct {
+	Type    EnumTestOutputType
+	Married Spouse
 }
 
-struct Spouse: Codable, Equatable {
-  var name: String
-  var age: Int
-  var hasBeard: Bool
-  var ocupation: Job
+func (e EnumTestOutput) MarshalJSON() ([]byte, error) {
+	switch e.Type {
+	case EnumTestOutputMarried:
+		return json.Marshal(struct {
+			Type  EnumTestOutputType `json:"type"`
+			Value Spouse             `json:"value"`
+		}{e.Type, e.Married})
+	default:
+		return json.Marshal(struct {
+			Type EnumTestOutputType `json:"type"`
+		}{e.Type})
+	}
 }
 
-enum Job: Codable, Equatable {
-  case developer
-  case construction
-  case other(String?)
+func (e *EnumTestOutput) UnmarshalJSON(data []byte) error {
+	var raw struct {
+		Type  EnumTestOutputType `json:"type"`
+		Value json.RawMessage    `json:"value"`
+	}
+	if err := json.Unmarshal(data, &raw); err != nil {
+		return err
+	}
+	e.Type = raw.Type
+	switch raw.Type {
+	case EnumTestOutputSingle:
+		return nil
+	case EnumTestOutputMarried:
+		return json.Unmarshal(raw.Value, &e.Married)
+	default:
+		return fmt.Errorf("unknown EnumTestOutputType %q", raw.Type)
+	}
+}
 
-  private enum CodingKeys: String, CodingKey {
-    case type
-    case value
-  }
+type Spouse struct {
+	Name      string       `json:"name"`
+	Age       int64        `json:"age"`
+	HasBeard  bool         `json:"hasBeard"`
+	Ocupation Job          `json:"ocupation"`
+	Born      *[]time.Time `json:"born"`
+}
 
-  private enum JobType: String, Codable {
-    case developer
-    case construction
-    case other
-  }
+type JobType string
 
-  init(from decoder: Decoder) throws {
-    let container = try decoder.container(keyedBy: CodingKeys.self)
-    let type = try container.decode(JobType.self, forKey: .type)
+const (
+	JobDeveloper    JobType = "developer"
+	JobConstruction JobType = "construction"
+	JobOther        JobType = "other"
+)
 
-    switch type {
-    case .developer:
-      self = .developer
-    case .construction:
-      self = .construction
-    case .other:
-      let value = try container.decodeIfPresent(String.self, forKey: .value)
-      self = .other(value)
-    }
-  }
+type Job struct {
+	Type  JobType
+	Other *string
+}
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
+func (e Job) MarshalJSON() ([]byte, error) {
+	switch e.Type {
+	case JobOther:
+		return json.Marshal(struct {
+			Type  JobType `json:"type"`
+			Value *string `json:"value"`
+		}{e.Type, e.Other})
+	default:
+		return json.Marshal(struct {
+			Type JobType `json:"type"`
+		}{e.Type})
+	}
+}
 
-    switch self {
-    case .developer:
-      try container.encode(JobType.developer, forKey: .type)
-    case .construction:
-      try container.encode(JobType.construction, forKey: .type)
-    case .other(let value):
-      try container.encode(JobType.other, forKey: .type)
-      try container.encode(value, forKey: .value)
-    }
-  }
+func (e *Job) UnmarshalJSON(data []byte) error {
+	var raw struct {
+		Type  JobType         `json:"type"`
+		Value json.RawMessage `json:"value"`
+	}
+	if err := json.Unmarshal(data, &raw); err != nil {
+		return err
+	}
+	e.Type = raw.Type
+	switch raw.Type {
+	case JobDeveloper, JobConstruction:
+		return nil
+	case JobOther:
+		return json.Unmarshal(raw.Value, &e.Other)
+	default:
+		return fmt.Errorf("unknown JobType %q", raw.Type)
+	}
+}
+
+// EnumTest - Just testing out enums
+func (c *Client) EnumTest(ctx context.Context, input EnumTestInput, sessionToken string) (EnumTestOutput, error) {
+	return post[EnumTestOutput](ctx, c, "/_fen_/enum-test", input, sessionToken)
 }
             "#
             .trim(),
@@ -1076,130 +9819,173 @@ enum Job: Codable, Equatable {
     }
 
     #[test]
-    fn enum_input() {
-        expect_swift(
+    fn array_output_authed() {
+        expect_go_client(
             r#"
-name: "AnotherEnumTest"
-description: "Just testing out some more enums"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
 
 ---
 
-@input (
-  a
-  b(Int)
-)
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
             "#
             .trim(),
             r#"
-extension APIClient {
-  /// Just testing out some more enums
-  func anotherEnumTest(input: AnotherEnumTestInput) async throws -> Response<NoData> {
-    return try await self.fetcher.post(
-      to: "/_fen_/another-enum-test",
-      with: input,
-      returning: NoData.self,
-      sessionToken: nil
-    )
-  }
-}
+import (
+	"context"
+	"time"
 
-enum AnotherEnumTestInput: Codable, Equatable {
-  case a
-  case b(Int)
+	"github.com/google/uuid"
+)
 
-  private enum CodingKeys: String, CodingKey {
-    case type
-    case value
-  }
+type Todo struct {
+	Id          uuid.UUID  `json:"id"`
+	Name        string     `json:"name"`
+	Description *string    `json:"description"`
+	Due         *time.Time `json:"due"`
+	IsCompleted bool       `json:"isCompleted"`
+}
 
-  private enum AnotherEnumTestInputType: String, Codable {
-    case a
-    case b
-  }
+// GetTodos - Fetches all todos
+func (c *Client) GetTodos(ctx context.Context, sessionToken string) ([]Todo, error) {
+	return get[[]Todo](ctx, c, "/_fen_/get-todos", sessionToken)
+}
+            "#
+            .trim(),
+        );
+    }
 
-  init(from decoder: Decoder) throws {
-    let container = try decoder.container(keyedBy: CodingKeys.self)
-    let type = try container.decode(AnotherEnumTestInputType.self, forKey: .type)
+    #[test]
+    fn primitive_input_no_output() {
+        expect_go_client(
+            r#"
+name: "ToggleTodoCompletion"
+description: "Completes or uncompletes a todo"
+authed: true
 
-    switch type {
-    case .a:
-      self = .a
-    case .b:
-      let value = try container.decode(Int.self, forKey: .value)
-      self = .b(value)
-    }
-  }
+---
 
-  func encode(to encoder: Encoder) throws {
-    var container = encoder.container(keyedBy: CodingKeys.self)
+@input UUID
+            "#
+            .trim(),
+            r#"
+import (
+	"context"
 
-    switch self {
-    case .a:
-      try container.encode(AnotherEnumTestInputType.a, forKey: .type)
-    case .b(let value):
-      try container.encode(AnotherEnumTestInputType.b, forKey: .type)
-      try container.encode(value, forKey: .value)
-    }
-  }
+	"github.com/google/uuid"
+)
+
+// ToggleTodoCompletion - Completes or uncompletes a todo
+func (c *Client) ToggleTodoCompletion(ctx context.Context, input uuid.UUID, sessionToken string) error {
+	_, err := post[struct{}](ctx, c, "/_fen_/toggle-todo-completion", input, sessionToken)
+	return err
 }
             "#
             .trim(),
         );
     }
+
+    #[test]
+    fn sized_numbers() {
+        expect_go_client(
+            r#"
+name: "GetInvoice"
+authed: false
+
+---
+
+@input Int64
+
+@output {
+  id: Int64
+  line_count: Int32
+  views: UInt
+  total: Decimal
+  discount: Decimal?
 }
+            "#
+            .trim(),
+            r#"
+import (
+	"context"
 
-mod rust_server_tests {
-    use super::{Context, GenCode};
-    use crate::Parser;
-    use pretty_assertions::assert_eq;
+	"github.com/shopspring/decimal"
+)
 
-    fn expect_rust(fen_code: &str, rust_code: &str) {
-        let mut parser = Parser::new(fen_code);
-        let ast = parser.parse().unwrap();
-        let rust = ast.rust_server_code(&Context {
-            override_name: None,
-        });
-        assert_eq!(rust, rust_code);
+type GetInvoiceOutput struct {
+	Id        int64            `json:"id"`
+	LineCount int32            `json:"lineCount"`
+	Views     uint64           `json:"views"`
+	Total     decimal.Decimal  `json:"total"`
+	Discount  *decimal.Decimal `json:"discount"`
+}
+
+func (c *Client) GetInvoice(ctx context.Context, input int64) (GetInvoiceOutput, error) {
+	return post[GetInvoiceOutput](ctx, c, "/_fen_/get-invoice", input, "")
+}
+            "#
+            .trim(),
+        );
     }
 
     #[test]
-    fn just_output() {
-        expect_rust(
+    fn put_with_path_params() {
+        expect_go_client(
             r#"
-name: "GetTodos"
-description: "Fetches all todos"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
 authed: true
 
 ---
 
-@output [Todo]
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
 
 ---
 
-Todo {
+User {
   id: UUID
   name: String
-  description: String?
-  due: Date?
-  is_completed: Bool
 }
             "#
             .trim(),
             r#"
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+import (
+	"context"
 
-pub type Output = Vec<Todo>;
+	"github.com/google/uuid"
+)
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Todo {
-    pub id: Uuid,
-    pub name: String,
-    pub description: Option<String>,
-    pub due: Option<DateTime<Utc>>,
-    pub is_completed: bool,
+type UpdateUserInput struct {
+	UserId uuid.UUID `json:"userId"`
+	Name   string    `json:"name"`
+}
+
+type User struct {
+	Id   uuid.UUID `json:"id"`
+	Name string    `json:"name"`
+}
+
+// UpdateUser - Renames a user
+func (c *Client) UpdateUser(ctx context.Context, input UpdateUserInput, sessionToken string) (User, error) {
+	return request[User](ctx, c, "PUT", "/users/" + pathSegment(input.UserId) + "/name", input, sessionToken)
 }
             "#
             .trim(),
@@ -1207,30 +9993,123 @@ pub struct Todo {
     }
 
     #[test]
-    fn just_input() {
-        expect_rust(
+    fn delete_without_input() {
+        expect_go_client(
             r#"
-name: "ToggleTodoCompletion"
-description: "Completes or uncompletes a todo"
+name: "ClearCache"
+method: "DELETE"
+path: "/cache"
+authed: true
+---
+
+@output Int
+            "#
+            .trim(),
+            r#"
+import (
+	"context"
+)
+
+func (c *Client) ClearCache(ctx context.Context, sessionToken string) (int64, error) {
+	return request[int64](ctx, c, "DELETE", "/cache", nil, sessionToken)
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_go_client(
+            r#"
+name: "Shorten"
+description: "Shortens a link"
+
+---
+
+@input String
+@output Int
+@error String
+            "#
+            .trim(),
+            r#"
+import (
+	"context"
+)
+
+// Shorten - Shortens a link
+func (c *Client) Shorten(ctx context.Context, input string) (int64, error) {
+	return requestWithError[int64, string](ctx, c, "POST", "/_fen_/shorten", input, "")
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_go_client(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
 authed: true
 
 ---
 
-@input UUID
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
             "#
             .trim(),
-            r"
-use uuid::Uuid;
+            r#"
+import (
+	"context"
+	"encoding/json"
 
-pub type Input = Uuid;
-                "
+	"github.com/google/uuid"
+)
+
+type ListPostsInput struct {
+	UserId         uuid.UUID `json:"userId"`
+	Page           *int64    `json:"page"`
+	Tag            *string   `json:"tag"`
+	XClientVersion string    `json:"xClientVersion"`
+}
+
+// ListPosts - Lists a user's posts
+func (c *Client) ListPosts(ctx context.Context, input ListPostsInput, sessionToken string) ([]string, error) {
+	return requestWithHeaders[[]string, json.RawMessage](ctx, c, "GET", "/users/" + pathSegment(input.UserId) + "/posts" + queryString(param{"page", input.Page}, param{"tag", input.Tag}), nil, []param{{"x-client-version", input.XClientVersion}}, sessionToken)
+}
+            "#
             .trim(),
         );
     }
+}
+
+mod go_server_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_go_server(fen_code: &str, go_server_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        let go_server = ast.go_server_code(&Context {
+            override_name: None,
+        });
+        assert_eq!(go_server, go_server_code);
+    }
 
     #[test]
-    fn struct_for_input_and_output() {
-        expect_rust(
+    fn struct_input_output() {
+        expect_go_server(
             r#"
 name: "Test"
 
@@ -1254,220 +10133,318 @@ Thing {
 }
 
 ThingType (
-  a
-  b
-  c
+  first_option
+  second_option
 )
-                "#
+            "#
             .trim(),
             r#"
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+import (
+	"encoding/json"
+	"fmt"
+	"time"
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Input {
-    pub id: Uuid,
-    pub foo: String,
-    pub bar: Option<Vec<DateTime<Utc>>>,
+	"github.com/google/uuid"
+)
+
+type TestInput struct {
+	Id  uuid.UUID    `json:"id"`
+	Foo string       `json:"foo"`
+	Bar *[]time.Time `json:"bar"`
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Output {
-    pub stuff: Vec<Thing>,
+type TestOutput struct {
+	Stuff []Thing `json:"stuff"`
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Thing {
-    pub type: ThingType,
-    pub happy: bool,
+type Thing struct {
+	Type  ThingType `json:"type"`
+	Happy bool      `json:"happy"`
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(tag = "type", rename_all = "camelCase")]
-pub enum ThingType {
-    A,
-    B,
-    C,
+type ThingTypeType string
+
+const (
+	ThingTypeFirstOption  ThingTypeType = "firstOption"
+	ThingTypeSecondOption ThingTypeType = "secondOption"
+)
+
+type ThingType struct {
+	Type ThingTypeType
 }
-                "#
+
+func (e ThingType) MarshalJSON() ([]byte, error) {
+	return json.Marshal(struct {
+		Type ThingTypeType `json:"type"`
+	}{e.Type})
+}
+
+func (e *ThingType) UnmarshalJSON(data []byte) error {
+	var raw struct {
+		Type  ThingTypeType   `json:"type"`
+		Value json.RawMessage `json:"value"`
+	}
+	if err := json.Unmarshal(data, &raw); err != nil {
+		return err
+	}
+	e.Type = raw.Type
+	switch raw.Type {
+	case ThingTypeFirstOption, ThingTypeSecondOption:
+		return nil
+	default:
+		return fmt.Errorf("unknown ThingTypeType %q", raw.Type)
+	}
+}
+
+const (
+	TestMethod = "POST"
+	TestPath   = "/_fen_/test"
+)
+            "#
             .trim(),
         );
     }
 
     #[test]
-    fn struct_for_input_no_output() {
-        expect_rust(
+    fn just_output() {
+        expect_go_server(
             r#"
-name: "Test"
+name: "GetTodos"
+description: "Fetches all todos"
 authed: true
 
 ---
 
-@input {
+@output [Todo]
+
+---
+
+Todo {
   id: UUID
-  foo: String
-  bar: [Date]?
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
 }
-                "#
+            "#
             .trim(),
             r#"
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+import (
+	"time"
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Input {
-    pub id: Uuid,
-    pub foo: String,
-    pub bar: Option<Vec<DateTime<Utc>>>,
+	"github.com/google/uuid"
+)
+
+type Todo struct {
+	Id          uuid.UUID  `json:"id"`
+	Name        string     `json:"name"`
+	Description *string    `json:"description"`
+	Due         *time.Time `json:"due"`
+	IsCompleted bool       `json:"isCompleted"`
 }
-             "#
+
+const (
+	GetTodosMethod = "GET"
+	GetTodosPath   = "/_fen_/get-todos"
+)
+            "#
             .trim(),
         );
     }
 
     #[test]
-    fn input_is_struct_output_is_type() {
-        expect_rust(
+    fn put_with_path_params() {
+        expect_go_server(
             r#"
-name: "YetAnotherTest"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
 
 ---
 
 @input {
-  id: UUID
-  foo: String
+  user_id: UUID
+  name: String
 }
 
-@output [UUID]
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
             "#
             .trim(),
             r#"
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+import (
+	"github.com/google/uuid"
+)
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Input {
-    pub id: Uuid,
-    pub foo: String,
+type UpdateUserInput struct {
+	UserId uuid.UUID `json:"userId"`
+	Name   string    `json:"name"`
 }
 
-pub type Output = Vec<Uuid>;
+type User struct {
+	Id   uuid.UUID `json:"id"`
+	Name string    `json:"name"`
+}
+
+const (
+	UpdateUserMethod = "PUT"
+	UpdateUserPath   = "/users/{user_id}/name"
+)
             "#
             .trim(),
         );
     }
 
     #[test]
-    fn without_foundation() {
-        expect_rust(
+    fn route_errors() {
+        expect_go_server(
             r#"
-name: "Test"
-authed: true
+name: "GetQuota"
+description: "Gets the remaining quota"
 
 ---
 
-@output Int
-                    "#
-            .trim(),
-            r"
-pub type Output = isize;
-                "
-            .trim(),
-        );
-    }
+@output {
+  count: Int
+}
 
-    #[test]
-    fn enum_output_with_helpers() {
-        expect_rust(
+@error {
+  reason: String
+  retry_after: Int?
+}
+            "#
+            .trim(),
             r#"
-name: "EnumTest"
-description: "Just testing out enums"
-authed: true
+type GetQuotaOutput struct {
+	Count int64 `json:"count"`
+}
 
----
+type GetQuotaError struct {
+	Reason     string `json:"reason"`
+	RetryAfter *int64 `json:"retryAfter"`
+}
 
-@output (
-  single
-  married(Spouse)
+const (
+	GetQuotaMethod = "GET"
+	GetQuotaPath   = "/_fen_/get-quota"
 )
+            "#
+            .trim(),
+        );
+    }
+}
 
----
+mod shared_types_tests {
+    use super::{Context, GenCode};
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
 
-Spouse {
-  name: String
-  age: Int
-  has_beard: Bool
-  ocupation: Job
+    const SHARED: &str = r"
+User {
+  id: UUID
+  role: Role
 }
 
-Job (
-  developer
-  construction
-  other(String?)
+Role (
+  admin
+  member(Int)
 )
-                "#
-            .trim(),
+";
+
+    fn expect_shared(gen: impl Fn(&dyn GenCode, &Context) -> String, code: &str) {
+        let shared = Parser::new(SHARED.trim()).parse_shared().unwrap();
+        assert_eq!(
+            gen(
+                &shared,
+                &Context {
+                    override_name: None,
+                }
+            ),
+            code
+        );
+    }
+
+    fn expect_route(fen_code: &str, gen: impl Fn(&dyn GenCode, &Context) -> String, code: &str) {
+        let shared = Parser::new(SHARED.trim()).parse_shared().unwrap();
+        let ast = Parser::new(fen_code)
+            .with_shared_types(&shared)
+            .parse()
+            .unwrap();
+        assert_eq!(
+            gen(
+                &ast,
+                &Context {
+                    override_name: None,
+                }
+            ),
+            code
+        );
+    }
+
+    #[test]
+    fn rust_shared_file() {
+        expect_shared(
+            |node, ctx| node.rust_server_code(ctx),
             r#"
 use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(tag = "type", content = "value", rename_all = "camelCase")]
-pub enum Output {
-    Single,
-    Married(Spouse),
-}
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
-pub struct Spouse {
-    pub name: String,
-    pub age: isize,
-    pub has_beard: bool,
-    pub ocupation: Job,
+pub struct User {
+    pub id: Uuid,
+    pub role: Role,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 #[serde(tag = "type", content = "value", rename_all = "camelCase")]
-pub enum Job {
-    Developer,
-    Construction,
-    Other(Option<String>),
+pub enum Role {
+    Admin,
+    Member(isize),
 }
-                "#
+            "#
             .trim(),
         );
     }
 
     #[test]
-    fn enum_input() {
-        expect_rust(
+    fn typescript_shared_file() {
+        expect_shared(
+            |node, ctx| node.typescript_client_code(ctx),
             r#"
-name: "AnotherEnumTest"
-description: "Just testing out some more enums"
-
----
+export interface User {
+  id: string;
+  role: Role;
+}
 
-@input (
-  a
-  b(Int)
-)
-            "#
-            .trim(),
-            r#"
-use serde::{Deserialize, Serialize};
+export function decodeUser(json: any): User {
+  return {
+    id: json.id,
+    role: decodeRole(json.role),
+  };
+}
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(tag = "type", content = "value", rename_all = "camelCase")]
-pub enum Input {
-    A,
-    B(isize),
+export type Role =
+  | { type: "admin" }
+  | { type: "member"; value: number };
+
+export function decodeRole(json: any): Role {
+  switch (json.type) {
+    case "admin":
+      return { type: "admin" };
+    case "member":
+      return { type: "member", value: json.value };
+    default:
+      throw new Error(`Unknown Role type: ${json.type}`);
+  }
 }
             "#
             .trim(),
@@ -1475,60 +10452,56 @@ pub enum Input {
     }
 
     #[test]
-    fn sqlx_types() {
-        expect_rust(
+    fn rust_route_with_imports() {
+        expect_route(
             r#"
-name: "GetRepertoire"
-description: "Get a user's repertoire"
-authed: true
+name: "GetUsers"
+authed: false
+imports: [User Role]
 
 ---
 
-@output [Song]
+@input Role
 
----
+@output [User]
+            "#
+            .trim(),
+            |node, ctx| node.rust_server_code(ctx),
+            r"
+use super::shared::{User, Role};
 
-Song {
-  id: UUID
-  title: String
-  familiarity: FamiliarityLevel
-}
+pub type Input = Role;
 
-@sqlxType
-FamiliarityLevel (
-  todo
-  learning
-  playable
-  good
-  mastered
-)
-                "#
+pub type Output = Vec<User>;
+            "
             .trim(),
+        );
+    }
+
+    #[test]
+    fn typescript_route_with_imports() {
+        expect_route(
             r#"
-use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+name: "GetUsers"
+authed: false
+imports: [User Role]
 
-pub type Output = Vec<Song>;
+---
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(rename_all = "camelCase")]
-pub struct Song {
-    pub id: Uuid,
-    pub title: String,
-    pub familiarity: FamiliarityLevel,
-}
+@input Role
 
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, sqlx::Type)]
-#[serde(tag = "type", rename_all = "camelCase")]
-#[sqlx(type_name = "familiarity_level", rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum FamiliarityLevel {
-    Todo,
-    Learning,
-    Playable,
-    Good,
-    Mastered,
+@output [User]
+            "#
+            .trim(),
+            |node, ctx| node.typescript_client_code(ctx),
+            r#"
+import { Fetcher, Response } from "./api";
+import { User, decodeUser, Role, decodeRole } from "./shared";
+
+export async function getUsers(fetcher: Fetcher, input: Role): Promise<Response<User[]>> {
+  return fetcher.post("/_fen_/get-users", input, null, (json) => json.map((item: any) => decodeUser(item)));
 }
-                "#
+            "#
             .trim(),
         );
     }
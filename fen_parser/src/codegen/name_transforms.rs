@@ -79,6 +79,12 @@ pub fn pascal_to_snake(s: &str) -> String {
     result
 }
 
+/// The name of a route's module (and file) in the Rust client and server, so both sides agree.
+#[must_use]
+pub fn rust_module_name(route_name: &str) -> String {
+    pascal_to_snake(route_name)
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -125,4 +131,10 @@ mod tests {
         assert_eq!(super::pascal_to_snake("FooBar"), "foo_bar");
         assert_eq!(super::pascal_to_snake("FooBarBazQux"), "foo_bar_baz_qux");
     }
+
+    #[test]
+    fn test_rust_module_name() {
+        assert_eq!(super::rust_module_name("GetTodos"), "get_todos");
+        assert_eq!(super::rust_module_name("GetURL"), "get_u_r_l");
+    }
 }
@@ -0,0 +1,906 @@
+use serde_json::{json, Map, Value};
+
+use super::json_schema::{add_instance_schemas, enum_schema, io_types, struct_schema, type_schema};
+use super::name_transforms::{pascal_to_camel, snake_to_camel};
+use crate::ast::{
+    EnumDefinition, FileNode, IOType, ParamLocation, SharedTypes, StructDefinition, Type,
+};
+
+const COMPONENTS: &str = "#/components/schemas/";
+
+/// Builds an `OpenAPI` 3.1 document describing every route.
+///
+/// Each route becomes a single operation (at its own method and path, or by default GET when it
+/// has no input and POST otherwise), and struct/enum inputs, outputs, errors, helper types, and shared types become component schemas.
+#[must_use]
+pub fn openapi_document(
+    routes: &[&FileNode],
+    shared: &SharedTypes,
+    title: &str,
+    version: &str,
+) -> Value {
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+
+    for struct_def in shared.structs.iter().filter(|s| s.generics.is_empty()) {
+        schemas.insert(
+            struct_def.name.clone(),
+            struct_schema(struct_def, COMPONENTS),
+        );
+    }
+    for enum_def in shared.enums.iter().filter(|e| e.generics.is_empty()) {
+        schemas.insert(enum_def.name.clone(), enum_schema(enum_def, COMPONENTS));
+    }
+
+    for route in routes {
+        // register the route's named types as components
+        // (when only part of the input is in the body, that part is all the document describes)
+        if let Some(body) = route.body_struct() {
+            schemas.insert(
+                format!("{}Body", route.name),
+                struct_schema(&body, COMPONENTS),
+            );
+        } else if let Some(input) = &route.input {
+            add_io_schema(&mut schemas, input, &format!("{}Input", route.name));
+        }
+        if let Some(output) = &route.output {
+            add_io_schema(&mut schemas, output, &format!("{}Output", route.name));
+        }
+        if let Some(error) = &route.error {
+            add_io_schema(&mut schemas, error, &format!("{}Error", route.name));
+        }
+        for struct_def in route.structs.iter().filter(|s| s.generics.is_empty()) {
+            schemas.insert(
+                struct_def.name.clone(),
+                struct_schema(struct_def, COMPONENTS),
+            );
+        }
+        for enum_def in route.enums.iter().filter(|e| e.generics.is_empty()) {
+            schemas.insert(enum_def.name.clone(), enum_schema(enum_def, COMPONENTS));
+        }
+        add_route_instance_schemas(&mut schemas, route, shared);
+
+        // routes can share a path (with different methods)
+        if let Value::Object(operations) = paths
+            .entry(route.http_path())
+            .or_insert_with(|| Value::Object(Map::new()))
+        {
+            operations.insert(
+                route.http_method().lowercase_name().to_string(),
+                operation(route),
+            );
+        }
+    }
+
+    // every route shares the same failure shape
+    schemas.insert(
+        "FailureResponse".to_string(),
+        json!({
+            "type": "object",
+            "properties": {
+                "type": { "const": "failure" },
+                "message": { "type": "string" },
+                "status": { "type": "integer" },
+            },
+            "required": ["type", "message", "status"],
+        }),
+    );
+
+    let mut components = Map::new();
+    components.insert("schemas".to_string(), Value::Object(schemas));
+    if routes.iter().any(|route| route.authed) {
+        components.insert(
+            "securitySchemes".to_string(),
+            json!({
+                "bearerAuth": { "type": "http", "scheme": "bearer" },
+            }),
+        );
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": { "title": title, "version": version },
+        "paths": paths,
+        "components": components,
+    })
+}
+
+/// Adds component schemas for the instantiations of generic types that a route refers to.
+fn add_route_instance_schemas(
+    schemas: &mut Map<String, Value>,
+    route: &FileNode,
+    shared: &SharedTypes,
+) {
+    let structs = route
+        .structs
+        .iter()
+        .chain(&shared.structs)
+        .collect::<Vec<&StructDefinition>>();
+    let enums = route
+        .enums
+        .iter()
+        .chain(&shared.enums)
+        .collect::<Vec<&EnumDefinition>>();
+
+    let mut types = vec![];
+    types.extend(route.input.iter().flat_map(io_types));
+    types.extend(route.output.iter().flat_map(io_types));
+    types.extend(route.error.iter().flat_map(io_types));
+    for struct_def in structs.iter().filter(|s| s.generics.is_empty()) {
+        types.extend(struct_def.fields.iter().map(|field| &field.t));
+    }
+    for enum_def in enums.iter().filter(|e| e.generics.is_empty()) {
+        types.extend(enum_def.variants.iter().filter_map(|v| v.t.as_ref()));
+    }
+
+    add_instance_schemas(schemas, &types, &structs, &enums, COMPONENTS);
+}
+
+fn operation(route: &FileNode) -> Value {
+    let mut operation = Map::new();
+    operation.insert(
+        "operationId".to_string(),
+        Value::String(pascal_to_camel(&route.name)),
+    );
+    if let Some(description) = &route.description {
+        operation.insert(
+            "description".to_string(),
+            Value::String(description.clone()),
+        );
+    }
+
+    if let Some(IOType::Struct(s)) = &route.input {
+        let mut parameters = route
+            .path_params()
+            .into_iter()
+            .filter_map(|param| s.fields.iter().find(|field| field.name == param))
+            .map(|field| {
+                json!({
+                    "name": field.name,
+                    "in": "path",
+                    "required": true,
+                    "schema": type_schema(&field.t, COMPONENTS),
+                })
+            })
+            .collect::<Vec<Value>>();
+        // `@query` and `@header` fields can be left out, if they're optional
+        for location in [ParamLocation::Query, ParamLocation::Header] {
+            parameters.extend(route.fields_in(location).into_iter().map(|field| {
+                let (t, required) = match &field.t {
+                    Type::Optional(t, _) => (&**t, false),
+                    t => (t, true),
+                };
+                json!({
+                    "name": match location {
+                        ParamLocation::Query => snake_to_camel(&field.name),
+                        ParamLocation::Header => field.header_name(),
+                    },
+                    "in": location.name(),
+                    "required": required,
+                    "schema": type_schema(t, COMPONENTS),
+                })
+            }));
+        }
+        if !parameters.is_empty() {
+            operation.insert("parameters".to_string(), Value::Array(parameters));
+        }
+    }
+
+    if let (Some(input), true) = (&route.input, route.has_body()) {
+        operation.insert(
+            "requestBody".to_string(),
+            json!({
+                "required": true,
+                "content": {
+                    "application/json": {
+                        "schema": if route.body_struct().is_some() {
+                        schema_ref(&format!("{}Body", route.name))
+                    } else {
+                        io_schema(input, &format!("{}Input", route.name))
+                    },
+                    },
+                },
+            }),
+        );
+    }
+
+    operation.insert("responses".to_string(), json!({ "200": response(route) }));
+
+    if route.authed {
+        operation.insert("security".to_string(), json!([{ "bearerAuth": [] }]));
+    }
+
+    Value::Object(operation)
+}
+
+/// The route's 200 response. Successful values are wrapped in the `Response<T>` envelope (or, for
+/// streams, each line is an event, a failure, or the end).
+fn response(route: &FileNode) -> Value {
+    let value = route.output.as_ref().map_or_else(
+        || json!({ "type": "null" }),
+        |output| io_schema(output, &format!("{}Output", route.name)),
+    );
+    let mut variants = vec![json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": if route.stream { "event" } else { "success" } },
+            "value": value,
+        },
+        "required": ["type", "value"],
+    })];
+    // the route's own errors are wrapped too
+    if let Some(error) = &route.error {
+        variants.push(json!({
+            "type": "object",
+            "properties": {
+                "type": { "const": "error" },
+                "value": io_schema(error, &format!("{}Error", route.name)),
+            },
+            "required": ["type", "value"],
+        }));
+    }
+    variants.push(json!({ "$ref": "#/components/schemas/FailureResponse" }));
+    if route.stream {
+        variants.push(json!({
+            "type": "object",
+            "properties": {
+                "type": { "const": "end" },
+            },
+            "required": ["type"],
+        }));
+        json!({
+            "description": "The route's events, as newline-delimited JSON",
+            "content": {
+                "application/x-ndjson": {
+                    "schema": { "oneOf": variants },
+                },
+            },
+        })
+    } else {
+        json!({
+            "description": "The route's response",
+            "content": {
+                "application/json": {
+                    "schema": { "oneOf": variants },
+                },
+            },
+        })
+    }
+}
+
+fn add_io_schema(schemas: &mut Map<String, Value>, io: &IOType, name: &str) {
+    match io {
+        IOType::Struct(s) => {
+            schemas.insert(name.to_string(), struct_schema(s, COMPONENTS));
+        }
+        IOType::Enum(e) => {
+            schemas.insert(name.to_string(), enum_schema(e, COMPONENTS));
+        }
+        IOType::Type(_) => {}
+    }
+}
+
+/// The schema for an input, output, or error, referencing its component if it has one.
+fn io_schema(io: &IOType, name: &str) -> Value {
+    match io {
+        IOType::Type(t) => type_schema(t, COMPONENTS),
+        IOType::Struct(_) | IOType::Enum(_) => schema_ref(name),
+    }
+}
+
+fn schema_ref(name: &str) -> Value {
+    json!({ "$ref": format!("{COMPONENTS}{name}") })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::openapi_document;
+    use crate::ast::SharedTypes;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn expect_openapi(fen_code: &str, document: &serde_json::Value) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        assert_eq!(
+            &openapi_document(&[&ast], &SharedTypes::default(), "Test", "1.0.0"),
+            document
+        );
+    }
+
+    #[test]
+    fn get_without_input() {
+        expect_openapi(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  due: Date?
+}
+            "#
+            .trim(),
+            &json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {
+                    "/_fen_/get-todos": {
+                        "get": {
+                            "operationId": "getTodos",
+                            "description": "Fetches all todos",
+                            "responses": {
+                                "200": {
+                                    "description": "The route's response",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": {
+                                                "oneOf": [
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "success" },
+                                                            "value": {
+                                                                "type": "array",
+                                                                "items": { "$ref": "#/components/schemas/Todo" },
+                                                            },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    { "$ref": "#/components/schemas/FailureResponse" },
+                                                ],
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                            "security": [{ "bearerAuth": [] }],
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "Todo": {
+                            "type": "object",
+                            "properties": {
+                                "id": { "type": "string", "format": "uuid" },
+                                "name": { "type": "string" },
+                                "due": {
+                                    "anyOf": [
+                                        { "type": "string", "format": "date-time" },
+                                        { "type": "null" },
+                                    ],
+                                },
+                            },
+                            "required": ["id", "name"],
+                        },
+                        "FailureResponse": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "const": "failure" },
+                                "message": { "type": "string" },
+                                "status": { "type": "integer" },
+                            },
+                            "required": ["type", "message", "status"],
+                        },
+                    },
+                    "securitySchemes": {
+                        "bearerAuth": { "type": "http", "scheme": "bearer" },
+                    },
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn post_with_enum_input_and_no_output() {
+        expect_openapi(
+            r#"
+name: "SetMood"
+authed: false
+
+---
+
+@input (
+  happy
+  other(String)
+)
+            "#
+            .trim(),
+            &json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {
+                    "/_fen_/set-mood": {
+                        "post": {
+                            "operationId": "setMood",
+                            "requestBody": {
+                                "required": true,
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/SetMoodInput" },
+                                    },
+                                },
+                            },
+                            "responses": {
+                                "200": {
+                                    "description": "The route's response",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": {
+                                                "oneOf": [
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "success" },
+                                                            "value": { "type": "null" },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    { "$ref": "#/components/schemas/FailureResponse" },
+                                                ],
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "SetMoodInput": {
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "properties": { "type": { "const": "happy" } },
+                                    "required": ["type"],
+                                },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "type": { "const": "other" },
+                                        "value": { "type": "string" },
+                                    },
+                                    "required": ["type", "value"],
+                                },
+                            ],
+                        },
+                        "FailureResponse": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "const": "failure" },
+                                "message": { "type": "string" },
+                                "status": { "type": "integer" },
+                            },
+                            "required": ["type", "message", "status"],
+                        },
+                    },
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn get_with_path_params() {
+        expect_openapi(
+            r#"
+name: "GetPost"
+method: "GET"
+path: "/users/{user_id}/posts/{slug}"
+authed: false
+
+---
+
+@input {
+  user_id: UUID
+  slug: String
+}
+
+@output String
+            "#
+            .trim(),
+            &json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {
+                    "/users/{user_id}/posts/{slug}": {
+                        "get": {
+                            "operationId": "getPost",
+                            "parameters": [
+                                {
+                                    "name": "user_id",
+                                    "in": "path",
+                                    "required": true,
+                                    "schema": { "type": "string", "format": "uuid" },
+                                },
+                                {
+                                    "name": "slug",
+                                    "in": "path",
+                                    "required": true,
+                                    "schema": { "type": "string" },
+                                },
+                            ],
+                            "responses": {
+                                "200": {
+                                    "description": "The route's response",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": {
+                                                "oneOf": [
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "success" },
+                                                            "value": { "type": "string" },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    { "$ref": "#/components/schemas/FailureResponse" },
+                                                ],
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "GetPostInput": {
+                            "type": "object",
+                            "properties": {
+                                "userId": { "type": "string", "format": "uuid" },
+                                "slug": { "type": "string" },
+                            },
+                            "required": ["userId", "slug"],
+                        },
+                        "FailureResponse": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "const": "failure" },
+                                "message": { "type": "string" },
+                                "status": { "type": "integer" },
+                            },
+                            "required": ["type", "message", "status"],
+                        },
+                    },
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn put_with_path_query_and_header_params() {
+        expect_openapi(
+            r#"
+name: "RenameItem"
+method: "PUT"
+path: "/items/{item_id}"
+
+---
+
+@input {
+  item_id: UUID
+  new_name: String
+  @query dry_run: Bool?
+  @header x_request_id: String
+}
+
+@output Bool
+            "#
+            .trim(),
+            &json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {
+                    "/items/{item_id}": {
+                        "put": {
+                            "operationId": "renameItem",
+                            "parameters": [
+                                {
+                                    "name": "item_id",
+                                    "in": "path",
+                                    "required": true,
+                                    "schema": { "type": "string", "format": "uuid" },
+                                },
+                                {
+                                    "name": "dryRun",
+                                    "in": "query",
+                                    "required": false,
+                                    "schema": { "type": "boolean" },
+                                },
+                                {
+                                    "name": "x-request-id",
+                                    "in": "header",
+                                    "required": true,
+                                    "schema": { "type": "string" },
+                                },
+                            ],
+                            "requestBody": {
+                                "required": true,
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/RenameItemBody" },
+                                    },
+                                },
+                            },
+                            "responses": {
+                                "200": {
+                                    "description": "The route's response",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": {
+                                                "oneOf": [
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "success" },
+                                                            "value": { "type": "boolean" },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    { "$ref": "#/components/schemas/FailureResponse" },
+                                                ],
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "RenameItemBody": {
+                            "type": "object",
+                            "properties": {
+                                "newName": { "type": "string" },
+                            },
+                            "required": ["newName"],
+                        },
+                        "FailureResponse": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "const": "failure" },
+                                "message": { "type": "string" },
+                                "status": { "type": "integer" },
+                            },
+                            "required": ["type", "message", "status"],
+                        },
+                    },
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_openapi(
+            r#"
+name: "DeleteTodo"
+description: "Deletes a todo"
+
+---
+
+@input UUID
+
+@error (
+  not_found
+  locked(String)
+)
+            "#
+            .trim(),
+            &json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {
+                    "/_fen_/delete-todo": {
+                        "post": {
+                            "operationId": "deleteTodo",
+                            "description": "Deletes a todo",
+                            "requestBody": {
+                                "required": true,
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "type": "string", "format": "uuid" },
+                                    },
+                                },
+                            },
+                            "responses": {
+                                "200": {
+                                    "description": "The route's response",
+                                    "content": {
+                                        "application/json": {
+                                            "schema": {
+                                                "oneOf": [
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "success" },
+                                                            "value": { "type": "null" },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "error" },
+                                                            "value": { "$ref": "#/components/schemas/DeleteTodoError" },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    { "$ref": "#/components/schemas/FailureResponse" },
+                                                ],
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "DeleteTodoError": {
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "properties": { "type": { "const": "notFound" } },
+                                    "required": ["type"],
+                                },
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "type": { "const": "locked" },
+                                        "value": { "type": "string" },
+                                    },
+                                    "required": ["type", "value"],
+                                },
+                            ],
+                        },
+                        "FailureResponse": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "const": "failure" },
+                                "message": { "type": "string" },
+                                "status": { "type": "integer" },
+                            },
+                            "required": ["type", "message", "status"],
+                        },
+                    },
+                },
+            }),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        let ast = Parser::new(
+            r#"
+name: "ListPosts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: false
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+        )
+        .parse()
+        .unwrap();
+        let document = openapi_document(&[&ast], &SharedTypes::default(), "Test", "1.0.0");
+
+        assert_eq!(
+            document["paths"]["/users/{user_id}/posts"]["get"]["parameters"],
+            json!([
+                {
+                    "name": "user_id",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string", "format": "uuid" },
+                },
+                {
+                    "name": "page",
+                    "in": "query",
+                    "required": false,
+                    "schema": { "type": "integer", "format": "int64" },
+                },
+                {
+                    "name": "x-client-version",
+                    "in": "header",
+                    "required": true,
+                    "schema": { "type": "string" },
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn streaming_output() {
+        expect_openapi(
+            r#"
+name: "WatchPrices"
+method: "GET"
+path: "/prices"
+authed: false
+
+---
+
+@output stream Int
+            "#
+            .trim(),
+            &json!({
+                "openapi": "3.1.0",
+                "info": { "title": "Test", "version": "1.0.0" },
+                "paths": {
+                    "/prices": {
+                        "get": {
+                            "operationId": "watchPrices",
+                            "responses": {
+                                "200": {
+                                    "description": "The route's events, as newline-delimited JSON",
+                                    "content": {
+                                        "application/x-ndjson": {
+                                            "schema": {
+                                                "oneOf": [
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "event" },
+                                                            "value": { "type": "integer", "format": "int64" },
+                                                        },
+                                                        "required": ["type", "value"],
+                                                    },
+                                                    { "$ref": "#/components/schemas/FailureResponse" },
+                                                    {
+                                                        "type": "object",
+                                                        "properties": {
+                                                            "type": { "const": "end" },
+                                                        },
+                                                        "required": ["type"],
+                                                    },
+                                                ],
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                },
+                "components": {
+                    "schemas": {
+                        "FailureResponse": {
+                            "type": "object",
+                            "properties": {
+                                "type": { "const": "failure" },
+                                "message": { "type": "string" },
+                                "status": { "type": "integer" },
+                            },
+                            "required": ["type", "message", "status"],
+                        },
+                    },
+                },
+            }),
+        );
+    }
+}
@@ -0,0 +1,948 @@
+use super::name_transforms::{pascal_to_kebab, pascal_to_snake, rust_module_name};
+use super::{has_constraints, rust_deprecated};
+use crate::ast::{FileNode, IOType, Method, ParamLocation, Type};
+
+/// Generates a `Handlers` trait with one method per route, and a `router` function that
+/// mounts each of them on an `axum::Router` (so a missing handler is a compile error).
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub fn rust_router_code(routes: &[&FileNode]) -> String {
+    let mut routes = routes.to_vec();
+    routes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut mods = routes
+        .iter()
+        .map(|route| rust_module_name(&route.name))
+        .collect::<Vec<String>>();
+    // only import what's used, so the generated code is warning-free
+    if routes.iter().any(|route| route.path.is_none()) {
+        mods.push("fen_path".to_string());
+    }
+    if routes.iter().any(|route| !route.stream) {
+        mods.push("Response".to_string());
+    }
+    if routes.iter().any(|route| route.stream) {
+        mods.push("EventStream".to_string());
+    }
+
+    let any_authed = routes.iter().any(|route| route.authed);
+    let any_headers = routes
+        .iter()
+        .any(|route| !route.fields_in(ParamLocation::Header).is_empty());
+    let any_path = routes.iter().any(|route| !route.path_params().is_empty());
+    let any_query = routes
+        .iter()
+        .any(|route| !route.fields_in(ParamLocation::Query).is_empty());
+    let methods = [
+        Method::Get,
+        Method::Post,
+        Method::Put,
+        Method::Patch,
+        Method::Delete,
+    ]
+    .into_iter()
+    .filter(|method| routes.iter().any(|route| route.http_method() == *method))
+    .map(Method::lowercase_name)
+    .collect::<Vec<&str>>();
+
+    let mut lines = vec!["use axum::{".to_string()];
+    match (any_path, any_query) {
+        (true, true) => lines.push("    extract::{Path, Query},".to_string()),
+        (true, false) => lines.push("    extract::Path,".to_string()),
+        (false, true) => lines.push("    extract::Query,".to_string()),
+        (false, false) => {}
+    }
+    if any_authed {
+        lines.push("    http::{header::AUTHORIZATION, HeaderMap},".to_string());
+    } else if any_headers {
+        lines.push("    http::HeaderMap,".to_string());
+    }
+    match methods.as_slice() {
+        [method] => lines.push(format!("    routing::{method},")),
+        _ => lines.push(format!("    routing::{{{}}},", methods.join(", "))),
+    }
+    // streams aren't sent as JSON, but their input might still be
+    if routes.iter().any(|route| !route.stream || route.has_body()) {
+        lines.push("    Json, Router,".to_string());
+    } else {
+        lines.push("    Router,".to_string());
+    }
+    lines.extend([
+        "};".to_string(),
+        "use std::{future::Future, sync::Arc};".to_string(),
+        String::new(),
+        format!("use super::{{{}}};", mods.join(", ")),
+        String::new(),
+    ]);
+
+    // the trait
+    lines.push("pub trait Handlers: Send + Sync + 'static {".to_string());
+    for (i, route) in routes.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+        if let Some(description) = &route.description {
+            lines.push(format!("    /// {description}"));
+        }
+        if let Some(message) = &route.deprecated {
+            lines.push(format!("    {}", rust_deprecated(message)));
+        }
+        lines.push(format!("    fn {}(", pascal_to_snake(&route.name)));
+        lines.push("        &self,".to_string());
+        if route.input.is_some() {
+            lines.push(format!(
+                "        input: {}::Input,",
+                rust_module_name(&route.name)
+            ));
+        }
+        if route.authed {
+            lines.push("        session_token: String,".to_string());
+        }
+        lines.push(format!(
+            "    ) -> impl Future<Output = {}> + Send;",
+            response_type(route)
+        ));
+    }
+    lines.push("}".to_string());
+    lines.push(String::new());
+
+    // the router (which still has to serve deprecated routes)
+    if routes.iter().any(|route| route.deprecated.is_some()) {
+        lines.push("#[allow(deprecated)]".to_string());
+    }
+    lines.push("pub fn router(handlers: impl Handlers) -> Router {".to_string());
+    lines.push("    let handlers = Arc::new(handlers);".to_string());
+    lines.push("    Router::new()".to_string());
+    for route in &routes {
+        for line in route_code(route).lines() {
+            lines.push(format!("        {line}"));
+        }
+    }
+    lines.push("}".to_string());
+
+    if any_authed {
+        lines.push(String::new());
+        lines.push("fn bearer_token(headers: &HeaderMap) -> Option<String> {".to_string());
+        lines.push("    headers".to_string());
+        lines.push("        .get(AUTHORIZATION)?".to_string());
+        lines.push("        .to_str()".to_string());
+        lines.push("        .ok()?".to_string());
+        lines.push("        .strip_prefix(\"Bearer \")".to_string());
+        lines.push("        .map(ToString::to_string)".to_string());
+        lines.push("}".to_string());
+    }
+
+    if any_headers {
+        lines.push(String::new());
+        lines.push(
+            "fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {"
+                .to_string(),
+        );
+        lines.push("    headers.get(name)?.to_str().ok()?.parse().ok()".to_string());
+        lines.push("}".to_string());
+    }
+
+    lines.join("\n")
+}
+
+/// The `.route(...)` call for a single route.
+#[allow(clippy::too_many_lines)]
+fn route_code(route: &FileNode) -> String {
+    let name = pascal_to_snake(&route.name);
+
+    let path_params = route.path_params();
+    let query = route.fields_in(ParamLocation::Query);
+    let headers = route.fields_in(ParamLocation::Header);
+
+    // extractors (derived from input and auth)
+    let mut params = vec![];
+    if route.authed || !headers.is_empty() {
+        params.push("headers: HeaderMap".to_string());
+    }
+    if !path_params.is_empty() {
+        params.push(format!("Path(path): Path<{name}::PathParams>"));
+    }
+    if !query.is_empty() {
+        params.push(format!("Query(query): Query<{name}::QueryParams>"));
+    }
+    // when part of the input comes from elsewhere, the body only has the rest of it
+    let body = route.body_struct();
+    if route.has_body() {
+        params.push(if body.is_some() {
+            format!("Json(body): Json<{name}::Body>")
+        } else {
+            format!("Json(input): Json<{name}::Input>")
+        });
+    }
+
+    let mut args = vec![];
+    if route.input.is_some() {
+        args.push("input");
+    }
+    if route.authed {
+        args.push("session_token");
+    }
+
+    let mut lines = vec![
+        ".route(".to_string(),
+        route.path.as_ref().map_or_else(
+            || format!("    &fen_path(\"/{}\"),", pascal_to_kebab(&route.name)),
+            |path| format!("    \"{path}\","),
+        ),
+        format!("    {}({{", route.http_method().lowercase_name()),
+        "        let handlers = Arc::clone(&handlers);".to_string(),
+        format!("        move |{}| async move {{", params.join(", ")),
+    ];
+    if route.authed {
+        lines.push(
+            "            let Some(session_token) = bearer_token(&headers) else {".to_string(),
+        );
+        lines.push(format!(
+            "                return {};",
+            early_response(route, "failure(401, \"Unauthorized\")")
+        ));
+        lines.push("            };".to_string());
+    }
+    // a missing optional header is just `None`, but a missing required one is a bad request
+    for field in &headers {
+        if matches!(field.t, Type::Optional(..)) {
+            lines.push(format!(
+                "            let {} = header(&headers, \"{}\");",
+                field.name,
+                field.header_name()
+            ));
+        } else {
+            lines.extend([
+                format!(
+                    "            let Some({}) = header(&headers, \"{}\") else {{",
+                    field.name,
+                    field.header_name()
+                ),
+                format!(
+                    "                return {};",
+                    early_response(
+                        route,
+                        &format!(
+                            "failure(400, \"Missing or invalid {} header\")",
+                            field.header_name()
+                        )
+                    )
+                ),
+                "            };".to_string(),
+            ]);
+        }
+    }
+    // otherwise the input is put together from the path, query string, headers, and body
+    if let (Some(IOType::Struct(s)), true) = (&route.input, !route.has_body() || body.is_some()) {
+        let fields = s
+            .fields
+            .iter()
+            .map(|field| match field.location {
+                Some(ParamLocation::Query) => format!("{0}: query.{0}", field.name),
+                Some(ParamLocation::Header) => field.name.clone(),
+                None if path_params.contains(&field.name.as_str()) => {
+                    format!("{0}: path.{0}", field.name)
+                }
+                None => format!("{0}: body.{0}", field.name),
+            })
+            .collect::<Vec<String>>();
+        lines.push(format!(
+            "            let input = {name}::Input {{ {} }};",
+            fields.join(", ")
+        ));
+    }
+    // handlers only see inputs that pass their constraints
+    if matches!(&route.input, Some(IOType::Struct(s)) if has_constraints(&s.fields)) {
+        lines.push("            if let Err(errors) = input.validate() {".to_string());
+        lines.push(format!(
+            "                return {};",
+            early_response(route, "invalid(&errors)")
+        ));
+        lines.push("            }".to_string());
+    }
+    // a stream is its own response, rather than JSON
+    let call = format!("handlers.{name}({}).await", args.join(", "));
+    if route.stream {
+        lines.push(format!("            {call}"));
+    } else {
+        lines.push(format!("            Json({call})"));
+    }
+    lines.push("        }".to_string());
+    lines.push("    }),".to_string());
+    lines.push(")".to_string());
+
+    lines.join("\n")
+}
+
+/// A response for the router to send before the handler runs (like a 401), built with one of
+/// `Response`'s (or `EventStream`'s) constructors.
+fn early_response(route: &FileNode, constructor: &str) -> String {
+    if route.stream {
+        format!("EventStream::{constructor}")
+    } else {
+        format!("Json(Response::{constructor})")
+    }
+}
+
+/// What a route's handler responds with (`Response<Output>`, `Response<Output, Error>` if the
+/// route declares its errors, or `EventStream<Output>` if it streams).
+fn response_type(route: &FileNode) -> String {
+    let name = rust_module_name(&route.name);
+    if route.stream {
+        return format!("EventStream<{name}::Output>");
+    }
+    let output = if route.output.is_some() {
+        format!("{name}::Output")
+    } else {
+        "()".to_string()
+    };
+    if route.error.is_some() {
+        format!("Response<{output}, {name}::Error>")
+    } else {
+        format!("Response<{output}>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rust_router_code;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_router(fen_code: &str, rust_code: &str) {
+        let mut parser = Parser::new(fen_code);
+        let ast = parser.parse().unwrap();
+        assert_eq!(rust_router_code(&[&ast]), rust_code);
+    }
+
+    #[test]
+    fn authed_get() {
+        expect_router(
+            r#"
+name: "GetTodos"
+description: "Fetches all todos"
+authed: true
+
+---
+
+@output [Todo]
+
+---
+
+Todo {
+  id: UUID
+  name: String
+  description: String?
+  due: Date?
+  is_completed: Bool
+}
+            "#
+            .trim(),
+            r#"
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::get,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{get_todos, fen_path, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    /// Fetches all todos
+    fn get_todos(
+        &self,
+        session_token: String,
+    ) -> impl Future<Output = Response<get_todos::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/get-todos"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move |headers: HeaderMap| async move {
+                    let Some(session_token) = bearer_token(&headers) else {
+                        return Json(Response::failure(401, "Unauthorized"));
+                    };
+                    Json(handlers.get_todos(session_token).await)
+                }
+            }),
+        )
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(ToString::to_string)
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn unauthed_post_without_output() {
+        expect_router(
+            r#"
+name: "SetMood"
+authed: false
+
+---
+
+@input (
+  happy
+  other(String)
+)
+            "#
+            .trim(),
+            r#"
+use axum::{
+    routing::post,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{set_mood, fen_path, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    fn set_mood(
+        &self,
+        input: set_mood::Input,
+    ) -> impl Future<Output = Response<()>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/set-mood"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<set_mood::Input>| async move {
+                    Json(handlers.set_mood(input).await)
+                }
+            }),
+        )
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn validates_input() {
+        expect_router(
+            r#"
+name: "CreateUser"
+authed: false
+
+---
+
+@input {
+  @nonEmpty
+  @pattern("^[^@]+@[^@]+$")
+  email: String
+  @maxLength(80)
+  name: String
+  @min(1) @max(100)
+  age: Int?
+}
+
+@output UUID
+            "#
+            .trim(),
+            r#"
+use axum::{
+    routing::post,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{create_user, fen_path, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    fn create_user(
+        &self,
+        input: create_user::Input,
+    ) -> impl Future<Output = Response<create_user::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/create-user"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<create_user::Input>| async move {
+                    if let Err(errors) = input.validate() {
+                        return Json(Response::invalid(&errors));
+                    }
+                    Json(handlers.create_user(input).await)
+                }
+            }),
+        )
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn deprecated_route() {
+        expect_router(
+            r#"
+name: "GetTasks"
+authed: false
+@deprecated("use ListTasks instead")
+
+---
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+use axum::{
+    routing::get,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{get_tasks, fen_path, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    #[deprecated(note = "use ListTasks instead")]
+    fn get_tasks(
+        &self,
+    ) -> impl Future<Output = Response<get_tasks::Output>> + Send;
+}
+
+#[allow(deprecated)]
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/get-tasks"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move || async move {
+                    Json(handlers.get_tasks().await)
+                }
+            }),
+        )
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_path_params() {
+        expect_router(
+            r#"
+name: "UpdateUser"
+description: "Renames a user"
+method: "PUT"
+path: "/users/{user_id}/name"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  name: String
+}
+
+@output User
+
+---
+
+User {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            r#"
+use axum::{
+    extract::Path,
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::put,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{update_user, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    /// Renames a user
+    fn update_user(
+        &self,
+        input: update_user::Input,
+        session_token: String,
+    ) -> impl Future<Output = Response<update_user::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            "/users/{user_id}/name",
+            put({
+                let handlers = Arc::clone(&handlers);
+                move |headers: HeaderMap, Path(path): Path<update_user::PathParams>, Json(body): Json<update_user::Body>| async move {
+                    let Some(session_token) = bearer_token(&headers) else {
+                        return Json(Response::failure(401, "Unauthorized"));
+                    };
+                    let input = update_user::Input { user_id: path.user_id, name: body.name };
+                    Json(handlers.update_user(input, session_token).await)
+                }
+            }),
+        )
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(ToString::to_string)
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        expect_router(
+            r#"
+name: "GetQuota"
+description: "Gets the remaining quota"
+
+---
+
+@output {
+  count: Int
+}
+
+@error {
+  reason: String
+  retry_after: Int?
+}
+            "#
+            .trim(),
+            r#"
+use axum::{
+    routing::get,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{get_quota, fen_path, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    /// Gets the remaining quota
+    fn get_quota(
+        &self,
+    ) -> impl Future<Output = Response<get_quota::Output, get_quota::Error>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/get-quota"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move || async move {
+                    Json(handlers.get_quota().await)
+                }
+            }),
+        )
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn query_and_header_params() {
+        expect_router(
+            r#"
+name: "ListPosts"
+description: "Lists a user's posts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: true
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @query tag: String?
+  @header x_client_version: String
+}
+
+@output [String]
+            "#
+            .trim(),
+            r#"
+use axum::{
+    extract::{Path, Query},
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::get,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{list_posts, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    /// Lists a user's posts
+    fn list_posts(
+        &self,
+        input: list_posts::Input,
+        session_token: String,
+    ) -> impl Future<Output = Response<list_posts::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            "/users/{user_id}/posts",
+            get({
+                let handlers = Arc::clone(&handlers);
+                move |headers: HeaderMap, Path(path): Path<list_posts::PathParams>, Query(query): Query<list_posts::QueryParams>| async move {
+                    let Some(session_token) = bearer_token(&headers) else {
+                        return Json(Response::failure(401, "Unauthorized"));
+                    };
+                    let Some(x_client_version) = header(&headers, "x-client-version") else {
+                        return Json(Response::failure(400, "Missing or invalid x-client-version header"));
+                    };
+                    let input = list_posts::Input { user_id: path.user_id, page: query.page, tag: query.tag, x_client_version };
+                    Json(handlers.list_posts(input, session_token).await)
+                }
+            }),
+        )
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(ToString::to_string)
+}
+
+fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn put_with_query_and_header_params() {
+        expect_router(
+            r#"
+name: "RenameItem"
+method: "PUT"
+path: "/items/{id}"
+
+---
+
+@input {
+  id: UUID
+  name: String
+  @query notify: Bool?
+  @header if_match: Int
+}
+
+@output String
+            "#
+            .trim(),
+            r#"
+use axum::{
+    extract::{Path, Query},
+    http::HeaderMap,
+    routing::put,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{rename_item, Response};
+
+pub trait Handlers: Send + Sync + 'static {
+    fn rename_item(
+        &self,
+        input: rename_item::Input,
+    ) -> impl Future<Output = Response<rename_item::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            "/items/{id}",
+            put({
+                let handlers = Arc::clone(&handlers);
+                move |headers: HeaderMap, Path(path): Path<rename_item::PathParams>, Query(query): Query<rename_item::QueryParams>, Json(body): Json<rename_item::Body>| async move {
+                    let Some(if_match) = header(&headers, "if-match") else {
+                        return Json(Response::failure(400, "Missing or invalid if-match header"));
+                    };
+                    let input = rename_item::Input { id: path.id, name: body.name, notify: query.notify, if_match };
+                    Json(handlers.rename_item(input).await)
+                }
+            }),
+        )
+}
+
+fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn streaming_post() {
+        expect_router(
+            r#"
+name: "ImportProgress"
+description: "Imports a file, reporting progress as it goes"
+authed: true
+
+---
+
+@input {
+  @min(1) rows: Int
+}
+
+@output stream Progress
+
+---
+
+Progress {
+  done: Int
+  total: Int
+}
+            "#
+            .trim(),
+            r#"
+use axum::{
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::post,
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{import_progress, fen_path, EventStream};
+
+pub trait Handlers: Send + Sync + 'static {
+    /// Imports a file, reporting progress as it goes
+    fn import_progress(
+        &self,
+        input: import_progress::Input,
+        session_token: String,
+    ) -> impl Future<Output = EventStream<import_progress::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/import-progress"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |headers: HeaderMap, Json(input): Json<import_progress::Input>| async move {
+                    let Some(session_token) = bearer_token(&headers) else {
+                        return EventStream::failure(401, "Unauthorized");
+                    };
+                    if let Err(errors) = input.validate() {
+                        return EventStream::invalid(&errors);
+                    }
+                    handlers.import_progress(input, session_token).await
+                }
+            }),
+        )
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(ToString::to_string)
+}
+            "#
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn streaming_get_with_query_params() {
+        expect_router(
+            r#"
+name: "LiveFeed"
+method: "GET"
+path: "/feed"
+
+---
+
+@input {
+  @query topic: String?
+}
+
+@output stream String
+            "#
+            .trim(),
+            r#"
+use axum::{
+    extract::Query,
+    routing::get,
+    Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{live_feed, EventStream};
+
+pub trait Handlers: Send + Sync + 'static {
+    fn live_feed(
+        &self,
+        input: live_feed::Input,
+    ) -> impl Future<Output = EventStream<live_feed::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            "/feed",
+            get({
+                let handlers = Arc::clone(&handlers);
+                move |Query(query): Query<live_feed::QueryParams>| async move {
+                    let input = live_feed::Input { topic: query.topic };
+                    handlers.live_feed(input).await
+                }
+            }),
+        )
+}
+            "#
+            .trim(),
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::ast::Span;
+use crate::tokens::TokenKind;
+use crate::Error;
+
+/// An error in a fen file, with where it is and (when there's an obvious fix) a hint.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub error: Error,
+    pub span: Span,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn new(error: Error, span: Span) -> Self {
+        let help = help(&error);
+        Self { error, span, help }
+    }
+
+    /// Renders the diagnostic like a compiler error: the message, the file and position, the
+    /// line it's on with carets under the problem, and the hint.
+    #[must_use]
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        let line = source.lines().nth(self.span.line - 1).unwrap_or_default();
+        let line_number = self.span.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        // keep tabs, so the carets line up with the code above them
+        let indent = line
+            .bytes()
+            .take(self.span.column - 1)
+            .map(|c| if c == b'\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let width = self
+            .span
+            .end
+            .min(self.span.start - (self.span.column - 1) + line.len())
+            .saturating_sub(self.span.start)
+            .max(1);
+
+        let mut lines = vec![
+            self.error.to_string(),
+            format!(
+                "{gutter}--> {file_name}:{}:{}",
+                self.span.line, self.span.column
+            ),
+            format!("{gutter} |"),
+            format!("{line_number} | {line}"),
+            format!("{gutter} | {indent}{}", "^".repeat(width)),
+        ];
+        if let Some(help) = &self.help {
+            lines.push(format!("{gutter} = help: {help}"));
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (at {}:{})",
+            self.error, self.span.line, self.span.column
+        )
+    }
+}
+
+fn help(error: &Error) -> Option<String> {
+    let help = match error {
+        Error::UnexpectedEOF
+        | Error::Expected {
+            got: TokenKind::Eof,
+            ..
+        }
+        | Error::WrongToken {
+            got: TokenKind::Eof,
+            ..
+        } => "the file ends in the middle of a definition; is a `}` or `)` missing?",
+        Error::WrongToken {
+            expected: TokenKind::Colon,
+            ..
+        } => "names are separated from their types (or values) by a colon, like `id: UUID`",
+        Error::Expected {
+            expected: "a type", ..
+        } => "a type is a primitive (like `String`), an array (`[T]`), a map (`{String: T}`), or a type defined in this file",
+        Error::Message(message) if message.starts_with("Reference to undefined type") => {
+            "define it after the second `---`, or (if it's in shared.fen) add it to `imports`"
+        }
+        Error::Message(message) if message == "Route must have input, output, or both" => {
+            "add an `@input` or an `@output` after the first `---`"
+        }
+        Error::FromLexer(lex_err) if lex_err.message.starts_with("Forbidden character") => {
+            "that character can only be used in strings and comments"
+        }
+        _ => return None,
+    };
+
+    Some(help.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn expect_rendered(fen_code: &str, rendered: &str) {
+        let diagnostic = Parser::new(fen_code).parse().unwrap_err();
+        assert_eq!(diagnostic.render("fen/get_user.fen", fen_code), rendered);
+    }
+
+    #[test]
+    fn renders_the_line_with_carets() {
+        expect_rendered(
+            r#"
+name: "GetUser"
+authed: false
+
+---
+
+@input {
+  id UUID
+}
+            "#
+            .trim(),
+            r"
+Expected `:`, got `UUID`
+ --> fen/get_user.fen:7:6
+  |
+7 |   id UUID
+  |      ^^^^
+  = help: names are separated from their types (or values) by a colon, like `id: UUID`
+            "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn points_at_semantic_errors() {
+        expect_rendered(
+            r#"
+name: "GetUser"
+authed: false
+
+---
+
+@input {
+  id: UUID
+}
+
+@output {
+  name: String
+  friend: Friend
+}
+            "#
+            .trim(),
+            r"
+Reference to undefined type: Friend
+  --> fen/get_user.fen:12:11
+   |
+12 |   friend: Friend
+   |           ^^^^^^
+   = help: define it after the second `---`, or (if it's in shared.fen) add it to `imports`
+            "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn points_at_fields_checked_after_parsing() {
+        expect_rendered(
+            r#"
+name: "GetUser"
+authed: false
+
+---
+
+@output {
+  @nonEmpty
+  name: String
+}
+            "#
+            .trim(),
+            r"
+name can't have constraints (only the fields of a route's input can)
+ --> fen/get_user.fen:8:3
+  |
+8 |   name: String
+  |   ^^^^
+            "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn points_past_the_end_of_a_truncated_file() {
+        expect_rendered(
+            "name: \"GetUser\"\nauthed: false\n\n---\n\n@input {\n  id: UUID",
+            r"
+Expected `}`, got the end of the file
+ --> fen/get_user.fen:7:11
+  |
+7 |   id: UUID
+  |           ^
+  = help: the file ends in the middle of a definition; is a `}` or `)` missing?
+            "
+            .trim(),
+        );
+    }
+
+    #[test]
+    fn points_at_lexer_errors() {
+        expect_rendered(
+            "name: \"GetUser\"\nauthed: false\n\n---\n\n@output [String]!",
+            r"
+Forbidden character '!'
+ --> fen/get_user.fen:6:17
+  |
+6 | @output [String]!
+  |                 ^
+  = help: that character can only be used in strings and comments
+            "
+            .trim(),
+        );
+    }
+}
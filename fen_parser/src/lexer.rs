@@ -1,3 +1,4 @@
+use crate::ast::Span;
 use crate::tokens::{Token, TokenKind};
 
 #[derive(PartialEq, Eq, Debug)]
@@ -26,16 +27,29 @@ pub struct Lexer {
     pos: usize,
     has_errored: bool,
     peek_token: Option<Token>,
+    /// Where each line starts (as a byte offset), for turning offsets into lines and columns.
+    line_starts: Vec<usize>,
+    last_span: Span,
 }
 
 impl Lexer {
     pub fn new(file: impl Into<String>) -> Self {
         let file: String = file.into();
+        let line_starts = std::iter::once(0)
+            .chain(file.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
         Self {
             chars: file.as_bytes().to_vec(),
             pos: 0,
             has_errored: false,
             peek_token: None,
+            line_starts,
+            // nothing's been read yet, so errors point at the start of the file
+            last_span: Span {
+                line: 1,
+                column: 1,
+                ..Span::default()
+            },
         }
     }
 
@@ -43,10 +57,30 @@ impl Lexer {
         self.pos = 0;
         self.has_errored = false;
         self.peek_token = None;
+        self.last_span = self.span(0, 0);
+    }
+
+    /// Moves back (or forward) to byte `pos`, forgetting any peeked token.
+    pub(crate) fn rewind(&mut self, pos: usize) {
+        self.pos = pos;
+        self.peek_token = None;
+    }
+
+    /// Where the next token starts (or, if it hasn't been read yet, where reading will resume).
+    pub(crate) fn position(&self) -> usize {
+        self.peek_token
+            .as_ref()
+            .map_or(self.pos, |token| token.span.start)
+    }
+
+    /// Lets the lexer carry on after an error (past the character it tripped on).
+    pub(crate) fn clear_error(&mut self) {
+        self.has_errored = false;
+        self.peek_token = None;
     }
 
     fn skip_whitespace(&mut self) {
-        while self.peek_char().map_or(false, u8::is_ascii_whitespace) {
+        while self.peek_char().is_some_and(u8::is_ascii_whitespace) {
             self.pos += 1;
         }
     }
@@ -54,16 +88,16 @@ impl Lexer {
     fn skip_extras(&mut self) -> Result<(), Error> {
         self.skip_whitespace();
 
-        // skip over comments
-        if self.peek_char().map_or(false, |c| c == &b'/') {
+        // skip over comments (doc comments are tokens of their own)
+        if self.peek_char() == Some(&b'/') && !self.at_doc_comment() {
             self.next_char();
-            if self.peek_char().map_or(false, |c| c == &b'/') {
-                while self.peek_char().map_or(false, |c| c != &b'\n') {
+            if self.peek_char() == Some(&b'/') {
+                while self.peek_char().is_some_and(|c| c != &b'\n') {
                     self.pos += 1;
                 }
                 self.skip_whitespace();
                 // recurse to skip any other comments:
-                if self.peek_char().map_or(false, |c| c == &b'/') {
+                if self.peek_char() == Some(&b'/') {
                     return self.skip_extras();
                 }
             } else {
@@ -74,6 +108,12 @@ impl Lexer {
         Ok(())
     }
 
+    /// Whether a `///` doc comment starts here (`////` is just a comment, like in Rust).
+    fn at_doc_comment(&self) -> bool {
+        let rest = &self.chars[self.pos.min(self.chars.len())..];
+        rest.starts_with(b"///") && !rest.starts_with(b"////")
+    }
+
     pub(crate) fn peek_tok(&mut self) -> Result<Option<&Token>, Error> {
         if self.peek_token.is_none() {
             self.peek_token = self.next_tok()?;
@@ -81,74 +121,120 @@ impl Lexer {
         Ok(self.peek_token.as_ref())
     }
 
+    /// Looks at the token after the peeked one, without consuming either.
+    pub(crate) fn peek_second_tok(&mut self) -> Result<Option<Token>, Error> {
+        let (pos, has_errored, peek_token, last_span) = (
+            self.pos,
+            self.has_errored,
+            self.peek_token.clone(),
+            self.last_span,
+        );
+        self.peek_tok()?;
+        self.peek_token = None;
+        let second = self.next_tok();
+        (self.pos, self.has_errored, self.peek_token, self.last_span) =
+            (pos, has_errored, peek_token, last_span);
+        second
+    }
+
     pub(crate) fn next_tok(&mut self) -> Result<Option<Token>, Error> {
         if let Some(peeked) = self.peek_token.take() {
+            self.last_span = peeked.span;
             return Ok(Some(peeked));
         }
 
         self.skip_extras()?;
 
-        let mut return_val: Result<Option<Token>, Error> = Ok(None);
-
         if self.has_errored {
-            return return_val;
+            return Ok(None);
         }
 
-        if let Some(c) = self.next_char() {
-            match c {
-                // single char tokens
-                b'{' => return_val = Ok(Some(Token::new(TokenKind::LeftBrace, self.pos - 1))),
-                b'}' => return_val = Ok(Some(Token::new(TokenKind::RightBrace, self.pos - 1))),
-                b'(' => return_val = Ok(Some(Token::new(TokenKind::LeftParen, self.pos - 1))),
-                b')' => return_val = Ok(Some(Token::new(TokenKind::RightParen, self.pos - 1))),
-                b'[' => return_val = Ok(Some(Token::new(TokenKind::LeftBracket, self.pos - 1))),
-                b']' => return_val = Ok(Some(Token::new(TokenKind::RightBracket, self.pos - 1))),
-                b'@' => return_val = Ok(Some(Token::new(TokenKind::At, self.pos - 1))),
-                b':' => return_val = Ok(Some(Token::new(TokenKind::Colon, self.pos - 1))),
-                b'?' => return_val = Ok(Some(Token::new(TokenKind::QuestionMark, self.pos - 1))),
-
-                // string literals
-                b'"' => {
-                    return_val = Ok(Some(Token::new(
-                        TokenKind::StringLiteral(self.parse_string_literal()?),
-                        self.pos - 1,
-                    )));
-                }
-
-                // illegal chars
-                b'%' => return_val = Err(Error::new("Forbidden character '%'", self.pos - 1)),
-                b'!' => return_val = Err(Error::new("Forbidden character '!'", self.pos - 1)),
-                b'&' => return_val = Err(Error::new("Forbidden character '&'", self.pos - 1)),
-                b'*' => return_val = Err(Error::new("Forbidden character '*'", self.pos - 1)),
-                b'+' => return_val = Err(Error::new("Forbidden character '+'", self.pos - 1)),
-                b'/' => return_val = Err(Error::new("Forbidden character '/'", self.pos - 1)),
-                b'<' => return_val = Err(Error::new("Forbidden character '<'", self.pos - 1)),
-                b'>' => return_val = Err(Error::new("Forbidden character '>'", self.pos - 1)),
-                b'=' => return_val = Err(Error::new("Forbidden character '='", self.pos - 1)),
-                b'.' => return_val = Err(Error::new("Forbidden character '.'", self.pos - 1)),
-                b';' => return_val = Err(Error::new("Forbidden character ';'", self.pos - 1)),
-                b'\'' => return_val = Err(Error::new("Forbidden character '''", self.pos - 1)),
-                b'\\' => return_val = Err(Error::new("Forbidden character '\\'", self.pos - 1)),
-                b'`' => return_val = Err(Error::new("Forbidden character '`'", self.pos - 1)),
-                b'~' => return_val = Err(Error::new("Forbidden character '~'", self.pos - 1)),
-                b'|' => return_val = Err(Error::new("Forbidden character '|'", self.pos - 1)),
-                b'^' => return_val = Err(Error::new("Forbidden character '^'", self.pos - 1)),
-
-                // multichar tokens
-                _ => {
-                    return_val = Ok(Some(Token {
-                        kind: self.parse_multichar_token()?,
-                        index: 0,
-                    }));
-                }
+        // a `-` only starts a number (rather than a rule) when a digit follows it
+        let starts_number = self.peek_char().is_some_and(u8::is_ascii_digit)
+            || (self.peek_char() == Some(&b'-')
+                && self.chars.get(self.pos + 1).is_some_and(u8::is_ascii_digit));
+
+        let starts_doc_comment = self.at_doc_comment();
+
+        let start = self.pos;
+        let Some(c) = self.next_char() else {
+            return Ok(None);
+        };
+        let kind = match c {
+            // single char tokens
+            b'{' => Ok(TokenKind::LeftBrace),
+            b'}' => Ok(TokenKind::RightBrace),
+            b'(' => Ok(TokenKind::LeftParen),
+            b')' => Ok(TokenKind::RightParen),
+            b'[' => Ok(TokenKind::LeftBracket),
+            b']' => Ok(TokenKind::RightBracket),
+            b'<' => Ok(TokenKind::LeftAngle),
+            b'>' => Ok(TokenKind::RightAngle),
+            b'@' => Ok(TokenKind::At),
+            b':' => Ok(TokenKind::Colon),
+            b'?' => Ok(TokenKind::QuestionMark),
+            b'=' => Ok(TokenKind::Equals),
+
+            // string literals
+            b'"' => self.parse_string_literal().map(TokenKind::StringLiteral),
+
+            // doc comments
+            b'/' if starts_doc_comment => Ok(TokenKind::DocComment(self.parse_doc_comment())),
+
+            // illegal chars
+            b'%' | b'!' | b'&' | b'*' | b'+' | b'/' | b'.' | b';' | b'\'' | b'\\' | b'`' | b'~'
+            | b'|' | b'^' => Err(Error::new(
+                format!("Forbidden character '{}'", char::from(*c)),
+                start,
+            )),
+
+            // number literals
+            _ if starts_number => self.parse_number_literal().map(TokenKind::NumberLiteral),
+
+            // multichar tokens
+            _ => self.parse_multichar_token(),
+        };
+
+        match kind {
+            Ok(kind) => {
+                let token = Token::new(kind, self.span(start, self.pos));
+                self.last_span = token.span;
+                Ok(Some(token))
+            }
+            Err(err) => {
+                self.has_errored = true;
+                self.last_span = self.span(err.position, err.position + 1);
+                Err(err)
             }
         }
+    }
 
-        if return_val.is_err() {
-            self.has_errored = true;
+    /// The span from byte `start` up to (but not including) byte `end`.
+    pub(crate) fn span(&self, start: usize, end: usize) -> Span {
+        let line = self
+            .line_starts
+            .partition_point(|line_start| *line_start <= start);
+        Span {
+            start,
+            end,
+            line,
+            column: start - self.line_starts[line - 1] + 1,
         }
+    }
 
-        return_val
+    /// The span of the last token handed out (peeked or not), or of the last error.
+    pub(crate) const fn last_span(&self) -> Span {
+        self.last_span
+    }
+
+    /// An empty span just past the last (non-whitespace) character of the file.
+    pub(crate) fn end_span(&self) -> Span {
+        let end = self
+            .chars
+            .iter()
+            .rposition(|c| !c.is_ascii_whitespace())
+            .map_or(0, |i| i + 1);
+        self.span(end, end)
     }
 
     fn next_char(&mut self) -> Option<&u8> {
@@ -169,7 +255,7 @@ impl Lexer {
     fn parse_string_literal(&mut self) -> Result<String, Error> {
         let initial_pos = self.pos - 1;
 
-        while self.peek_char().map_or(false, |c| *c != b'"') {
+        while self.peek_char().is_some_and(|c| *c != b'"') {
             self.pos += 1;
         }
 
@@ -178,19 +264,76 @@ impl Lexer {
 
         let slice = &self.chars[initial_pos + 1..self.pos - 1]; // exclude the quotes
 
-        std::str::from_utf8(slice).map_or(
-            Err(Error {
-                message: "Invalid UTF8 encoding".to_string(),
-                position: initial_pos,
-            }),
+        std::str::from_utf8(slice).map_or_else(
+            |_| {
+                Err(Error {
+                    message: "Invalid UTF8 encoding".to_string(),
+                    position: initial_pos,
+                })
+            },
             |s| Ok(s.to_string()),
         )
     }
 
+    /// Reads consecutive `///` lines into one comment, without the slashes (or the space
+    /// after them).
+    fn parse_doc_comment(&mut self) -> String {
+        self.pos -= 1;
+
+        let mut lines = vec![];
+        while self.at_doc_comment() {
+            self.pos += 3;
+            let start = self.pos;
+            while self.peek_char().is_some_and(|c| c != &b'\n') {
+                self.pos += 1;
+            }
+            let line = String::from_utf8_lossy(&self.chars[start..self.pos]);
+            lines.push(
+                line.strip_prefix(' ')
+                    .unwrap_or(&line)
+                    .trim_end()
+                    .to_string(),
+            );
+            self.skip_whitespace();
+        }
+
+        lines.join("\n")
+    }
+
+    fn parse_number_literal(&mut self) -> Result<String, Error> {
+        let initial_pos = self.pos - 1;
+
+        while self
+            .peek_char()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'.' || *c == b'_')
+        {
+            self.pos += 1;
+        }
+
+        // only plain decimal numbers (like `20` or `-1.5`) are allowed
+        let literal = String::from_utf8_lossy(&self.chars[initial_pos..self.pos]).to_string();
+        let digits = literal.strip_prefix('-').unwrap_or(&literal);
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, "0"));
+        if whole.is_empty()
+            || fraction.is_empty()
+            || !whole
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(Error::new(
+                format!("Invalid number literal '{literal}'"),
+                initial_pos,
+            ));
+        }
+
+        Ok(literal)
+    }
+
     fn parse_multichar_token(&mut self) -> Result<TokenKind, Error> {
         let initial_pos = self.pos - 1;
 
-        while self.peek_char().map_or(false, |c| match c {
+        while self.peek_char().is_some_and(|c| match c {
             b'{' | b'}' | b'(' | b')' | b'[' | b']' | b',' | b':' | b'?' | b'%' | b'@' | b'!'
             | b'&' | b'*' | b'+' | b'/' | b'<' | b'>' | b'=' | b'.' | b';' | b'"' | b'\''
             | b'\\' | b'`' | b'~' | b'|' | b'^' => false,
@@ -203,19 +346,26 @@ impl Lexer {
 
         match slice {
             b"Int" => Ok(TokenKind::Int),
+            b"Int32" => Ok(TokenKind::Int32),
+            b"Int64" => Ok(TokenKind::Int64),
+            b"UInt" => Ok(TokenKind::UInt),
             b"Float" => Ok(TokenKind::Float),
+            b"Decimal" => Ok(TokenKind::Decimal),
             b"Date" => Ok(TokenKind::Date),
             b"UUID" => Ok(TokenKind::Uuid),
             b"String" => Ok(TokenKind::String),
             b"Bool" => Ok(TokenKind::Bool),
+            b"Bytes" => Ok(TokenKind::Bytes),
             b"true" => Ok(TokenKind::BoolLiteral(true)),
             b"false" => Ok(TokenKind::BoolLiteral(false)),
             b"---" => Ok(TokenKind::Rule),
-            _ => std::str::from_utf8(slice).map_or(
-                Err(Error {
-                    message: "Invalid UTF8 encoding".to_string(),
-                    position: initial_pos,
-                }),
+            _ => std::str::from_utf8(slice).map_or_else(
+                |_| {
+                    Err(Error {
+                        message: "Invalid UTF8 encoding".to_string(),
+                        position: initial_pos,
+                    })
+                },
                 |s| Ok(TokenKind::Identifier(s.to_string())),
             ),
         }
@@ -253,6 +403,9 @@ mod tests {
         expect_tokens(":", &[TokenKind::Colon]);
         expect_tokens("?", &[TokenKind::QuestionMark]);
         expect_tokens("@", &[TokenKind::At]);
+        expect_tokens("<", &[TokenKind::LeftAngle]);
+        expect_tokens(">", &[TokenKind::RightAngle]);
+        expect_tokens("=", &[TokenKind::Equals]);
 
         expect_tokens("{ }", &[TokenKind::LeftBrace, TokenKind::RightBrace]);
 
@@ -283,6 +436,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn numeric_types() {
+        expect_tokens(
+            "Int Int32 Int64 UInt Float Decimal Int8",
+            &[
+                TokenKind::Int,
+                TokenKind::Int32,
+                TokenKind::Int64,
+                TokenKind::UInt,
+                TokenKind::Float,
+                TokenKind::Decimal,
+                TokenKind::Identifier("Int8".to_string()),
+            ],
+        );
+    }
+
+    #[test]
+    fn number_literals() {
+        expect_tokens(
+            "20 -1 3.25 --- limit = 0",
+            &[
+                TokenKind::NumberLiteral("20".to_string()),
+                TokenKind::NumberLiteral("-1".to_string()),
+                TokenKind::NumberLiteral("3.25".to_string()),
+                TokenKind::Rule,
+                TokenKind::Identifier("limit".to_string()),
+                TokenKind::Equals,
+                TokenKind::NumberLiteral("0".to_string()),
+            ],
+        );
+
+        expect_error("1.", Error::new("Invalid number literal '1.'", 0));
+        expect_error("1.2.3", Error::new("Invalid number literal '1.2.3'", 0));
+        expect_error("20px", Error::new("Invalid number literal '20px'", 0));
+    }
+
+    #[test]
+    fn token_spans() {
+        let mut lexer = Lexer::new("name: \"Foo\"\n  limit = -20");
+        let mut spans = vec![];
+        while let Ok(Some(token)) = lexer.next_tok() {
+            spans.push((
+                token.span.start,
+                token.span.end,
+                token.span.line,
+                token.span.column,
+            ));
+        }
+        assert_eq!(
+            spans,
+            [
+                (0, 4, 1, 1),
+                (4, 5, 1, 5),
+                (6, 11, 1, 7),
+                (14, 19, 2, 3),
+                (20, 21, 2, 9),
+                (22, 25, 2, 11)
+            ]
+        );
+    }
+
     #[test]
     fn mid_ident_bad_char() {
         let mut lexer = Lexer::new("Foo%bar {}");
@@ -292,7 +506,12 @@ mod tests {
             lexer.next_tok(),
             Ok(Some(Token::new(
                 TokenKind::Identifier("Foo".to_string()),
-                0
+                Span {
+                    start: 0,
+                    end: 3,
+                    line: 1,
+                    column: 1,
+                }
             )))
         );
 
@@ -323,6 +542,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn doc_comments() {
+        expect_tokens(
+            r"
+            /// A user of the app.
+            ///
+            ///   Indented.
+            User
+            //// not a doc comment
+            // nor this
+            ///Tight
+            ",
+            &[
+                TokenKind::DocComment("A user of the app.\n\n  Indented.".to_string()),
+                TokenKind::Identifier("User".to_string()),
+                TokenKind::DocComment("Tight".to_string()),
+            ],
+        );
+    }
+
     #[test]
     fn all_syntax() {
         expect_tokens(
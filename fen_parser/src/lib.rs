@@ -1,11 +1,16 @@
 #![allow(dead_code)]
 
-use ast::{EnumDefinition, Field, FileNode, IOType, Primitive, StructDefinition, Type, Variant};
+use ast::{
+    Constraint, DefaultValue, EnumDefinition, Field, FileNode, IOType, MetadataSpans, Method,
+    ParamLocation, Primitive, SharedTypes, Span, StructDefinition, Type, Variant,
+};
+pub use diagnostic::Diagnostic;
 use lexer::Lexer;
 use tokens::{Token, TokenKind};
 
 pub mod ast;
 pub mod codegen;
+mod diagnostic;
 mod lexer;
 mod tokens;
 
@@ -34,14 +39,15 @@ impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             Self::Expected { expected, got } => {
-                write!(f, "Expected {expected}, got {got:?}")
+                write!(f, "Expected {expected}, got {got}")
             }
             Self::WrongToken { expected, got } => {
-                write!(f, "Expected {expected:?}, got {got:?}")
+                write!(f, "Expected {expected}, got {got}")
             }
-            Self::UnexpectedEOF => write!(f, "Unexpected EOF"),
+            Self::UnexpectedEOF => write!(f, "Unexpected end of file"),
             Self::Message(msg) => write!(f, "{msg}"),
-            Self::FromLexer(lex_err) => write!(f, "Lexer error: {lex_err}"),
+            // the position is shown by the diagnostic
+            Self::FromLexer(lex_err) => write!(f, "{}", lex_err.message),
         }
     }
 }
@@ -50,6 +56,17 @@ pub struct Parser {
     lexer: Lexer,
     ast: FileNode,
     defined_types: Vec<String>,
+    /// The generic types (local or shared), with how many type parameters each takes.
+    generic_types: Vec<(String, usize)>,
+    shared_types: Vec<String>,
+    /// The shared enums, so enum defaults can be checked against their variants.
+    shared_enums: Vec<EnumDefinition>,
+    helper_rules: usize,
+    /// Whether errors are collected (skipping past each one) rather than returned.
+    recovering: bool,
+    diagnostics: Vec<Diagnostic>,
+    /// Where the error being returned is, when that isn't the last token read.
+    error_span: Option<Span>,
 }
 
 impl Parser {
@@ -60,20 +77,242 @@ impl Parser {
             ast: FileNode {
                 name: String::new(),
                 description: None,
+                deprecated: None,
                 authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
                 input: None,
                 output: None,
+                stream: false,
+                error: None,
                 structs: vec![],
                 enums: vec![],
             },
             defined_types: vec![],
+            generic_types: vec![],
+            shared_types: vec![],
+            shared_enums: vec![],
+            helper_rules: 2,
+            recovering: false,
+            diagnostics: vec![],
+            error_span: None,
         }
     }
 
+    /// Makes the given shared types available to `imports`.
+    #[must_use]
+    pub fn with_shared_types(mut self, shared: &SharedTypes) -> Self {
+        self.shared_types = shared
+            .structs
+            .iter()
+            .map(|s| s.name.clone())
+            .chain(shared.enums.iter().map(|e| e.name.clone()))
+            .collect();
+        self.generic_types = shared
+            .structs
+            .iter()
+            .map(|s| (s.name.clone(), s.generics.len()))
+            .chain(
+                shared
+                    .enums
+                    .iter()
+                    .map(|e| (e.name.clone(), e.generics.len())),
+            )
+            .filter(|(_, params)| *params > 0)
+            .collect();
+        self.shared_enums.clone_from(&shared.enums);
+        self
+    }
+
+    /// Parses a file of shared types (one with no metadata or io, just type definitions).
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if input is not valid.
+    pub fn parse_shared(&mut self) -> Result<SharedTypes, Diagnostic> {
+        self.helper_rules = 0;
+        self.parse_helper_types()
+            .map_err(|error| self.diagnostic(error))?;
+        if let Some(diagnostic) = self.check_types().into_iter().next() {
+            return Err(diagnostic);
+        }
+
+        Ok(SharedTypes {
+            structs: self.ast.structs.clone(),
+            enums: self.ast.enums.clone(),
+        })
+    }
+
+    /// Like [`Parser::parse_shared`], but carries on past errors, returning the types it could
+    /// parse along with every error in the file.
+    pub fn parse_shared_with_recovery(&mut self) -> (SharedTypes, Vec<Diagnostic>) {
+        self.helper_rules = 0;
+        self.recovering = true;
+        if let Err(error) = self.parse_helper_types() {
+            let diagnostic = self.diagnostic(error);
+            self.diagnostics.push(diagnostic);
+        }
+
+        let shared = SharedTypes {
+            structs: self.ast.structs.clone(),
+            enums: self.ast.enums.clone(),
+        };
+        (shared, self.take_diagnostics())
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if input is not valid.
-    pub fn parse(&mut self) -> Result<FileNode, Error> {
+    pub fn parse(&mut self) -> Result<FileNode, Diagnostic> {
+        self.parse_file().map_err(|error| self.diagnostic(error))?;
+        if let Some(diagnostic) = self.check_types().into_iter().next() {
+            return Err(diagnostic);
+        }
+
+        Ok(self.ast.clone())
+    }
+
+    /// Like [`Parser::parse`], but rather than stopping at the first error, skips to the next
+    /// field, variant, or definition and keeps going. Returns what it could parse along with
+    /// every error in the file (so they can all be shown at once).
+    pub fn parse_with_recovery(&mut self) -> (FileNode, Vec<Diagnostic>) {
+        self.recovering = true;
+        if let Err(error) = self.parse_file() {
+            let diagnostic = self.diagnostic(error);
+            self.diagnostics.push(diagnostic);
+        }
+
+        (self.ast.clone(), self.take_diagnostics())
+    }
+
+    /// The errors found by checks that need the whole file.
+    fn check_types(&self) -> Vec<Diagnostic> {
+        // enums can be defined after the structs that use them, so this waits until the end
+        let mut diagnostics = self.variant_default_errors();
+        diagnostics.extend(self.misplaced_constraint_errors());
+        diagnostics.extend(self.path_errors());
+        diagnostics.extend(self.location_errors());
+        diagnostics
+    }
+
+    /// The diagnostics collected while recovering, in order. Metadata and helper types are
+    /// read more than once, so the same error can come up twice, and a second error in the same
+    /// place is just a knock-on effect of the first.
+    fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        let mut diagnostics = std::mem::take(&mut self.diagnostics);
+        diagnostics.extend(self.check_types());
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start);
+        diagnostics.dedup_by_key(|diagnostic| diagnostic.span.start);
+        diagnostics
+    }
+
+    /// Records an error and skips past the rest of the field, variant, or definition it's in,
+    /// stopping early at a `---` or at any of `stop` (which the caller deals with). Returns the
+    /// error when not recovering.
+    ///
+    /// Skipping starts from `item_start` when given (so brackets within the item are balanced),
+    /// and otherwise from the error (so a missing `}` doesn't take what follows with it).
+    fn recover(
+        &mut self,
+        error: Error,
+        item_start: Option<usize>,
+        stop: &[TokenKind],
+    ) -> Result<(), Error> {
+        if !self.recovering {
+            return Err(error);
+        }
+
+        let diagnostic = self.diagnostic(error);
+        if let Some(start) = item_start {
+            self.lexer.clear_error();
+            self.lexer.rewind(start);
+        } else if matches!(diagnostic.error, Error::FromLexer(_)) {
+            self.lexer.clear_error();
+        } else {
+            // the parser may have read past the error
+            self.lexer.rewind(diagnostic.span.start);
+        }
+        let line = diagnostic.span.line;
+        self.diagnostics.push(diagnostic);
+
+        let mut depth = 0_usize;
+        let mut skipped_any = false;
+        loop {
+            let tok = match self.lexer.peek_tok() {
+                Ok(Some(tok)) => tok,
+                Ok(None) => break,
+                Err(error) => {
+                    let diagnostic = self.diagnostic(error.into());
+                    self.diagnostics.push(diagnostic);
+                    self.lexer.clear_error();
+                    continue;
+                }
+            };
+            let starts_item = matches!(
+                tok.kind,
+                TokenKind::Identifier(_) | TokenKind::At | TokenKind::DocComment(_)
+            );
+            if (skipped_any && tok.kind == TokenKind::Rule)
+                || (depth == 0
+                    && (stop.contains(&tok.kind)
+                        || (skipped_any && starts_item && tok.span.line > line)))
+            {
+                break;
+            }
+            match tok.kind {
+                TokenKind::LeftBrace | TokenKind::LeftParen | TokenKind::LeftBracket => depth += 1,
+                TokenKind::RightBrace | TokenKind::RightParen | TokenKind::RightBracket => {
+                    depth = depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+            let _ = self.lexer.next_tok();
+            skipped_any = true;
+        }
+
+        Ok(())
+    }
+
+    /// Points the error about to be returned at `span`, rather than the last token read.
+    const fn error_at(&mut self, span: Span, error: Error) -> Error {
+        self.error_span = Some(span);
+        error
+    }
+
+    /// Points an error at the token the parser stopped on.
+    fn diagnostic(&mut self, error: Error) -> Diagnostic {
+        let at_end = matches!(
+            error,
+            Error::UnexpectedEOF
+                | Error::Expected {
+                    got: TokenKind::Eof,
+                    ..
+                }
+                | Error::WrongToken {
+                    got: TokenKind::Eof,
+                    ..
+                }
+        );
+        let span = if let Some(span) = self.error_span.take() {
+            span
+        } else if let Error::FromLexer(lex_err) = &error {
+            self.lexer.span(lex_err.position, lex_err.position + 1)
+        } else if at_end {
+            self.lexer.end_span()
+        } else {
+            self.lexer.last_span()
+        };
+
+        Diagnostic::new(error, span)
+    }
+
+    fn parse_file(&mut self) -> Result<(), Error> {
+        // parse metadata first so imported types are registered before anything references them
+        self.parse_metadata()?;
+        self.lexer.reset();
+
         // skip to helper types so those are registered first and can be referenced in io types
         let has_helper_types = self.skip_to_helper_types()?;
 
@@ -86,37 +325,169 @@ impl Parser {
         self.lexer.reset();
 
         self.parse_metadata()?;
-        self.parse_io()?;
+        while let Err(error) = self.parse_io() {
+            self.recover(error, None, &[TokenKind::Rule, TokenKind::At])?;
+            // an error in the input shouldn't hide the output
+            if !self
+                .lexer
+                .peek_tok()?
+                .is_some_and(|tok| tok.kind == TokenKind::At)
+            {
+                break;
+            }
+        }
 
-        Ok(self.ast.clone())
+        Ok(())
     }
 
     fn skip_to_helper_types(&mut self) -> Result<bool, Error> {
         let mut rules_found = 0;
-        while rules_found < 2 {
-            let next_token = self.lexer.next_tok()?;
+        while rules_found < self.helper_rules {
+            let next_token = match self.lexer.next_tok() {
+                Ok(next_token) => next_token,
+                // the error is reported when the section it's in is parsed
+                Err(_) if self.recovering => {
+                    self.lexer.clear_error();
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
             match next_token {
                 Some(Token {
                     kind: TokenKind::Rule,
-                    index: _,
+                    span: _,
                 }) => rules_found += 1,
                 None => break,
-                _ => continue,
+                _ => {}
             }
         }
 
-        Ok(rules_found == 2)
+        Ok(rules_found == self.helper_rules)
     }
 
     fn parse_helper_types(&mut self) -> Result<(), Error> {
         // first look through and register all the types
+        while let Err(error) = self.register_helper_types() {
+            self.recover(error, None, &[])?;
+        }
+
+        // then go back and parse them
+        self.lexer.reset();
+        self.skip_to_helper_types()?;
+        let mut annotations = vec![];
+        let mut description = None;
+        loop {
+            match self.parse_helper_type(&mut annotations, &mut description) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(error) => self.recover(error, None, &[])?,
+            }
+        }
+        if description.is_some() {
+            return Err(Error::Message(
+                "Doc comments must be followed by a definition".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Parses the next helper type (or the doc comment or annotation before one), returning
+    /// `false` at the end of the file.
+    fn parse_helper_type(
+        &mut self,
+        annotations: &mut Vec<String>,
+        description: &mut Option<String>,
+    ) -> Result<bool, Error> {
+        let Some(tok) = self.lexer.next_tok()? else {
+            return Ok(false);
+        };
+        match tok.kind {
+            TokenKind::DocComment(text) => *description = Some(text),
+            TokenKind::At => {
+                let annotation = self.expect_identifier()?;
+                annotations.push(annotation);
+            }
+            TokenKind::Identifier(name) => {
+                // type parameters can only be referenced within their own definition
+                let defined_types = self.defined_types.len();
+                let result = self.parse_helper_definition(
+                    &name,
+                    tok.span,
+                    std::mem::take(annotations),
+                    description.take(),
+                );
+                self.defined_types.truncate(defined_types);
+                result?;
+            }
+            other => {
+                return Err(Error::Expected {
+                    expected: "an identifier",
+                    got: other,
+                })
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn parse_helper_definition(
+        &mut self,
+        name: &str,
+        span: Span,
+        annotations: Vec<String>,
+        description: Option<String>,
+    ) -> Result<(), Error> {
+        let generics = self.parse_type_params()?;
+        for param in &generics {
+            if self.defined_types.contains(param) {
+                return Err(Error::Message(format!(
+                    "Type parameter {param} of {name} has the same name as a type"
+                )));
+            }
+        }
+        self.defined_types.extend(generics.iter().cloned());
+
+        let next_token = self.lexer.peek_tok()?.ok_or(Error::UnexpectedEOF)?;
+        if next_token.kind == TokenKind::LeftBrace {
+            let mut struct_def = self.parse_struct_definition(name, span, generics, annotations)?;
+            struct_def.description = description;
+            self.ast.structs.push(struct_def);
+        } else if next_token.kind == TokenKind::LeftParen {
+            let mut enum_def = self.parse_enum_definition(name, span, generics, annotations)?;
+            enum_def.description = description;
+            self.ast.enums.push(enum_def);
+        } else {
+            return Err(Error::Expected {
+                expected: "a struct or enum definition",
+                got: next_token.kind.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Skims the helper types, registering their names, without parsing their bodies.
+    fn register_helper_types(&mut self) -> Result<(), Error> {
         while let Some(tok) = self.lexer.next_tok()? {
             match tok.kind {
+                TokenKind::DocComment(_) => continue,
                 TokenKind::At => {
                     self.expect_identifier()?;
                     continue;
                 }
-                TokenKind::Identifier(name) => self.defined_types.push(name),
+                TokenKind::Identifier(name) => {
+                    if self.ast.imports.contains(&name) {
+                        return Err(Error::Message(format!(
+                            "{name} is imported from shared types and also defined in this file"
+                        )));
+                    }
+                    self.defined_types.push(name.clone());
+                    let params = self.parse_type_params()?;
+                    if !params.is_empty() {
+                        self.generic_types.push((name, params.len()));
+                    }
+                }
                 _ => {
                     return Err(Error::Expected {
                         expected: "an identifier",
@@ -126,9 +497,17 @@ impl Parser {
             }
             let next_token = self.lexer.next_tok()?.ok_or(Error::UnexpectedEOF)?;
             if next_token.kind == TokenKind::LeftBrace {
+                // fields may be maps, which have braces of their own
+                let mut open_inner_sets = 0;
                 while let Some(tok) = self.lexer.next_tok()? {
+                    if tok.kind == TokenKind::LeftBrace {
+                        open_inner_sets += 1;
+                    }
                     if tok.kind == TokenKind::RightBrace {
-                        break;
+                        if open_inner_sets == 0 {
+                            break;
+                        }
+                        open_inner_sets -= 1;
                     }
                 }
             } else if next_token.kind == TokenKind::LeftParen {
@@ -146,49 +525,12 @@ impl Parser {
                 }
             } else {
                 return Err(Error::Expected {
-                    expected: "a struct or enum definition >>>>>",
+                    expected: "a struct or enum definition",
                     got: next_token.kind,
                 });
             }
         }
 
-        // then go back and parse them
-        self.lexer.reset();
-        self.skip_to_helper_types()?;
-        let annotations = &mut vec![];
-        while let Some(tok) = self.lexer.next_tok()? {
-            match tok.kind {
-                TokenKind::At => {
-                    let annotation = self.expect_identifier()?;
-                    annotations.push(annotation);
-                }
-                TokenKind::Identifier(name) => {
-                    let next_token = self.lexer.peek_tok()?.ok_or(Error::UnexpectedEOF)?;
-                    if next_token.kind == TokenKind::LeftBrace {
-                        let struct_def =
-                            self.parse_struct_definition(&name, annotations.clone())?;
-                        annotations.clear();
-                        self.ast.structs.push(struct_def);
-                    } else if next_token.kind == TokenKind::LeftParen {
-                        let enum_def = self.parse_enum_definition(&name, annotations.clone())?;
-                        annotations.clear();
-                        self.ast.enums.push(enum_def);
-                    } else {
-                        return Err(Error::Expected {
-                            expected: "a struct or enum definition",
-                            got: next_token.kind.clone(),
-                        });
-                    }
-                }
-                other => {
-                    return Err(Error::Expected {
-                        expected: "an identifier",
-                        got: other,
-                    })
-                }
-            }
-        }
-
         Ok(())
     }
 
@@ -200,104 +542,243 @@ impl Parser {
             ));
         }
 
-        let first_ident = self.expect_identifier()?;
-        match first_ident.as_str() {
-            "input" => {
-                self.ast.input = Some(self.parse_io_type("input")?);
-                let next_token = self.lexer.peek_tok()?;
-                if next_token.is_none() {
-                    return Ok(());
-                }
-                let next_token = next_token.unwrap();
-                if next_token.kind == TokenKind::At {
-                    self.expect_token(&TokenKind::At)?;
-                    let ident = self.expect_identifier()?;
-                    if ident == "output" {
-                        self.ast.output = Some(self.parse_io_type("output")?);
-                    } else {
-                        return Err(Error::Expected {
-                            expected: "output",
-                            got: TokenKind::Identifier(ident),
-                        });
-                    }
-                }
+        // any of the sections can be left out, but the rest come in this order
+        let sections = ["input", "output", "error"];
+        let expected = [
+            "input, output, or error",
+            "output or error",
+            "error",
+            "nothing (`@error` comes last)",
+        ];
+        let mut next = 0;
+        loop {
+            let ident = self.expect_identifier()?;
+            let Some(offset) = sections[next..]
+                .iter()
+                .position(|section| *section == ident)
+            else {
+                return Err(Error::Expected {
+                    expected: expected[next],
+                    got: TokenKind::Identifier(ident),
+                });
+            };
+            next += offset + 1;
+
+            if ident == "output" && self.at_stream()? {
+                self.expect_identifier()?;
+                self.ast.stream = true;
             }
-            "output" => {
-                self.ast.output = Some(self.parse_io_type("output")?);
+            let io_type = Some(self.parse_io_type(&ident)?);
+            match ident.as_str() {
+                "input" => self.ast.input = io_type,
+                "output" => self.ast.output = io_type,
+                _ => self.ast.error = io_type,
             }
-            _ => {
-                return Err(Error::Expected {
-                    expected: "input or output",
-                    got: TokenKind::Identifier(first_ident),
-                })
+
+            let at_next = self
+                .lexer
+                .peek_tok()?
+                .is_some_and(|tok| tok.kind == TokenKind::At);
+            if !at_next {
+                break;
             }
+            self.expect_token(&TokenKind::At)?;
+        }
+
+        if self.ast.input.is_none() && self.ast.output.is_none() {
+            return Err(Error::Message(
+                "Route must have input, output, or both".to_string(),
+            ));
+        }
+        if self.ast.stream && self.ast.error.is_some() {
+            return Err(Error::Message(
+                "Streaming routes can't have an @error section (send errors as events instead)"
+                    .to_string(),
+            ));
         }
 
         Ok(())
     }
 
+    /// Whether the output is `stream` followed by its type (rather than a type named `stream`).
+    fn at_stream(&mut self) -> Result<bool, Error> {
+        let at_keyword = self
+            .lexer
+            .peek_tok()?
+            .is_some_and(|tok| tok.kind == TokenKind::Identifier("stream".to_string()));
+        let type_follows = self
+            .lexer
+            .peek_second_tok()?
+            .is_some_and(|tok| !matches!(tok.kind, TokenKind::At | TokenKind::Rule));
+        Ok(at_keyword && type_follows)
+    }
+
     fn parse_metadata(&mut self) -> Result<(), Error> {
+        if let Err(error) = self.parse_name() {
+            self.recover(error, None, &[TokenKind::Rule])?;
+        }
+        loop {
+            match self.parse_metadata_entry() {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(error) => self.recover(error, None, &[TokenKind::Rule])?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn parse_name(&mut self) -> Result<(), Error> {
         self.expect_token(&TokenKind::Identifier("name".to_string()))?;
         self.expect_token(&TokenKind::Colon)?;
         self.ast.name = self.expect_string_literal()?;
+        self.ast.spans.name = self.lexer.last_span();
 
-        while let Some(tok) = self.lexer.next_tok()? {
-            match tok {
-                Token {
-                    kind: TokenKind::Identifier(name),
-                    index: _,
-                } => {
-                    if name == "description" {
-                        self.expect_token(&TokenKind::Colon)?;
-                        self.ast.description = Some(self.expect_string_literal()?);
-                    } else if name == "authed" {
-                        self.expect_token(&TokenKind::Colon)?;
-                        if let Some(Token {
-                            kind: TokenKind::BoolLiteral(value),
-                            index: _,
-                        }) = self.lexer.next_tok()?
-                        {
+        Ok(())
+    }
+
+    /// Parses the next line of metadata, returning `false` at the rule that ends it.
+    fn parse_metadata_entry(&mut self) -> Result<bool, Error> {
+        let Some(tok) = self.lexer.next_tok()? else {
+            return Ok(false);
+        };
+        match tok {
+            Token {
+                kind: TokenKind::Identifier(name),
+                span: _,
+            } => {
+                if name == "description" {
+                    self.expect_token(&TokenKind::Colon)?;
+                    self.ast.description = Some(self.expect_string_literal()?);
+                } else if name == "imports" {
+                    self.expect_token(&TokenKind::Colon)?;
+                    self.ast.imports = self.parse_imports()?;
+                } else if name == "method" {
+                    self.expect_token(&TokenKind::Colon)?;
+                    let method = self.expect_string_literal()?;
+                    self.ast.spans.method = self.lexer.last_span();
+                    self.ast.method = Some(Method::from_name(&method).ok_or_else(|| {
+                        Error::Message(format!(
+                            "Unknown method \"{method}\" (it should be GET, POST, PUT, PATCH, or DELETE)"
+                        ))
+                    })?);
+                } else if name == "path" {
+                    self.expect_token(&TokenKind::Colon)?;
+                    let path = self.expect_string_literal()?;
+                    self.ast.spans.path = self.lexer.last_span();
+                    check_path(&path)?;
+                    self.ast.path = Some(path);
+                } else if name == "authed" {
+                    self.expect_token(&TokenKind::Colon)?;
+                    match self.lexer.next_tok()?.map(|tok| tok.kind) {
+                        Some(TokenKind::BoolLiteral(value)) => {
                             self.ast.authed = value;
-                        } else {
+                            self.ast.spans.authed = self.lexer.last_span();
+                        }
+                        got => {
                             return Err(Error::Expected {
                                 expected: "a boolean literal",
-                                got: TokenKind::Eof,
-                            });
+                                got: got.unwrap_or(TokenKind::Eof),
+                            })
                         }
                     }
                 }
-                Token {
-                    kind: TokenKind::Rule,
-                    index: _,
-                } => break,
-                Token { kind, index: _ } => {
+            }
+            Token {
+                kind: TokenKind::At,
+                span: _,
+            } => self.ast.deprecated = Some(self.parse_deprecation()?),
+            Token {
+                kind: TokenKind::Rule,
+                span: _,
+            } => return Ok(false),
+            Token { kind, span: _ } => {
+                return Err(Error::Expected {
+                    expected: "an identifier or rule",
+                    got: kind,
+                });
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn parse_imports(&mut self) -> Result<Vec<String>, Error> {
+        self.expect_token(&TokenKind::LeftBracket)?;
+
+        let mut imports = vec![];
+        loop {
+            match self.lexer.next_tok()? {
+                Some(Token {
+                    kind: TokenKind::RightBracket,
+                    span: _,
+                }) => break,
+                Some(Token {
+                    kind: TokenKind::Identifier(name),
+                    span: _,
+                }) => {
+                    if !self.shared_types.contains(&name) {
+                        return Err(Error::Message(format!(
+                            "Imported type not found in shared types: {name}"
+                        )));
+                    }
+                    if !self.defined_types.contains(&name) {
+                        self.defined_types.push(name.clone());
+                    }
+                    imports.push(name);
+                }
+                Some(tok) => {
                     return Err(Error::Expected {
-                        expected: "an identifier or rule",
-                        got: kind,
-                    });
+                        expected: "an identifier",
+                        got: tok.kind,
+                    })
                 }
+                None => return Err(Error::UnexpectedEOF),
             }
         }
 
-        Ok(())
+        Ok(imports)
     }
 
     fn parse_io_type(&mut self, name: &str) -> Result<IOType, Error> {
-        let next_tok = self.lexer.peek_tok()?.ok_or(Error::UnexpectedEOF)?;
+        // the span of `input` or `output`
+        let span = self.lexer.last_span();
+        let next_tok = self.lexer.peek_tok()?.ok_or(Error::UnexpectedEOF)?.clone();
         match next_tok.kind {
-            TokenKind::LeftBrace => Ok(IOType::Struct(self.parse_struct_definition(name, vec![])?)),
-            TokenKind::LeftParen => Ok(IOType::Enum(self.parse_enum_definition(name, vec![])?)),
+            // `{String: Int}` is a map, while `{ name: String }` is an inline struct
+            TokenKind::LeftBrace if self.is_map_ahead()? => Ok(IOType::Type(self.parse_type()?)),
+            TokenKind::LeftBrace => Ok(IOType::Struct(self.parse_struct_definition(
+                name,
+                span,
+                vec![],
+                vec![],
+            )?)),
+            TokenKind::LeftParen => Ok(IOType::Enum(self.parse_enum_definition(
+                name,
+                span,
+                vec![],
+                vec![],
+            )?)),
             TokenKind::LeftBracket
             | TokenKind::Identifier(_)
             | TokenKind::Int
+            | TokenKind::Int32
+            | TokenKind::Int64
+            | TokenKind::UInt
             | TokenKind::Float
+            | TokenKind::Decimal
             | TokenKind::String
             | TokenKind::Bool
             | TokenKind::Date
-            | TokenKind::Uuid => Ok(IOType::Type(self.parse_type()?)),
+            | TokenKind::Uuid
+            | TokenKind::Bytes => {
+                let t = self.parse_type()?;
+                reject_field_only_primitives(&t)?;
+                Ok(IOType::Type(t))
+            }
             _ => Err(Error::Expected {
                 expected: "an inline struct, an inline enum, or a type",
-                got: next_tok.kind.clone(),
+                got: next_tok.kind,
             }),
         }
     }
@@ -305,159 +786,707 @@ impl Parser {
     fn parse_struct_definition(
         &mut self,
         name: &str,
+        span: Span,
+        generics: Vec<String>,
         annotations: Vec<String>,
     ) -> Result<StructDefinition, Error> {
         self.expect_token(&TokenKind::LeftBrace)?;
 
         let mut struct_def = StructDefinition {
             name: name.to_string(),
+            span,
+            description: None,
+            generics,
             fields: vec![],
             annotations,
         };
 
-        while let Some(tok) = self.lexer.peek_tok()? {
-            match tok.kind {
-                TokenKind::RightBrace => break,
-                TokenKind::Identifier(_) => struct_def.fields.push(self.parse_struct_field()?),
-                _ => {
-                    return Err(Error::Expected {
-                        expected: "an identifier",
-                        got: tok.kind.clone(),
-                    });
+        // doc comments and annotations belong to the field that follows them
+        let mut description = None;
+        let mut deprecated = None;
+        let mut constraints = vec![];
+        let mut location = None;
+        while let Some(kind) = self.lexer.peek_tok()?.map(|tok| tok.kind.clone()) {
+            let start = self.lexer.position();
+            let result = match kind {
+                TokenKind::RightBrace | TokenKind::Rule => break,
+                TokenKind::DocComment(_) => self
+                    .expect_doc_comment()
+                    .map(|text| description = Some(text)),
+                // a missing `}` shouldn't swallow the output
+                TokenKind::At if self.at_io_type()? => break,
+                TokenKind::At => {
+                    self.parse_field_annotation(&mut deprecated, &mut constraints, &mut location)
                 }
+                TokenKind::Identifier(_) => self
+                    .parse_struct_field(description.take(), std::mem::take(&mut constraints))
+                    .map(|mut field| {
+                        field.deprecated = deprecated.take();
+                        field.location = location.take();
+                        struct_def.fields.push(field);
+                    }),
+                _ => Err(Error::Expected {
+                    expected: "an identifier",
+                    got: kind,
+                }),
+            };
+            if let Err(error) = result {
+                self.recover(error, Some(start), &[TokenKind::RightBrace])?;
             }
         }
+        if !constraints.is_empty() {
+            return Err(Error::Message(
+                "Constraints must be followed by a field".to_string(),
+            ));
+        }
+        if deprecated.is_some() {
+            return Err(Error::Message(
+                "@deprecated must be followed by a field".to_string(),
+            ));
+        }
+        if let Some(location) = location {
+            return Err(Error::Message(format!(
+                "@{} must be followed by a field",
+                location.name()
+            )));
+        }
+        if description.is_some() {
+            return Err(Error::Message(
+                "Doc comments must be followed by a field".to_string(),
+            ));
+        }
         self.expect_token(&TokenKind::RightBrace)?;
 
         Ok(struct_def)
     }
 
-    fn parse_struct_field(&mut self) -> Result<Field, Error> {
-        let name = self.expect_identifier()?;
-        self.expect_token(&TokenKind::Colon)?;
-        let t = self.parse_type()?;
-
-        Ok(Field { name, t })
-    }
-
-    fn parse_enum_definition(
+    /// Parses a `@deprecated(...)`, `@query`, `@header`, or a constraint on the field that
+    /// follows.
+    fn parse_field_annotation(
         &mut self,
-        name: &str,
-        annotations: Vec<String>,
-    ) -> Result<EnumDefinition, Error> {
-        self.expect_token(&TokenKind::LeftParen)?;
-
-        let mut enum_def = EnumDefinition {
-            name: name.to_string(),
-            variants: vec![],
-            annotations,
-        };
-
-        while let Some(tok) = self.lexer.peek_tok()? {
-            match tok.kind {
-                TokenKind::RightParen => break,
-                TokenKind::Identifier(_) => enum_def.variants.push(self.parse_enum_variant()?),
-                _ => {
-                    return Err(Error::Expected {
-                        expected: "an identifier",
-                        got: tok.kind.clone(),
-                    });
-                }
+        deprecated: &mut Option<String>,
+        constraints: &mut Vec<Constraint>,
+        location: &mut Option<ParamLocation>,
+    ) -> Result<(), Error> {
+        if self.at_deprecation()? {
+            self.expect_token(&TokenKind::At)?;
+            *deprecated = Some(self.parse_deprecation()?);
+        } else if let Some(found) = self.at_location()? {
+            self.expect_token(&TokenKind::At)?;
+            self.expect_identifier()?;
+            if location.is_some() {
+                return Err(Error::Message(
+                    "A field can only be sent in one place (@query or @header)".to_string(),
+                ));
             }
+            *location = Some(found);
+        } else {
+            constraints.push(self.parse_constraint()?);
         }
-        self.expect_token(&TokenKind::RightParen)?;
 
-        Ok(enum_def)
+        Ok(())
     }
 
-    fn parse_enum_variant(&mut self) -> Result<Variant, Error> {
+    fn parse_struct_field(
+        &mut self,
+        description: Option<String>,
+        constraints: Vec<Constraint>,
+    ) -> Result<Field, Error> {
         let name = self.expect_identifier()?;
-        let next_token = self.lexer.peek_tok()?.ok_or(Error::UnexpectedEOF)?;
-        let t = if next_token.kind == TokenKind::LeftParen {
-            self.expect_token(&TokenKind::LeftParen)?;
-            let t = self.parse_type()?;
-            self.expect_token(&TokenKind::RightParen)?;
-            Some(t)
+        let span = self.lexer.last_span();
+        self.expect_token(&TokenKind::Colon)?;
+        let t = self.parse_type()?;
+        for constraint in &constraints {
+            check_constraint(&name, &t, constraint).map_err(|error| self.error_at(span, error))?;
+        }
+
+        let has_default = self
+            .lexer
+            .peek_tok()?
+            .is_some_and(|tok| tok.kind == TokenKind::Equals);
+        let default = if has_default {
+            self.expect_token(&TokenKind::Equals)?;
+            Some(self.parse_default_value(&name, &t)?)
         } else {
             None
         };
 
-        Ok(Variant { name, t })
+        Ok(Field {
+            name,
+            span,
+            description,
+            deprecated: None,
+            t,
+            default,
+            constraints,
+            location: None,
+        })
     }
 
-    fn parse_type(&mut self) -> Result<Type, Error> {
-        let first_token = self.lexer.peek_tok()?.ok_or(Error::Expected {
-            expected: "a type",
-            got: TokenKind::Eof,
-        })?;
+    fn parse_constraint(&mut self) -> Result<Constraint, Error> {
+        self.expect_token(&TokenKind::At)?;
+        let name = self.expect_identifier()?;
+        match name.as_str() {
+            "nonEmpty" => return Ok(Constraint::NonEmpty),
+            "min" | "max" | "minLength" | "maxLength" | "pattern" => {}
+            _ => return Err(Error::Message(format!("Unknown constraint @{name}"))),
+        }
 
-        let inner = match &first_token.kind {
-            TokenKind::Identifier(name) => {
-                if self.defined_types.contains(name) {
-                    let name = name.to_string();
-                    self.expect_identifier()?;
-                    Type::Named(name)
-                } else {
-                    return Err(Error::Message(format!(
-                        "Reference to undefined type: {name}"
-                    )));
-                }
-            }
-            TokenKind::Int => {
-                self.expect_token(&TokenKind::Int)?;
-                Type::Primitive(Primitive::Int)
+        self.expect_token(&TokenKind::LeftParen)?;
+        let argument = self.lexer.next_tok()?.ok_or(Error::UnexpectedEOF)?.kind;
+        self.expect_token(&TokenKind::RightParen)?;
+
+        match (name.as_str(), argument) {
+            ("min", TokenKind::NumberLiteral(n)) => Ok(Constraint::Min(n)),
+            ("max", TokenKind::NumberLiteral(n)) => Ok(Constraint::Max(n)),
+            ("minLength", TokenKind::NumberLiteral(n)) if n.parse::<usize>().is_ok() => {
+                Ok(Constraint::MinLength(n.parse().unwrap_or_default()))
+            }
+            ("maxLength", TokenKind::NumberLiteral(n)) if n.parse::<usize>().is_ok() => {
+                Ok(Constraint::MaxLength(n.parse().unwrap_or_default()))
+            }
+            ("pattern", TokenKind::StringLiteral(pattern)) => Ok(Constraint::Pattern(pattern)),
+            ("min" | "max", got) => Err(Error::Expected {
+                expected: "a number",
+                got,
+            }),
+            ("minLength" | "maxLength", got) => Err(Error::Expected {
+                expected: "a whole number",
+                got,
+            }),
+            (_, got) => Err(Error::Expected {
+                expected: "a regular expression string",
+                got,
+            }),
+        }
+    }
+
+    /// Whether an `@input`, `@output`, or `@error` is next.
+    fn at_io_type(&mut self) -> Result<bool, Error> {
+        Ok(self.lexer.peek_second_tok()?.is_some_and(|tok| {
+            matches!(&tok.kind, TokenKind::Identifier(name) if ["input", "output", "error"].contains(&name.as_str()))
+        }))
+    }
+
+    /// Whether a `@query` or `@header` is next, and which.
+    fn at_location(&mut self) -> Result<Option<ParamLocation>, Error> {
+        Ok(self
+            .lexer
+            .peek_second_tok()?
+            .and_then(|tok| match &tok.kind {
+                TokenKind::Identifier(name) if name == "query" => Some(ParamLocation::Query),
+                TokenKind::Identifier(name) if name == "header" => Some(ParamLocation::Header),
+                _ => None,
+            }))
+    }
+
+    fn at_deprecation(&mut self) -> Result<bool, Error> {
+        Ok(self
+            .lexer
+            .peek_second_tok()?
+            .is_some_and(|tok| tok.kind == TokenKind::Identifier("deprecated".to_string())))
+    }
+
+    /// Parses the rest of a `@deprecated("...")` annotation (after the `@`), returning its
+    /// message.
+    fn parse_deprecation(&mut self) -> Result<String, Error> {
+        let name = self.expect_identifier()?;
+        if name != "deprecated" {
+            return Err(Error::Message(format!("Unknown annotation @{name}")));
+        }
+        self.expect_token(&TokenKind::LeftParen)?;
+        let message = self.expect_string_literal()?;
+        self.expect_token(&TokenKind::RightParen)?;
+
+        Ok(message)
+    }
+
+    fn parse_default_value(&mut self, field: &str, t: &Type) -> Result<DefaultValue, Error> {
+        let tok = self.lexer.next_tok()?.ok_or(Error::UnexpectedEOF)?;
+        let default = match tok.kind {
+            TokenKind::BoolLiteral(value) => DefaultValue::Bool(value),
+            TokenKind::NumberLiteral(value) => DefaultValue::Number(value),
+            TokenKind::StringLiteral(value) => DefaultValue::String(value),
+            TokenKind::Identifier(variant) => DefaultValue::Variant(variant),
+            other => {
+                return Err(Error::Expected {
+                    expected: "a default value",
+                    got: other,
+                })
+            }
+        };
+        check_default(field, t, &default).map_err(|error| self.error_at(tok.span, error))?;
+
+        Ok(default)
+    }
+
+    /// Constraints are checked when a route is called, so only the fields of its input can
+    /// have them.
+    fn misplaced_constraint_errors(&self) -> Vec<Diagnostic> {
+        let output_fields = [&self.ast.output, &self.ast.error]
+            .into_iter()
+            .filter_map(|io| match io {
+                Some(IOType::Struct(s)) => Some(&s.fields),
+                _ => None,
+            })
+            .flatten();
+        self.ast
+            .structs
+            .iter()
+            .flat_map(|s| &s.fields)
+            .chain(output_fields)
+            .filter(|field| !field.constraints.is_empty())
+            .map(|field| {
+                Diagnostic::new(
+                    Error::Message(format!(
+                        "{} can't have constraints (only the fields of a route's input can)",
+                        field.name
+                    )),
+                    field.span,
+                )
+            })
+            .collect()
+    }
+
+    /// Checks that the path's parameters are fields of the input that can be put in a path, and
+    /// that GET routes (which have no body) take all of their input from the path.
+    fn path_errors(&self) -> Vec<Diagnostic> {
+        let fields = match &self.ast.input {
+            Some(IOType::Struct(s)) => s.fields.as_slice(),
+            _ => &[],
+        };
+        let params = self.ast.path_params();
+
+        let mut errors = vec![];
+        for param in &params {
+            match fields.iter().find(|field| field.name == *param) {
+                None => errors.push((
+                    format!("Path parameter {{{param}}} must be a field of the input"),
+                    self.ast.spans.path,
+                )),
+                Some(field) if !can_be_path_param(&field.t) => errors.push((
+                    format!(
+                        "{param} can't be a path parameter (only strings, numbers, bools, and UUIDs can)"
+                    ),
+                    field.span,
+                )),
+                Some(_) => {}
+            }
+        }
+
+        let has_body_fields = match &self.ast.input {
+            Some(IOType::Struct(s)) => s
+                .fields
+                .iter()
+                .any(|f| f.location.is_none() && !params.contains(&f.name.as_str())),
+            Some(IOType::Type(_) | IOType::Enum(_)) => true,
+            None => false,
+        };
+        if self.ast.http_method() == Method::Get && has_body_fields {
+            errors.push((
+                "GET requests have no body, so every field of the input must be a path parameter, or marked @query or @header"
+                    .to_string(),
+                self.ast.spans.method,
+            ));
+        }
+
+        errors
+            .into_iter()
+            .map(|(message, span)| Diagnostic::new(Error::Message(message), span))
+            .collect()
+    }
+
+    /// Checks that `@query` and `@header` are only on fields of the input that can be sent as
+    /// text, and that aren't already path parameters.
+    fn location_errors(&self) -> Vec<Diagnostic> {
+        let input_fields = match &self.ast.input {
+            Some(IOType::Struct(s)) => s.fields.as_slice(),
+            _ => &[],
+        };
+        let other_fields = [&self.ast.output, &self.ast.error]
+            .into_iter()
+            .filter_map(|io| match io {
+                Some(IOType::Struct(s)) => Some(&s.fields),
+                _ => None,
+            })
+            .flatten()
+            .chain(self.ast.structs.iter().flat_map(|s| &s.fields));
+        let params = self.ast.path_params();
+
+        let mut errors = vec![];
+        for field in other_fields {
+            if let Some(location) = field.location {
+                errors.push((
+                    format!(
+                        "{} can't be marked @{} (only the fields of a route's input can)",
+                        field.name,
+                        location.name()
+                    ),
+                    field.span,
+                ));
             }
-            TokenKind::Float => {
-                self.expect_token(&TokenKind::Float)?;
-                Type::Primitive(Primitive::Float)
+        }
+        for field in input_fields {
+            let Some(location) = field.location else {
+                continue;
+            };
+            let t = match &field.t {
+                Type::Optional(t, _) => t,
+                t => t,
+            };
+            if params.contains(&field.name.as_str()) {
+                errors.push((
+                    format!(
+                        "{} is already a path parameter, so it can't be marked @{}",
+                        field.name,
+                        location.name()
+                    ),
+                    field.span,
+                ));
+            } else if !can_be_path_param(t) {
+                errors.push((
+                    format!(
+                        "{} can't be marked @{} (only strings, numbers, bools, and UUIDs, or optionals of them, can)",
+                        field.name,
+                        location.name()
+                    ),
+                    field.span,
+                ));
             }
-            TokenKind::Date => {
-                self.expect_token(&TokenKind::Date)?;
-                Type::Primitive(Primitive::Date)
+        }
+
+        errors
+            .into_iter()
+            .map(|(message, span)| Diagnostic::new(Error::Message(message), span))
+            .collect()
+    }
+
+    /// Checks that every enum default names a variant (without a value) of the field's enum.
+    fn variant_default_errors(&self) -> Vec<Diagnostic> {
+        let io_structs = [&self.ast.input, &self.ast.output, &self.ast.error]
+            .into_iter()
+            .flatten()
+            .filter_map(|io| match io {
+                IOType::Struct(s) => Some(s),
+                IOType::Type(_) | IOType::Enum(_) => None,
+            });
+        let fields = self
+            .ast
+            .structs
+            .iter()
+            .chain(io_structs)
+            .flat_map(|s| &s.fields);
+
+        let mut diagnostics = vec![];
+        for field in fields {
+            let (Some(DefaultValue::Variant(variant)), Some(enum_name)) =
+                (&field.default, named_type(&field.t))
+            else {
+                continue;
+            };
+            let is_unit_variant = self
+                .ast
+                .enums
+                .iter()
+                .chain(&self.shared_enums)
+                .find(|e| &e.name == enum_name)
+                .is_some_and(|e| {
+                    e.variants
+                        .iter()
+                        .any(|v| &v.name == variant && v.t.is_none())
+                });
+            if !is_unit_variant {
+                diagnostics.push(Diagnostic::new(
+                    Error::Message(format!(
+                        "The default value of {} must be a variant of {enum_name} without a value",
+                        field.name
+                    )),
+                    field.span,
+                ));
             }
-            TokenKind::Uuid => {
-                self.expect_token(&TokenKind::Uuid)?;
-                Type::Primitive(Primitive::Uuid)
+        }
+
+        diagnostics
+    }
+
+    fn parse_enum_definition(
+        &mut self,
+        name: &str,
+        span: Span,
+        generics: Vec<String>,
+        annotations: Vec<String>,
+    ) -> Result<EnumDefinition, Error> {
+        self.expect_token(&TokenKind::LeftParen)?;
+
+        let mut enum_def = EnumDefinition {
+            name: name.to_string(),
+            span,
+            description: None,
+            generics,
+            variants: vec![],
+            annotations,
+        };
+
+        let mut description = None;
+        let mut deprecated = None;
+        while let Some(kind) = self.lexer.peek_tok()?.map(|tok| tok.kind.clone()) {
+            let start = self.lexer.position();
+            let result = match kind {
+                TokenKind::RightParen | TokenKind::Rule => break,
+                TokenKind::DocComment(_) => self
+                    .expect_doc_comment()
+                    .map(|text| description = Some(text)),
+                TokenKind::At if self.at_io_type()? => break,
+                TokenKind::At => self
+                    .expect_token(&TokenKind::At)
+                    .and_then(|()| self.parse_deprecation())
+                    .map(|message| deprecated = Some(message)),
+                TokenKind::Identifier(_) => {
+                    self.parse_enum_variant(description.take())
+                        .map(|mut variant| {
+                            variant.deprecated = deprecated.take();
+                            enum_def.variants.push(variant);
+                        })
+                }
+                _ => Err(Error::Expected {
+                    expected: "an identifier",
+                    got: kind,
+                }),
+            };
+            if let Err(error) = result {
+                self.recover(error, Some(start), &[TokenKind::RightParen])?;
             }
-            TokenKind::String => {
-                self.expect_token(&TokenKind::String)?;
-                Type::Primitive(Primitive::String)
+        }
+        if description.is_some() {
+            return Err(Error::Message(
+                "Doc comments must be followed by a variant".to_string(),
+            ));
+        }
+        if deprecated.is_some() {
+            return Err(Error::Message(
+                "@deprecated must be followed by a variant".to_string(),
+            ));
+        }
+        self.expect_token(&TokenKind::RightParen)?;
+
+        Ok(enum_def)
+    }
+
+    fn parse_enum_variant(&mut self, description: Option<String>) -> Result<Variant, Error> {
+        let name = self.expect_identifier()?;
+        let span = self.lexer.last_span();
+        let next_token = self.lexer.peek_tok()?.ok_or(Error::UnexpectedEOF)?;
+        let t = if next_token.kind == TokenKind::LeftParen {
+            self.expect_token(&TokenKind::LeftParen)?;
+            let t = self.parse_type()?;
+            self.expect_token(&TokenKind::RightParen)?;
+            Some(t)
+        } else {
+            None
+        };
+
+        Ok(Variant {
+            name,
+            span,
+            description,
+            deprecated: None,
+            t,
+        })
+    }
+
+    fn parse_type(&mut self) -> Result<Type, Error> {
+        let Token { kind, span: start } =
+            self.lexer.peek_tok()?.cloned().ok_or(Error::Expected {
+                expected: "a type",
+                got: TokenKind::Eof,
+            })?;
+
+        let primitive = match kind {
+            TokenKind::Int => Some(Primitive::Int),
+            TokenKind::Int32 => Some(Primitive::Int32),
+            TokenKind::Int64 => Some(Primitive::Int64),
+            TokenKind::UInt => Some(Primitive::UInt),
+            TokenKind::Float => Some(Primitive::Float),
+            TokenKind::Decimal => Some(Primitive::Decimal),
+            TokenKind::Date => Some(Primitive::Date),
+            TokenKind::Uuid => Some(Primitive::Uuid),
+            TokenKind::String => Some(Primitive::String),
+            TokenKind::Bool => Some(Primitive::Bool),
+            TokenKind::Bytes => Some(Primitive::Bytes),
+            _ => None,
+        };
+
+        let inner = match (&kind, primitive) {
+            (_, Some(primitive)) => {
+                let _ = self.lexer.next_tok()?;
+                Type::Primitive(primitive, start)
             }
-            TokenKind::Bool => {
-                self.expect_token(&TokenKind::Bool)?;
-                Type::Primitive(Primitive::Bool)
+            (TokenKind::Identifier(name), None) => {
+                if self.defined_types.contains(name) {
+                    let name = name.clone();
+                    self.expect_identifier()?;
+                    self.parse_type_args(name, start)?
+                } else {
+                    return Err(self.error_at(
+                        start,
+                        Error::Message(format!("Reference to undefined type: {name}")),
+                    ));
+                }
             }
-            TokenKind::LeftBracket => self.parse_array()?,
-            _ => {
+            (TokenKind::LeftBracket, None) => self.parse_array()?,
+            (TokenKind::LeftBrace, None) => self.parse_map()?,
+            (kind, None) => {
                 return Err(Error::Expected {
                     expected: "a type",
-                    got: first_token.kind.clone(),
+                    got: kind.clone(),
                 })
             }
         };
 
         // check to see if it's optional (by looking for '?')
         let next_token = self.lexer.peek_tok()?;
-        let mut is_optional: bool = false;
-
-        if let Some(token) = next_token {
-            is_optional = token.kind == TokenKind::QuestionMark;
-        }
+        let is_optional = next_token.is_some_and(|token| token.kind == TokenKind::QuestionMark);
 
         if is_optional {
             self.expect_token(&TokenKind::QuestionMark)?;
-            Ok(Type::Optional(Box::new(inner)))
+            let span = self.span_from(start);
+            Ok(Type::Optional(Box::new(inner), span))
         } else {
             Ok(inner)
         }
     }
 
+    /// The span from the start of `start` to the end of the last token read.
+    const fn span_from(&self, start: Span) -> Span {
+        Span {
+            end: self.lexer.last_span().end,
+            ..start
+        }
+    }
+
+    /// Parses the type parameters of a definition (like the `<T>` in `Page<T> { ... }`), if it
+    /// has any.
+    fn parse_type_params(&mut self) -> Result<Vec<String>, Error> {
+        let mut params = vec![];
+        if self
+            .lexer
+            .peek_tok()?
+            .is_some_and(|tok| tok.kind == TokenKind::LeftAngle)
+        {
+            self.expect_token(&TokenKind::LeftAngle)?;
+            while let Some(tok) = self.lexer.next_tok()? {
+                match tok.kind {
+                    TokenKind::RightAngle => break,
+                    TokenKind::Identifier(param) => params.push(param),
+                    other => {
+                        return Err(Error::Expected {
+                            expected: "a type parameter",
+                            got: other,
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Parses the type arguments of a reference to a generic type (like the `<User>` in
+    /// `Page<User>`), checking that there's one for each of its type parameters.
+    fn parse_type_args(&mut self, name: String, start: Span) -> Result<Type, Error> {
+        let params = self
+            .generic_types
+            .iter()
+            .find(|(generic, _)| *generic == name)
+            .map_or(0, |(_, params)| *params);
+
+        let mut args = vec![];
+        let span = if self
+            .lexer
+            .peek_tok()?
+            .is_some_and(|tok| tok.kind == TokenKind::LeftAngle)
+        {
+            self.expect_token(&TokenKind::LeftAngle)?;
+            while self
+                .lexer
+                .peek_tok()?
+                .is_some_and(|tok| tok.kind != TokenKind::RightAngle)
+            {
+                let arg = self.parse_type()?;
+                reject_field_only_primitives(&arg)?;
+                args.push(arg);
+            }
+            self.expect_token(&TokenKind::RightAngle)?;
+            self.span_from(start)
+        } else {
+            start
+        };
+
+        if args.len() != params {
+            return Err(self.error_at(
+                span,
+                Error::Message(format!(
+                    "{name} takes {params} type argument{}, got {}",
+                    if params == 1 { "" } else { "s" },
+                    args.len()
+                )),
+            ));
+        }
+
+        if args.is_empty() {
+            Ok(Type::Named(name, span))
+        } else {
+            Ok(Type::Generic(name, args, span))
+        }
+    }
+
     fn parse_array(&mut self) -> Result<Type, Error> {
         self.expect_token(&TokenKind::LeftBracket)?;
-        let arr = Type::Array(Box::new(self.parse_type()?));
+        let start = self.lexer.last_span();
+        let element = self.parse_type()?;
+        reject_field_only_primitives(&element)?;
         self.expect_token(&TokenKind::RightBracket)?;
 
-        Ok(arr)
+        Ok(Type::Array(Box::new(element), self.span_from(start)))
+    }
+
+    fn parse_map(&mut self) -> Result<Type, Error> {
+        self.expect_token(&TokenKind::LeftBrace)?;
+        let start = self.lexer.last_span();
+        let key = self.parse_type()?;
+        if !matches!(
+            key,
+            Type::Primitive(Primitive::String | Primitive::Int | Primitive::Uuid, _)
+        ) {
+            return Err(self.error_at(
+                key.span(),
+                Error::Message("Map keys must be String, Int, or UUID".to_string()),
+            ));
+        }
+        self.expect_token(&TokenKind::Colon)?;
+        let value = self.parse_type()?;
+        reject_field_only_primitives(&value)?;
+        self.expect_token(&TokenKind::RightBrace)?;
+
+        Ok(Type::Map(
+            Box::new(key),
+            Box::new(value),
+            self.span_from(start),
+        ))
+    }
+
+    /// Whether the upcoming `{` opens a map (whose key is a type) rather than a struct (whose
+    /// first field name is an identifier).
+    fn is_map_ahead(&mut self) -> Result<bool, Error> {
+        Ok(self.lexer.peek_second_tok()?.is_some_and(|tok| {
+            !matches!(
+                tok.kind,
+                TokenKind::Identifier(_)
+                    | TokenKind::RightBrace
+                    | TokenKind::At
+                    | TokenKind::DocComment(_)
+            )
+        }))
     }
 
     fn expect_token(&mut self, kind: &TokenKind) -> Result<(), Error> {
@@ -478,7 +1507,7 @@ impl Parser {
         match self.lexer.next_tok()? {
             Some(Token {
                 kind: TokenKind::Identifier(name),
-                index: _,
+                span: _,
             }) => Ok(name),
             Some(tok) => Err(Error::Expected {
                 expected: "an identifier",
@@ -491,11 +1520,28 @@ impl Parser {
         }
     }
 
+    fn expect_doc_comment(&mut self) -> Result<String, Error> {
+        match self.lexer.next_tok()? {
+            Some(Token {
+                kind: TokenKind::DocComment(text),
+                span: _,
+            }) => Ok(text),
+            Some(tok) => Err(Error::Expected {
+                expected: "a doc comment",
+                got: tok.kind,
+            }),
+            None => Err(Error::Expected {
+                expected: "a doc comment",
+                got: TokenKind::Eof,
+            }),
+        }
+    }
+
     fn expect_string_literal(&mut self) -> Result<String, Error> {
         match self.lexer.next_tok()? {
             Some(Token {
                 kind: TokenKind::StringLiteral(value),
-                index: _,
+                span: _,
             }) => Ok(value),
             Some(tok) => Err(Error::Expected {
                 expected: "a string literal",
@@ -509,6 +1555,182 @@ impl Parser {
     }
 }
 
+/// Checks that a route's path starts with a `/`, only uses characters that are safe in a URL
+/// (so generators can paste it into string literals), and that each of its parameters is a whole
+/// segment (like `/users/{id}`) with a name of its own.
+fn check_path(path: &str) -> Result<(), Error> {
+    if !path.starts_with('/') {
+        return Err(Error::Message(format!(
+            "Path \"{path}\" must start with a `/`"
+        )));
+    }
+    if let Some(c) = path
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !"/{}-._~".contains(*c))
+    {
+        return Err(Error::Message(format!(
+            "Path \"{path}\" can't contain `{c}` (only letters, numbers, and `-._~`)"
+        )));
+    }
+
+    let mut params = vec![];
+    for segment in path.split('/') {
+        if !segment.contains(['{', '}']) {
+            continue;
+        }
+        let param = segment
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .filter(|param| {
+                param.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                    && param.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            })
+            .ok_or_else(|| {
+                Error::Message(format!(
+                    "Path segment \"{segment}\" isn't a parameter (those are a field's name in braces, like `{{id}}`)"
+                ))
+            })?;
+        if params.contains(&param) {
+            return Err(Error::Message(format!(
+                "Path parameter {{{param}}} is used more than once"
+            )));
+        }
+        params.push(param);
+    }
+
+    Ok(())
+}
+
+/// Path parameters are sent as plain text, so they have to be simple values.
+const fn can_be_path_param(t: &Type) -> bool {
+    matches!(
+        t,
+        Type::Primitive(
+            Primitive::Int
+                | Primitive::Int32
+                | Primitive::Int64
+                | Primitive::UInt
+                | Primitive::Float
+                | Primitive::String
+                | Primitive::Bool
+                | Primitive::Uuid,
+            _
+        )
+    )
+}
+
+/// `Bytes` (base64) and `Decimal` (a string) get special encodings in some languages (like
+/// Swift's keyed container overloads for `Decimal`), which only apply to fields and enum values,
+/// so they can't be nested in arrays, maps, or generics, or used as a whole input or output.
+fn reject_field_only_primitives(t: &Type) -> Result<(), Error> {
+    match t {
+        Type::Primitive(Primitive::Bytes, _) => Err(Error::Message(
+            "Bytes can only be used as the type of a field or enum value".to_string(),
+        )),
+        Type::Primitive(Primitive::Decimal, _) => Err(Error::Message(
+            "Decimal can only be used as the type of a field or enum value".to_string(),
+        )),
+        Type::Optional(t, _) | Type::Array(t, _) | Type::Map(_, t, _) => {
+            reject_field_only_primitives(t)
+        }
+        Type::Generic(_, args, _) => args.iter().try_for_each(reject_field_only_primitives),
+        Type::Primitive(..) | Type::Named(..) => Ok(()),
+    }
+}
+
+/// Checks that a field's default value is a literal of its type (enum defaults are checked
+/// against their enum's variants once everything is parsed).
+fn check_default(field: &str, t: &Type, default: &DefaultValue) -> Result<(), Error> {
+    let t = match t {
+        Type::Optional(t, _) => t,
+        t => t,
+    };
+    let matches = match (t, default) {
+        (Type::Primitive(Primitive::Bool, _), DefaultValue::Bool(_))
+        | (Type::Primitive(Primitive::String, _), DefaultValue::String(_))
+        | (Type::Named(..), DefaultValue::Variant(_)) => true,
+        (Type::Primitive(Primitive::Bool | Primitive::String, _) | Type::Named(..), _) => false,
+        (t, default) => {
+            let number = match default {
+                DefaultValue::Number(n) => n.as_str(),
+                _ => "",
+            };
+            let Some(fits) = number_fits(t, number) else {
+                return Err(Error::Message(format!(
+                    "{field} can't have a default value (only Bool, number, String, and enum fields can)"
+                )));
+            };
+            fits
+        }
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        Err(Error::Message(format!(
+            "The default value of {field} doesn't match its type"
+        )))
+    }
+}
+
+/// Whether `n` is a valid value of `t`, or `None` when `t` isn't a number.
+fn number_fits(t: &Type, n: &str) -> Option<bool> {
+    match t {
+        Type::Primitive(Primitive::Float, _) => Some(n.parse::<f64>().is_ok()),
+        Type::Primitive(Primitive::Int | Primitive::Int64, _) => Some(n.parse::<i64>().is_ok()),
+        Type::Primitive(Primitive::Int32, _) => Some(n.parse::<i32>().is_ok()),
+        Type::Primitive(Primitive::UInt, _) => Some(n.parse::<u64>().is_ok()),
+        _ => None,
+    }
+}
+
+/// Checks that a constraint makes sense for its field's type (optionals are only checked
+/// when they have a value).
+fn check_constraint(field: &str, t: &Type, constraint: &Constraint) -> Result<(), Error> {
+    let t = match t {
+        Type::Optional(t, _) => t,
+        t => t,
+    };
+    let allowed = match constraint {
+        Constraint::Min(n) | Constraint::Max(n) => match number_fits(t, n) {
+            Some(true) => true,
+            Some(false) => {
+                return Err(Error::Message(format!(
+                    "The @{} of {field} doesn't match its type",
+                    constraint.name()
+                )))
+            }
+            None => false,
+        },
+        Constraint::MinLength(_) | Constraint::MaxLength(_) => {
+            matches!(t, Type::Primitive(Primitive::String, _) | Type::Array(..))
+        }
+        Constraint::Pattern(_) => matches!(t, Type::Primitive(Primitive::String, _)),
+        Constraint::NonEmpty => matches!(
+            t,
+            Type::Primitive(Primitive::String, _) | Type::Array(..) | Type::Map(..)
+        ),
+    };
+
+    if allowed {
+        Ok(())
+    } else {
+        Err(Error::Message(format!(
+            "@{} can't be used on {field}",
+            constraint.name()
+        )))
+    }
+}
+
+/// The name of an enum or struct type (which may be optional).
+fn named_type(t: &Type) -> Option<&String> {
+    match t {
+        Type::Named(name, _) => Some(name),
+        Type::Optional(t, _) => named_type(t),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -519,14 +1741,48 @@ mod tests {
     fn expect_ast(file: &str, expected: FileNode) {
         let mut parser = Parser::new(file);
 
-        assert_eq!(parser.parse().unwrap(), expected);
+        // spans are checked separately, so the expected trees don't have to spell them out
+        assert_eq!(without_spans(parser.parse().unwrap()), expected);
+    }
+
+    fn without_spans(mut ast: FileNode) -> FileNode {
+        ast.spans = MetadataSpans::default();
+        let mut structs = ast.structs.iter_mut().collect::<Vec<_>>();
+        let mut enums = ast.enums.iter_mut().collect::<Vec<_>>();
+        for io in ast
+            .input
+            .iter_mut()
+            .chain(&mut ast.output)
+            .chain(&mut ast.error)
+        {
+            match io {
+                IOType::Struct(s) => structs.push(s),
+                IOType::Enum(e) => enums.push(e),
+                IOType::Type(_) => {}
+            }
+        }
+
+        for s in structs {
+            s.span = Span::default();
+            for field in &mut s.fields {
+                field.span = Span::default();
+            }
+        }
+        for e in enums {
+            e.span = Span::default();
+            for variant in &mut e.variants {
+                variant.span = Span::default();
+            }
+        }
+
+        ast
     }
 
     fn expect_error(file: &str, expected: &Error) {
         let mut parser = Parser::new(file);
 
         let err = parser.parse().unwrap_err();
-        assert_eq!(err, *expected);
+        assert_eq!(err.error, *expected);
     }
 
     #[allow(clippy::too_many_lines)]
@@ -548,9 +1804,22 @@ mod tests {
             FileNode {
                 name: "Test".to_string(),
                 description: Some("This is a test".to_string()),
+                deprecated: None,
                 authed: true,
-                input: Some(IOType::Type(Type::Primitive(ast::Primitive::Int))),
-                output: Some(IOType::Type(Type::Primitive(ast::Primitive::String))),
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Type(Type::Primitive(
+                    ast::Primitive::Int,
+                    Span::default(),
+                ))),
+                output: Some(IOType::Type(Type::Primitive(
+                    ast::Primitive::String,
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
                 structs: vec![],
                 enums: vec![],
             },
@@ -568,11 +1837,22 @@ mod tests {
             FileNode {
                 name: "Test".to_string(),
                 description: None,
+                deprecated: None,
                 authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
                 input: None,
-                output: Some(IOType::Type(Type::Optional(Box::new(Type::Array(
-                    Box::new(Type::Primitive(Primitive::String)),
-                ))))),
+                output: Some(IOType::Type(Type::Optional(
+                    Box::new(Type::Array(
+                        Box::new(Type::Primitive(Primitive::String, Span::default())),
+                        Span::default(),
+                    )),
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
                 structs: vec![],
                 enums: vec![],
             },
@@ -604,16 +1884,32 @@ mod tests {
             FileNode {
                 name: "CompleteTodo".to_string(),
                 description: None,
+                deprecated: None,
                 authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
                 input: Some(IOType::Struct(StructDefinition {
                     name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     fields: vec![Field {
                         name: "id".to_string(),
-                        t: Type::Primitive(Primitive::Uuid),
+                        span: Span::default(),
+                        description: None,
+                        deprecated: None,
+                        t: Type::Primitive(Primitive::Uuid, Span::default()),
+                        default: None,
+                        constraints: vec![],
+                        location: None,
                     }],
                     annotations: vec![],
                 })),
                 output: None,
+                stream: false,
+                error: None,
                 structs: vec![],
                 enums: vec![],
             },
@@ -639,41 +1935,73 @@ mod tests {
             FileNode {
                 name: "Test".to_string(),
                 description: None,
+                deprecated: None,
                 authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
                 input: Some(IOType::Struct(StructDefinition {
                     name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     fields: vec![
                         Field {
                             name: "username".to_string(),
-                            t: Type::Primitive(Primitive::String),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
                         },
                         Field {
                             name: "password".to_string(),
-                            t: Type::Primitive(Primitive::String),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
                         },
                     ],
                     annotations: vec![],
                 })),
                 output: Some(IOType::Enum(EnumDefinition {
                     name: "output".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     variants: vec![
                         Variant {
                             name: "foo".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
                             t: None,
                         },
                         Variant {
                             name: "bar".to_string(),
-                            t: Some(Type::Primitive(Primitive::Int)),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Some(Type::Primitive(Primitive::Int, Span::default())),
                         },
                     ],
                     annotations: vec![],
                 })),
+                stream: false,
+                error: None,
                 structs: vec![],
                 enums: vec![],
             },
         );
     }
 
+    #[allow(clippy::too_many_lines)]
     #[test]
     fn helper_types() {
         expect_ast(
@@ -706,45 +2034,94 @@ mod tests {
             FileNode {
                 name: "Login".to_string(),
                 description: Some("Login to the system".to_string()),
+                deprecated: None,
                 authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
                 input: Some(IOType::Struct(StructDefinition {
                     name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     fields: vec![
                         Field {
                             name: "username".to_string(),
-                            t: Type::Primitive(Primitive::String),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
                         },
                         Field {
                             name: "password".to_string(),
-                            t: Type::Primitive(Primitive::String),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
                         },
                     ],
                     annotations: vec![],
                 })),
-                output: Some(IOType::Type(Type::Named("Token".to_string()))),
+                output: Some(IOType::Type(Type::Named(
+                    "Token".to_string(),
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
                 structs: vec![StructDefinition {
                     name: "Token".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     fields: vec![
                         Field {
                             name: "token".to_string(),
-                            t: Type::Primitive(Primitive::String),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
                         },
                         Field {
                             name: "expiry".to_string(),
-                            t: Type::Named("Expiration".to_string()),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Named("Expiration".to_string(), Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
                         },
                     ],
                     annotations: vec![],
                 }],
                 enums: vec![EnumDefinition {
                     name: "Expiration".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     variants: vec![
                         Variant {
                             name: "standard".to_string(),
-                            t: Some(Type::Primitive(Primitive::Date)),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Some(Type::Primitive(Primitive::Date, Span::default())),
                         },
                         Variant {
                             name: "never".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
                             t: None,
                         },
                     ],
@@ -799,59 +2176,145 @@ mod tests {
             FileNode {
                 name: "GetPeopleInfo".to_string(),
                 description: Some("Get information about people".to_string()),
+                deprecated: None,
                 authed: true,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
                 input: Some(IOType::Struct(StructDefinition {
                     name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     fields: vec![Field {
                         name: "ids".to_string(),
-                        t: Type::Array(Box::new(Type::Primitive(Primitive::Uuid))),
+                        span: Span::default(),
+                        description: None,
+                        deprecated: None,
+                        t: Type::Array(
+                            Box::new(Type::Primitive(Primitive::Uuid, Span::default())),
+                            Span::default(),
+                        ),
+                        default: None,
+                        constraints: vec![],
+                        location: None,
                     }],
                     annotations: vec![],
                 })),
-                output: Some(IOType::Type(Type::Array(Box::new(Type::Named(
-                    "PersonInfo".to_string(),
-                ))))),
+                output: Some(IOType::Type(Type::Array(
+                    Box::new(Type::Named("PersonInfo".to_string(), Span::default())),
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
                 structs: vec![
                     StructDefinition {
                         name: "PersonInfo".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        generics: vec![],
                         fields: vec![
                             Field {
                                 name: "id".to_string(),
-                                t: Type::Primitive(Primitive::Uuid),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Primitive(Primitive::Uuid, Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                             Field {
                                 name: "born".to_string(),
-                                t: Type::Primitive(Primitive::Date),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Primitive(Primitive::Date, Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                             Field {
                                 name: "spouse".to_string(),
-                                t: Type::Optional(Box::new(Type::Named("PersonInfo".to_string()))),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Optional(
+                                    Box::new(Type::Named(
+                                        "PersonInfo".to_string(),
+                                        Span::default(),
+                                    )),
+                                    Span::default(),
+                                ),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                             Field {
                                 name: "children".to_string(),
-                                t: Type::Array(Box::new(Type::Named("PersonInfo".to_string()))),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Array(
+                                    Box::new(Type::Named(
+                                        "PersonInfo".to_string(),
+                                        Span::default(),
+                                    )),
+                                    Span::default(),
+                                ),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                             Field {
                                 name: "job".to_string(),
-                                t: Type::Named("Work".to_string()),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Named("Work".to_string(), Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                         ],
                         annotations: vec!["someAnnotation".to_string()],
                     },
                     StructDefinition {
                         name: "Work".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        generics: vec![],
                         fields: vec![
                             Field {
                                 name: "title".to_string(),
-                                t: Type::Primitive(Primitive::String),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Primitive(Primitive::String, Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                             Field {
                                 name: "hours".to_string(),
-                                t: Type::Primitive(Primitive::Int),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Primitive(Primitive::Int, Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                             Field {
                                 name: "place".to_string(),
-                                t: Type::Named("WorkPlace".to_string()),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Named("WorkPlace".to_string(), Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
                             },
                         ],
                         annotations: vec![],
@@ -859,17 +2322,29 @@ mod tests {
                 ],
                 enums: vec![EnumDefinition {
                     name: "WorkPlace".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
                     variants: vec![
                         Variant {
                             name: "at_home".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
                             t: None,
                         },
                         Variant {
                             name: "on_site".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
                             t: None,
                         },
                         Variant {
                             name: "hybrid".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
                             t: None,
                         },
                     ],
@@ -878,4 +2353,1647 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn maps() {
+        expect_ast(
+            r#"
+name: "GetFlags"
+authed: false
+
+---
+
+@input {
+  overrides: {UUID: [Int]}?
+}
+
+@output {String: Bool}
+            "#
+            .trim(),
+            FileNode {
+                name: "GetFlags".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Struct(StructDefinition {
+                    name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![Field {
+                        name: "overrides".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        deprecated: None,
+                        t: Type::Optional(
+                            Box::new(Type::Map(
+                                Box::new(Type::Primitive(Primitive::Uuid, Span::default())),
+                                Box::new(Type::Array(
+                                    Box::new(Type::Primitive(Primitive::Int, Span::default())),
+                                    Span::default(),
+                                )),
+                                Span::default(),
+                            )),
+                            Span::default(),
+                        ),
+                        default: None,
+                        constraints: vec![],
+                        location: None,
+                    }],
+                    annotations: vec![],
+                })),
+                output: Some(IOType::Type(Type::Map(
+                    Box::new(Type::Primitive(Primitive::String, Span::default())),
+                    Box::new(Type::Primitive(Primitive::Bool, Span::default())),
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        expect_error(
+            r#"
+name: "GetFlags"
+authed: false
+
+---
+
+@output {Date: Bool}
+            "#
+            .trim(),
+            &Error::Message("Map keys must be String, Int, or UUID".to_string()),
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        expect_ast(
+            r#"
+name: "GetAvatar"
+authed: false
+
+---
+
+@output {
+  image: Bytes
+  thumbnail: Bytes?
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "GetAvatar".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: None,
+                output: Some(IOType::Struct(StructDefinition {
+                    name: "output".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![
+                        Field {
+                            name: "image".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::Bytes, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "thumbnail".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Optional(
+                                Box::new(Type::Primitive(Primitive::Bytes, Span::default())),
+                                Span::default(),
+                            ),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                    ],
+                    annotations: vec![],
+                })),
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        for output in ["Bytes", "[Bytes]", "{String: Bytes?}"] {
+            expect_error(
+                &format!("name: \"GetAvatar\"\nauthed: false\n\n---\n\n@output {output}"),
+                &Error::Message(
+                    "Bytes can only be used as the type of a field or enum value".to_string(),
+                ),
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_positions() {
+        // fields and enum values are fine
+        let ast = Parser::new(
+            "name: \"GetPrice\"\nauthed: false\n\n---\n\n@output Price\n\n---\n\nPrice {\n  amount: Decimal\n  discount: Decimal?\n}\n\nRate (\n  fixed(Decimal)\n  none\n)",
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(
+            ast.structs[0].fields[0].t,
+            Type::Primitive(Primitive::Decimal, Span::default())
+        );
+
+        for output in ["Decimal", "Decimal?", "[Decimal]", "{String: Decimal}"] {
+            expect_error(
+                &format!("name: \"GetPrices\"\nauthed: false\n\n---\n\n@output {output}"),
+                &Error::Message(
+                    "Decimal can only be used as the type of a field or enum value".to_string(),
+                ),
+            );
+        }
+        expect_error(
+            "name: \"GetPrices\"\nauthed: false\n\n---\n\n@output Page<Decimal>\n\n---\n\nPage<T> {\n  items: [T]\n}",
+            &Error::Message(
+                "Decimal can only be used as the type of a field or enum value".to_string(),
+            ),
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn generics() {
+        expect_ast(
+            r#"
+name: "GetUsers"
+authed: false
+
+---
+
+@output Page<User>
+
+---
+
+Page<T> {
+  items: [T]
+  next: String?
+}
+
+Pair<A B> (
+  left(A)
+  right(B)
+)
+
+User {
+  id: UUID
+  names: Pair<String Int?>
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "GetUsers".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: None,
+                output: Some(IOType::Type(Type::Generic(
+                    "Page".to_string(),
+                    vec![Type::Named("User".to_string(), Span::default())],
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
+                structs: vec![
+                    StructDefinition {
+                        name: "Page".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        generics: vec!["T".to_string()],
+                        fields: vec![
+                            Field {
+                                name: "items".to_string(),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Array(
+                                    Box::new(Type::Named("T".to_string(), Span::default())),
+                                    Span::default(),
+                                ),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
+                            },
+                            Field {
+                                name: "next".to_string(),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Optional(
+                                    Box::new(Type::Primitive(Primitive::String, Span::default())),
+                                    Span::default(),
+                                ),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
+                            },
+                        ],
+                        annotations: vec![],
+                    },
+                    StructDefinition {
+                        name: "User".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        generics: vec![],
+                        fields: vec![
+                            Field {
+                                name: "id".to_string(),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Primitive(Primitive::Uuid, Span::default()),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
+                            },
+                            Field {
+                                name: "names".to_string(),
+                                span: Span::default(),
+                                description: None,
+                                deprecated: None,
+                                t: Type::Generic(
+                                    "Pair".to_string(),
+                                    vec![
+                                        Type::Primitive(Primitive::String, Span::default()),
+                                        Type::Optional(
+                                            Box::new(Type::Primitive(
+                                                Primitive::Int,
+                                                Span::default(),
+                                            )),
+                                            Span::default(),
+                                        ),
+                                    ],
+                                    Span::default(),
+                                ),
+                                default: None,
+                                constraints: vec![],
+                                location: None,
+                            },
+                        ],
+                        annotations: vec![],
+                    },
+                ],
+                enums: vec![EnumDefinition {
+                    name: "Pair".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec!["A".to_string(), "B".to_string()],
+                    variants: vec![
+                        Variant {
+                            name: "left".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Some(Type::Named("A".to_string(), Span::default())),
+                        },
+                        Variant {
+                            name: "right".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Some(Type::Named("B".to_string(), Span::default())),
+                        },
+                    ],
+                    annotations: vec![],
+                }],
+            },
+        );
+
+        for (output, error) in [
+            ("Page", "Page takes 1 type argument, got 0"),
+            ("Page<Int String>", "Page takes 1 type argument, got 2"),
+            ("User<Int>", "User takes 0 type arguments, got 1"),
+            ("T", "Reference to undefined type: T"),
+        ] {
+            expect_error(
+                &format!(
+                    "name: \"GetUsers\"\nauthed: false\n\n---\n\n@output {output}\n\n---\n\nPage<T> {{\n  items: [T]\n}}\n\nUser {{\n  id: UUID\n}}"
+                ),
+                &Error::Message(error.to_string()),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn defaults() {
+        expect_ast(
+            r#"
+name: "SearchTodos"
+authed: false
+
+---
+
+@input {
+  limit: Int = 20
+  offset: UInt = 0
+  ratio: Float? = -1.5
+  tag: String = "inbox"
+  archived: Bool = false
+  sort: SortOrder = newest
+}
+
+---
+
+SortOrder (
+  newest
+  oldest
+)
+            "#
+            .trim(),
+            FileNode {
+                name: "SearchTodos".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Struct(StructDefinition {
+                    name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![
+                        Field {
+                            name: "limit".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::Int, Span::default()),
+                            default: Some(DefaultValue::Number("20".to_string())),
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "offset".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::UInt, Span::default()),
+                            default: Some(DefaultValue::Number("0".to_string())),
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "ratio".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Optional(
+                                Box::new(Type::Primitive(Primitive::Float, Span::default())),
+                                Span::default(),
+                            ),
+                            default: Some(DefaultValue::Number("-1.5".to_string())),
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "tag".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: Some(DefaultValue::String("inbox".to_string())),
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "archived".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::Bool, Span::default()),
+                            default: Some(DefaultValue::Bool(false)),
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "sort".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Named("SortOrder".to_string(), Span::default()),
+                            default: Some(DefaultValue::Variant("newest".to_string())),
+                            constraints: vec![],
+                            location: None,
+                        },
+                    ],
+                    annotations: vec![],
+                })),
+                output: None,
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![EnumDefinition {
+                    name: "SortOrder".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    variants: vec![
+                        Variant {
+                            name: "newest".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: None,
+                        },
+                        Variant {
+                            name: "oldest".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: None,
+                        },
+                    ],
+                    annotations: vec![],
+                }],
+            },
+        );
+
+        for (field, error) in [
+            (
+                "limit: Int = 1.5",
+                "The default value of limit doesn't match its type",
+            ),
+            (
+                "limit: UInt = -1",
+                "The default value of limit doesn't match its type",
+            ),
+            (
+                "limit: Int32 = 3000000000",
+                "The default value of limit doesn't match its type",
+            ),
+            (
+                "name: String = 5",
+                "The default value of name doesn't match its type",
+            ),
+            (
+                "sort: SortOrder = \"newest\"",
+                "The default value of sort doesn't match its type",
+            ),
+            (
+                "sort: SortOrder = latest",
+                "The default value of sort must be a variant of SortOrder without a value",
+            ),
+            (
+                "sort: SortOrder = custom",
+                "The default value of sort must be a variant of SortOrder without a value",
+            ),
+            (
+                "tags: [String] = \"a\"",
+                "tags can't have a default value (only Bool, number, String, and enum fields can)",
+            ),
+        ] {
+            expect_error(
+                &format!(
+                    "name: \"SearchTodos\"\nauthed: false\n\n---\n\n@input {{\n  {field}\n}}\n\n---\n\nSortOrder (\n  newest\n  custom(String)\n)"
+                ),
+                &Error::Message(error.to_string()),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn constraints() {
+        expect_ast(
+            r#"
+name: "CreateUser"
+authed: false
+
+---
+
+@input {
+  @nonEmpty @pattern("^[a-z]+$")
+  @maxLength(80)
+  name: String
+  @min(1) @max(100)
+  age: Int?
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "CreateUser".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Struct(StructDefinition {
+                    name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![
+                        Field {
+                            name: "name".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![
+                                Constraint::NonEmpty,
+                                Constraint::Pattern("^[a-z]+$".to_string()),
+                                Constraint::MaxLength(80),
+                            ],
+                            location: None,
+                        },
+                        Field {
+                            name: "age".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Optional(
+                                Box::new(Type::Primitive(Primitive::Int, Span::default())),
+                                Span::default(),
+                            ),
+                            default: None,
+                            constraints: vec![
+                                Constraint::Min("1".to_string()),
+                                Constraint::Max("100".to_string()),
+                            ],
+                            location: None,
+                        },
+                    ],
+                    annotations: vec![],
+                })),
+                output: None,
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        for (input, error) in [
+            ("@min(1)\n  name: String", "@min can't be used on name"),
+            ("@nonEmpty\n  age: Int", "@nonEmpty can't be used on age"),
+            (
+                "@pattern(\"a\")\n  tags: [String]",
+                "@pattern can't be used on tags",
+            ),
+            (
+                "@min(-1)\n  age: UInt",
+                "The @min of age doesn't match its type",
+            ),
+            ("@between(1 2)\n  age: Int", "Unknown constraint @between"),
+            (
+                "name: String\n  @nonEmpty",
+                "Constraints must be followed by a field",
+            ),
+        ] {
+            expect_error(
+                &format!("name: \"CreateUser\"\nauthed: false\n\n---\n\n@input {{\n  {input}\n}}"),
+                &Error::Message(error.to_string()),
+            );
+        }
+
+        expect_error(
+            "name: \"CreateUser\"\nauthed: false\n\n---\n\n@input {\n  @minLength(1.5)\n  name: String\n}",
+            &Error::Expected {
+                expected: "a whole number",
+                got: TokenKind::NumberLiteral("1.5".to_string()),
+            },
+        );
+        expect_error(
+            "name: \"CreateUser\"\nauthed: false\n\n---\n\n@output {\n  @nonEmpty\n  name: String\n}",
+            &Error::Message(
+                "name can't have constraints (only the fields of a route's input can)".to_string(),
+            ),
+        );
+    }
+
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn doc_comments() {
+        expect_ast(
+            r#"
+name: "GetTasks"
+authed: false
+
+---
+
+@output [Task]
+
+---
+
+/// A single unit of work
+///
+/// Tasks belong to exactly one project.
+Task {
+  /// Shown in the task list
+  title: String
+  status: Status
+}
+
+/// Where a task is in its lifecycle
+Status (
+  todo
+  /// Being worked on by someone
+  in_progress
+)
+            "#
+            .trim(),
+            FileNode {
+                name: "GetTasks".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: None,
+                output: Some(IOType::Type(Type::Array(
+                    Box::new(Type::Named("Task".to_string(), Span::default())),
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
+                structs: vec![StructDefinition {
+                    name: "Task".to_string(),
+                    span: Span::default(),
+                    description: Some(
+                        "A single unit of work\n\nTasks belong to exactly one project.".to_string(),
+                    ),
+                    generics: vec![],
+                    fields: vec![
+                        Field {
+                            name: "title".to_string(),
+                            span: Span::default(),
+                            description: Some("Shown in the task list".to_string()),
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "status".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Named("Status".to_string(), Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                    ],
+                    annotations: vec![],
+                }],
+                enums: vec![EnumDefinition {
+                    name: "Status".to_string(),
+                    span: Span::default(),
+                    description: Some("Where a task is in its lifecycle".to_string()),
+                    generics: vec![],
+                    variants: vec![
+                        Variant {
+                            name: "todo".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: None,
+                        },
+                        Variant {
+                            name: "in_progress".to_string(),
+                            span: Span::default(),
+                            description: Some("Being worked on by someone".to_string()),
+                            deprecated: None,
+                            t: None,
+                        },
+                    ],
+                    annotations: vec![],
+                }],
+            },
+        );
+
+        for (helper_types, error) in [
+            (
+                "Task {\n  title: String\n  /// The end\n}",
+                "Doc comments must be followed by a field",
+            ),
+            (
+                "Status (\n  todo\n  /// The end\n)",
+                "Doc comments must be followed by a variant",
+            ),
+            (
+                "Task {\n  title: String\n}\n\n/// The end",
+                "Doc comments must be followed by a definition",
+            ),
+        ] {
+            expect_error(
+                &format!("name: \"GetTasks\"\nauthed: false\n\n---\n\n@output Task\n\n---\n\n{helper_types}"),
+                &Error::Message(error.to_string()),
+            );
+        }
+    }
+
+    #[test]
+    fn deprecations() {
+        expect_ast(
+            r#"
+name: "GetTasks"
+authed: false
+@deprecated("use ListTasks instead")
+
+---
+
+@input {
+  @deprecated("filter by status instead")
+  @nonEmpty
+  owner: String
+}
+
+@output Status
+
+---
+
+Status (
+  todo
+  @deprecated("use todo instead")
+  open
+)
+            "#
+            .trim(),
+            FileNode {
+                name: "GetTasks".to_string(),
+                description: None,
+                deprecated: Some("use ListTasks instead".to_string()),
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Struct(StructDefinition {
+                    name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![Field {
+                        name: "owner".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        deprecated: Some("filter by status instead".to_string()),
+                        t: Type::Primitive(Primitive::String, Span::default()),
+                        default: None,
+                        constraints: vec![Constraint::NonEmpty],
+                        location: None,
+                    }],
+                    annotations: vec![],
+                })),
+                output: Some(IOType::Type(Type::Named(
+                    "Status".to_string(),
+                    Span::default(),
+                ))),
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![EnumDefinition {
+                    name: "Status".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    variants: vec![
+                        Variant {
+                            name: "todo".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: None,
+                        },
+                        Variant {
+                            name: "open".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: Some("use todo instead".to_string()),
+                            t: None,
+                        },
+                    ],
+                    annotations: vec![],
+                }],
+            },
+        );
+
+        for (helper_types, error) in [
+            (
+                "Task {\n  title: String\n  @deprecated(\"gone\")\n}",
+                "@deprecated must be followed by a field",
+            ),
+            (
+                "Mood (\n  happy\n  @deprecated(\"gone\")\n)",
+                "@deprecated must be followed by a variant",
+            ),
+            (
+                "Mood (\n  @nonEmpty\n  happy\n)",
+                "Unknown annotation @nonEmpty",
+            ),
+        ] {
+            expect_error(
+                &format!("name: \"GetTasks\"\nauthed: false\n\n---\n\n@output Status\n\n---\n\nStatus (\n  todo\n)\n\n{helper_types}"),
+                &Error::Message(error.to_string()),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn methods_and_paths() {
+        expect_ast(
+            r#"
+name: "UpdateUser"
+method: "PUT"
+path: "/users/{id}"
+authed: true
+
+---
+
+@input {
+  id: UUID
+  name: String
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "UpdateUser".to_string(),
+                description: None,
+                deprecated: None,
+                authed: true,
+                imports: vec![],
+                method: Some(Method::Put),
+                path: Some("/users/{id}".to_string()),
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Struct(StructDefinition {
+                    name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![
+                        Field {
+                            name: "id".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::Uuid, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "name".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                    ],
+                    annotations: vec![],
+                })),
+                output: None,
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        for (metadata, input, error) in [
+            (
+                "method: \"FETCH\"",
+                "@output String",
+                "Unknown method \"FETCH\" (it should be GET, POST, PUT, PATCH, or DELETE)",
+            ),
+            (
+                "path: \"users\"",
+                "@output String",
+                "Path \"users\" must start with a `/`",
+            ),
+            (
+                "path: \"/users?all\"",
+                "@output String",
+                "Path \"/users?all\" can't contain `?` (only letters, numbers, and `-._~`)",
+            ),
+            (
+                "path: \"/users/id-{id}\"",
+                "@input {\n  id: UUID\n}",
+                "Path segment \"id-{id}\" isn't a parameter (those are a field's name in braces, like `{id}`)",
+            ),
+            (
+                "path: \"/users/{id}/{id}\"",
+                "@input {\n  id: UUID\n}",
+                "Path parameter {id} is used more than once",
+            ),
+            (
+                "path: \"/users/{id}\"",
+                "@input {\n  name: String\n}",
+                "Path parameter {id} must be a field of the input",
+            ),
+            (
+                "path: \"/users/{id}\"",
+                "@input {\n  id: UUID?\n}",
+                "id can't be a path parameter (only strings, numbers, bools, and UUIDs can)",
+            ),
+            (
+                "method: \"GET\"\npath: \"/users/{id}\"",
+                "@input {\n  id: UUID\n  name: String\n}",
+                "GET requests have no body, so every field of the input must be a path parameter, or marked @query or @header",
+            ),
+        ] {
+            expect_error(
+                &format!("name: \"GetUser\"\n{metadata}\nauthed: false\n\n---\n\n{input}"),
+                &Error::Message(error.to_string()),
+            );
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn error_types() {
+        expect_ast(
+            r#"
+name: "ClaimHandle"
+authed: true
+
+---
+
+@input String
+
+@output UUID
+
+@error (
+  not_found
+  taken(String)
+)
+            "#
+            .trim(),
+            FileNode {
+                name: "ClaimHandle".to_string(),
+                description: None,
+                deprecated: None,
+                authed: true,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Type(Type::Primitive(
+                    Primitive::String,
+                    Span::default(),
+                ))),
+                output: Some(IOType::Type(Type::Primitive(
+                    Primitive::Uuid,
+                    Span::default(),
+                ))),
+                stream: false,
+                error: Some(IOType::Enum(EnumDefinition {
+                    name: "error".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    variants: vec![
+                        Variant {
+                            name: "not_found".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: None,
+                        },
+                        Variant {
+                            name: "taken".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Some(Type::Primitive(Primitive::String, Span::default())),
+                        },
+                    ],
+                    annotations: vec![],
+                })),
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        // either input or output can be left out
+        expect_ast(
+            r#"
+name: "GetQuota"
+authed: false
+
+---
+
+@output Int
+
+@error {
+  reason: String
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "GetQuota".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: None,
+                output: Some(IOType::Type(Type::Primitive(
+                    Primitive::Int,
+                    Span::default(),
+                ))),
+                stream: false,
+                error: Some(IOType::Struct(StructDefinition {
+                    name: "error".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![Field {
+                        name: "reason".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        deprecated: None,
+                        t: Type::Primitive(Primitive::String, Span::default()),
+                        default: None,
+                        constraints: vec![],
+                        location: None,
+                    }],
+                    annotations: vec![],
+                })),
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        // but not both
+        expect_error(
+            "name: \"Test\"\nauthed: false\n\n---\n\n@error String",
+            &Error::Message("Route must have input, output, or both".to_string()),
+        );
+
+        // and the sections come in order
+        expect_error(
+            "name: \"Test\"\nauthed: false\n\n---\n\n@input String\n@error String\n@output Int",
+            &Error::Expected {
+                expected: "nothing (`@error` comes last)",
+                got: TokenKind::Identifier("output".to_string()),
+            },
+        );
+        expect_error(
+            "name: \"Test\"\nauthed: false\n\n---\n\n@output Int\n@input String",
+            &Error::Expected {
+                expected: "error",
+                got: TokenKind::Identifier("input".to_string()),
+            },
+        );
+    }
+
+    #[allow(clippy::too_many_lines)]
+    #[test]
+    fn query_and_header_params() {
+        expect_ast(
+            r#"
+name: "ListPosts"
+method: "GET"
+path: "/users/{user_id}/posts"
+authed: false
+
+---
+
+@input {
+  user_id: UUID
+  @query page: Int?
+  @header x_client_version: String
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "ListPosts".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: Some(Method::Get),
+                path: Some("/users/{user_id}/posts".to_string()),
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Struct(StructDefinition {
+                    name: "input".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![
+                        Field {
+                            name: "user_id".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::Uuid, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: None,
+                        },
+                        Field {
+                            name: "page".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Optional(
+                                Box::new(Type::Primitive(Primitive::Int, Span::default())),
+                                Span::default(),
+                            ),
+                            default: None,
+                            constraints: vec![],
+                            location: Some(ParamLocation::Query),
+                        },
+                        Field {
+                            name: "x_client_version".to_string(),
+                            span: Span::default(),
+                            description: None,
+                            deprecated: None,
+                            t: Type::Primitive(Primitive::String, Span::default()),
+                            default: None,
+                            constraints: vec![],
+                            location: Some(ParamLocation::Header),
+                        },
+                    ],
+                    annotations: vec![],
+                })),
+                output: None,
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![],
+            },
+        );
+
+        for (input, error) in [
+            (
+                "@input {\n  @query @header page: Int\n}",
+                "A field can only be sent in one place (@query or @header)",
+            ),
+            (
+                "@input {\n  page: Int\n  @query\n}",
+                "@query must be followed by a field",
+            ),
+            (
+                "@input {\n  @header id: UUID\n}",
+                "id is already a path parameter, so it can't be marked @header",
+            ),
+            (
+                "@input {\n  id: UUID\n  @query tags: [String]\n}",
+                "tags can't be marked @query (only strings, numbers, bools, and UUIDs, or optionals of them, can)",
+            ),
+            (
+                "@input {\n  id: UUID\n}\n\n@output {\n  @header etag: String\n}",
+                "etag can't be marked @header (only the fields of a route's input can)",
+            ),
+        ] {
+            expect_error(
+                &format!("name: \"GetUser\"\nmethod: \"PUT\"\npath: \"/users/{{id}}\"\nauthed: false\n\n---\n\n{input}"),
+                &Error::Message(error.to_string()),
+            );
+        }
+    }
+
+    #[test]
+    fn streaming_output() {
+        expect_ast(
+            r#"
+name: "WatchPrices"
+authed: false
+
+---
+
+@input [String]
+
+@output stream Price
+
+---
+
+Price {
+  symbol: String
+}
+            "#
+            .trim(),
+            FileNode {
+                name: "WatchPrices".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec![],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Type(Type::Array(
+                    Box::new(Type::Primitive(Primitive::String, Span::default())),
+                    Span::default(),
+                ))),
+                output: Some(IOType::Type(Type::Named(
+                    "Price".to_string(),
+                    Span::default(),
+                ))),
+                stream: true,
+                error: None,
+                structs: vec![StructDefinition {
+                    name: "Price".to_string(),
+                    span: Span::default(),
+                    description: None,
+                    generics: vec![],
+                    fields: vec![Field {
+                        name: "symbol".to_string(),
+                        span: Span::default(),
+                        description: None,
+                        deprecated: None,
+                        t: Type::Primitive(Primitive::String, Span::default()),
+                        default: None,
+                        constraints: vec![],
+                        location: None,
+                    }],
+                    annotations: vec![],
+                }],
+                enums: vec![],
+            },
+        );
+
+        // without a type after it, `stream` is just a type's name
+        let ast = Parser::new(
+            "name: \"GetStream\"\nauthed: false\n\n---\n\n@output stream\n\n---\n\nstream {\n  id: UUID\n}",
+        )
+        .parse()
+        .unwrap();
+        assert!(!ast.stream);
+        assert_eq!(
+            ast.output,
+            Some(IOType::Type(Type::Named(
+                "stream".to_string(),
+                Span::default()
+            )))
+        );
+
+        expect_error(
+            "name: \"WatchPrices\"\nauthed: false\n\n---\n\n@output stream String\n\n@error (\n  closed\n)",
+            &Error::Message(
+                "Streaming routes can't have an @error section (send errors as events instead)"
+                    .to_string(),
+            ),
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let ast = Parser::new(
+            "name: \"GetMood\"\nauthed: false\n\n---\n\n@output Mood\n\n---\n\nMood (\n  happy\n  other(String)\n)",
+        )
+        .parse()
+        .unwrap();
+
+        let mood = &ast.enums[0];
+        assert_eq!(
+            mood.span,
+            Span {
+                start: 55,
+                end: 59,
+                line: 10,
+                column: 1,
+            }
+        );
+        assert_eq!(
+            mood.variants[1].span,
+            Span {
+                start: 72,
+                end: 77,
+                line: 12,
+                column: 3,
+            }
+        );
+
+        assert_eq!(
+            ast.spans.name,
+            Span {
+                start: 6,
+                end: 15,
+                line: 1,
+                column: 7,
+            }
+        );
+        assert_eq!(
+            ast.spans.authed,
+            Span {
+                start: 24,
+                end: 29,
+                line: 2,
+                column: 9,
+            }
+        );
+        let Some(IOType::Type(output)) = &ast.output else {
+            panic!("expected a type output");
+        };
+        assert_eq!(
+            output.span(),
+            Span {
+                start: 44,
+                end: 48,
+                line: 6,
+                column: 9,
+            }
+        );
+    }
+
+    #[test]
+    fn type_errors_point_at_the_type() {
+        let cases = [
+            ("[Page<Strin>]", "Reference to undefined type: Strin", 15, 5),
+            ("Page", "Page takes 1 type argument, got 0", 9, 4),
+            (
+                "[Page<Int String>]",
+                "Page takes 1 type argument, got 2",
+                10,
+                16,
+            ),
+            (
+                "{Bool: Int}",
+                "Map keys must be String, Int, or UUID",
+                10,
+                4,
+            ),
+        ];
+        for (output, message, column, len) in cases {
+            let file = format!(
+                "name: \"GetPage\"\nauthed: false\n\n---\n\n@output {output}\n\n---\n\nPage<T> {{\n  items: [T]\n}}"
+            );
+            let err = Parser::new(&file).parse().unwrap_err();
+            assert_eq!(err.error, Error::Message(message.to_string()));
+            assert_eq!(
+                (
+                    err.span.line,
+                    err.span.column,
+                    err.span.end - err.span.start
+                ),
+                (6, column, len),
+                "{output}"
+            );
+        }
+    }
+
+    fn recovery_errors(diagnostics: &[Diagnostic]) -> Vec<(String, usize)> {
+        diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.error.to_string(), diagnostic.span.line))
+            .collect()
+    }
+
+    #[test]
+    fn recovers_from_errors() {
+        let (ast, diagnostics) = Parser::new(
+            r#"
+name: "GetUser"
+authed: maybe
+
+---
+
+@input {
+  id UUID
+  name: String
+  age: Int = "old"
+}
+
+@output User
+
+---
+
+User {
+  name: Strin
+  email: String
+  tags: [String!]
+}
+
+Mood (
+  happy
+  other(Strin)
+)
+
+Broken [
+  x: Int
+]
+
+Fine {
+  ok: Bool
+}
+            "#
+            .trim(),
+        )
+        .parse_with_recovery();
+
+        assert_eq!(
+            recovery_errors(&diagnostics),
+            vec![
+                ("Expected a boolean literal, got `maybe`".to_string(), 2),
+                ("Expected `:`, got `UUID`".to_string(), 7),
+                (
+                    "The default value of age doesn't match its type".to_string(),
+                    9
+                ),
+                ("Reference to undefined type: Strin".to_string(), 17),
+                ("Forbidden character '!'".to_string(), 19),
+                ("Reference to undefined type: Strin".to_string(), 24),
+                (
+                    "Expected a struct or enum definition, got `[`".to_string(),
+                    27
+                ),
+            ]
+        );
+
+        // everything that did parse is still there
+        let Some(IOType::Struct(input)) = &ast.input else {
+            panic!("expected an inline struct input");
+        };
+        assert_eq!(input.fields.len(), 1);
+        assert_eq!(input.fields[0].name, "name");
+        assert!(ast.output.is_some());
+        let structs = ast
+            .structs
+            .iter()
+            .map(|s| (s.name.as_str(), s.fields.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(structs, vec![("User", 1), ("Fine", 1)]);
+        assert_eq!(ast.enums[0].variants.len(), 1);
+    }
+
+    #[test]
+    fn recovers_from_a_missing_closing_brace() {
+        let (ast, diagnostics) = Parser::new(
+            "name: \"GetUser\"\nauthed: false\n\n---\n\n@input {\n  id: UUID\n\n@output String",
+        )
+        .parse_with_recovery();
+
+        assert_eq!(
+            recovery_errors(&diagnostics),
+            vec![("Expected `}`, got `@`".to_string(), 9)]
+        );
+        assert_eq!(
+            ast.output,
+            Some(IOType::Type(Type::Primitive(
+                Primitive::String,
+                Span::default()
+            )))
+        );
+    }
+
+    #[test]
+    fn recovery_reports_nothing_for_valid_files() {
+        let file =
+            "name: \"GetMood\"\nauthed: false\n\n---\n\n@output Mood\n\n---\n\nMood (\n  happy\n)";
+        let (ast, diagnostics) = Parser::new(file).parse_with_recovery();
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(ast, Parser::new(file).parse().unwrap());
+    }
+
+    fn shared_types() -> SharedTypes {
+        Parser::new(
+            r"
+User {
+  id: UUID
+  role: Role
+}
+
+Role (
+  admin
+  member
+)
+            "
+            .trim(),
+        )
+        .parse_shared()
+        .unwrap()
+    }
+
+    #[test]
+    fn imports_shared_types() {
+        let shared = shared_types();
+        assert_eq!(shared.structs[0].name, "User");
+        assert_eq!(shared.enums[0].name, "Role");
+
+        let mut parser = Parser::new(
+            r#"
+name: "GetUsers"
+authed: false
+imports: [User Role]
+
+---
+
+@input Role
+
+@output [User]
+            "#
+            .trim(),
+        )
+        .with_shared_types(&shared);
+        assert_eq!(
+            without_spans(parser.parse().unwrap()),
+            FileNode {
+                name: "GetUsers".to_string(),
+                description: None,
+                deprecated: None,
+                authed: false,
+                imports: vec!["User".to_string(), "Role".to_string()],
+                method: None,
+                path: None,
+                spans: MetadataSpans::default(),
+                input: Some(IOType::Type(Type::Named(
+                    "Role".to_string(),
+                    Span::default()
+                ))),
+                output: Some(IOType::Type(Type::Array(
+                    Box::new(Type::Named("User".to_string(), Span::default())),
+                    Span::default()
+                ))),
+                stream: false,
+                error: None,
+                structs: vec![],
+                enums: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_redefined_imports() {
+        let mut parser = Parser::new(
+            r#"
+name: "GetUsers"
+authed: false
+imports: [Admin]
+
+---
+
+@output Int
+            "#
+            .trim(),
+        )
+        .with_shared_types(&shared_types());
+        assert_eq!(
+            parser.parse().unwrap_err().error,
+            Error::Message("Imported type not found in shared types: Admin".to_string())
+        );
+
+        let mut parser = Parser::new(
+            r#"
+name: "GetUsers"
+authed: false
+imports: [User]
+
+---
+
+@output User
+
+---
+
+User {
+  id: UUID
+}
+            "#
+            .trim(),
+        )
+        .with_shared_types(&shared_types());
+        assert_eq!(
+            parser.parse().unwrap_err().error,
+            Error::Message(
+                "User is imported from shared types and also defined in this file".to_string()
+            )
+        );
+    }
 }
@@ -1,12 +1,19 @@
+use crate::ast::Span;
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TokenKind {
     // types
     Int,
+    Int32,
+    Int64,
+    UInt,
     Float,
+    Decimal,
     String,
     Bool,
     Date,
     Uuid,
+    Bytes,
 
     // syntax
     QuestionMark,
@@ -17,10 +24,17 @@ pub enum TokenKind {
     RightBrace,
     LeftBracket,
     RightBracket,
+    LeftAngle,
+    RightAngle,
+    Equals,
     At,
     Rule,
     StringLiteral(String),
     BoolLiteral(bool),
+    /// Kept as written (like `20` or `-1.5`), so it can be emitted as-is.
+    NumberLiteral(String),
+    /// The text of one or more consecutive `///` lines.
+    DocComment(String),
 
     // identifiers
     Identifier(String),
@@ -29,14 +43,52 @@ pub enum TokenKind {
     Eof,
 }
 
+/// How a token is written (in error messages).
+impl std::fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let text = match self {
+            Self::Int => "Int",
+            Self::Int32 => "Int32",
+            Self::Int64 => "Int64",
+            Self::UInt => "UInt",
+            Self::Float => "Float",
+            Self::Decimal => "Decimal",
+            Self::String => "String",
+            Self::Bool => "Bool",
+            Self::Date => "Date",
+            Self::Uuid => "UUID",
+            Self::Bytes => "Bytes",
+            Self::QuestionMark => "?",
+            Self::Colon => ":",
+            Self::LeftParen => "(",
+            Self::RightParen => ")",
+            Self::LeftBrace => "{",
+            Self::RightBrace => "}",
+            Self::LeftBracket => "[",
+            Self::RightBracket => "]",
+            Self::LeftAngle => "<",
+            Self::RightAngle => ">",
+            Self::Equals => "=",
+            Self::At => "@",
+            Self::Rule => "---",
+            Self::StringLiteral(value) => return write!(f, "`\"{value}\"`"),
+            Self::BoolLiteral(value) => return write!(f, "`{value}`"),
+            Self::NumberLiteral(value) | Self::Identifier(value) => value,
+            Self::DocComment(_) => return write!(f, "a doc comment"),
+            Self::Eof => return write!(f, "the end of the file"),
+        };
+        write!(f, "`{text}`")
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Token {
     pub kind: TokenKind,
-    pub index: usize,
+    pub span: Span,
 }
 
 impl Token {
-    pub const fn new(kind: TokenKind, index: usize) -> Self {
-        Self { kind, index }
+    pub const fn new(kind: TokenKind, span: Span) -> Self {
+        Self { kind, span }
     }
 }
@@ -1,4 +1,4 @@
-// Created by Fen v0.5.3 at 14:49:02 on 2025-03-05
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
 // Do not manually modify this file as it is automatically generated
 
 import Foundation
@@ -9,10 +9,14 @@ import Foundation
 
 struct APIClient {
   var fetcher: any Fetcher
+  /// Whether to check inputs against their constraints before sending them (the server checks
+  /// them either way).
+  var validatesInput = false
 
   static func encodeAsData(_ value: Encodable) throws -> Data {
     let encoder = JSONEncoder()
     encoder.dateEncodingStrategy = .iso8601
+    encoder.dataEncodingStrategy = .base64
     return try encoder.encode(value)
   }
 
@@ -24,6 +28,7 @@ struct APIClient {
   static func decode<T: Decodable>(_ data: Data, type: T.Type) throws -> T {
     let decoder = JSONDecoder()
     decoder.dateDecodingStrategy = .iso8601withOptionalFractionalSeconds
+    decoder.dataDecodingStrategy = .base64
     return try decoder.decode(T.self, from: data)
   }
 
@@ -31,8 +36,35 @@ struct APIClient {
     let data = string.data(using: .utf8)!
     return try self.decode(data, type: T.self)
   }
+
+  /// Escapes a path parameter, so it can't be mistaken for more than one segment of the path.
+  static func pathSegment(_ value: some CustomStringConvertible) -> String {
+    var allowed = CharacterSet.urlPathAllowed
+    allowed.remove("/")
+    return value.description.addingPercentEncoding(withAllowedCharacters: allowed)!
+  }
+
+  /// Escapes a query parameter's value, so an `&`, `=`, or `+` in it can't be mistaken for the
+  /// start of another parameter (or a space).
+  static func queryValue(_ value: String) -> String {
+    var allowed = CharacterSet.urlQueryAllowed
+    allowed.remove(charactersIn: "&=+?/#")
+    return value.addingPercentEncoding(withAllowedCharacters: allowed)!
+  }
+
+  /// Builds a query string out of the parameters that have a value (or an empty string, if none
+  /// do).
+  static func queryString(_ parameters: [(String, String?)]) -> String {
+    let pairs = parameters.compactMap { name, value in
+      value.map { "\(name)=\(self.queryValue($0))" }
+    }
+    return pairs.isEmpty ? "" : "?" + pairs.joined(separator: "&")
+  }
 }
 
+/// Sends the client's requests. A custom fetcher (like a mock, for tests) only has to implement
+/// `send`, and `lines` too if it can read a streaming route's response as it arrives. Fetchers
+/// that implement `get` and `post` instead still work for the routes those cover.
 protocol Fetcher: Sendable {
   func get<T>(from path: String, sessionToken: String?) async throws -> Response<T>
   func post<T: Decodable, U: Encodable>(
@@ -41,83 +73,439 @@ protocol Fetcher: Sendable {
     returning type: T.Type,
     sessionToken: String?
   ) async throws -> Response<T>
-}
 
-struct LiveFetcher: Fetcher {
-  var endpoint: String
+  /// Sends a request, returning the body of the response.
+  func send(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) async throws -> Data
 
-  func get<T>(from path: String, sessionToken: String?) async throws -> Response<T>
-  where T: Decodable {
-    let url = URL(string: self.endpoint + path)!
-    var request = URLRequest(url: url)
-    request.httpMethod = "GET"
-    request.setValue("application/json", forHTTPHeaderField: "Content-Type")
-    if let sessionToken = sessionToken {
-      request.setValue("Bearer \(sessionToken)", forHTTPHeaderField: "Authorization")
-    }
+  /// Sends a request to a streaming route, returning the lines of the response as they arrive.
+  func lines(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) -> AsyncThrowingStream<Data, Error>
+}
 
-    let (data, _) = try await URLSession.shared.data(for: request)
-    let tag = try APIClient.decode(data, type: ResponseType.self)
+/// Thrown for a request that needs `send` (like a PUT, or one with headers), by a fetcher that
+/// only implements `get` and `post`.
+struct UnsupportedRequest: Error, Equatable {
+  var method: String
+  var path: String
+}
 
-    if tag.type == "success" {
-      let response = try APIClient.decode(data, type: SuccessResponse<T>.self)
-      return .success(response.value)
-    } else {
-      let response = try APIClient.decode(data, type: FailureResponse.self)
-      return .failure(message: response.message, status: response.status)
-    }
+extension Fetcher {
+  func get<T: Decodable>(from path: String, sessionToken: String?) async throws -> Response<T> {
+    try await self.request("GET", to: path, sessionToken: sessionToken)
   }
 
   func post<T: Decodable, U: Encodable>(
     to path: String,
     with body: U,
     returning type: T.Type,
-    sessionToken: String? = nil
+    sessionToken: String?
   ) async throws -> Response<T> {
+    try await self.request(
+      "POST",
+      to: path,
+      with: body,
+      returning: type,
+      sessionToken: sessionToken
+    )
+  }
+
+  func send(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) async throws -> Data {
+    throw UnsupportedRequest(method: method, path: path)
+  }
+
+  func request<T: Decodable>(
+    _ method: String,
+    to path: String,
+    headers: [String: String?] = [:],
+    sessionToken: String?
+  ) async throws -> Response<T> {
+    let data = try await self.send(
+      method,
+      to: path,
+      body: nil,
+      headers: headers.compactMapValues { $0 },
+      sessionToken: sessionToken
+    )
+    return try Response(decoding: data)
+  }
+
+  func request<T: Decodable, U: Encodable>(
+    _ method: String,
+    to path: String,
+    with body: U,
+    returning type: T.Type,
+    headers: [String: String?] = [:],
+    sessionToken: String?
+  ) async throws -> Response<T> {
+    let body = try APIClient.encodeAsData(body)
+    let data = try await self.send(
+      method,
+      to: path,
+      body: body,
+      headers: headers.compactMapValues { $0 },
+      sessionToken: sessionToken
+    )
+    return try Response(decoding: data)
+  }
+
+  func request<T: Decodable, E: Decodable>(
+    _ method: String,
+    to path: String,
+    failingWith errorType: E.Type,
+    headers: [String: String?] = [:],
+    sessionToken: String?
+  ) async throws -> ResponseWithError<T, E> {
+    let data = try await self.send(
+      method,
+      to: path,
+      body: nil,
+      headers: headers.compactMapValues { $0 },
+      sessionToken: sessionToken
+    )
+    return try ResponseWithError(decoding: data)
+  }
+
+  func request<T: Decodable, U: Encodable, E: Decodable>(
+    _ method: String,
+    to path: String,
+    with body: U,
+    returning type: T.Type,
+    failingWith errorType: E.Type,
+    headers: [String: String?] = [:],
+    sessionToken: String?
+  ) async throws -> ResponseWithError<T, E> {
+    let body = try APIClient.encodeAsData(body)
+    let data = try await self.send(
+      method,
+      to: path,
+      body: body,
+      headers: headers.compactMapValues { $0 },
+      sessionToken: sessionToken
+    )
+    return try ResponseWithError(decoding: data)
+  }
+
+  func stream<T: Decodable & Sendable>(
+    _ method: String,
+    to path: String,
+    returning type: T.Type,
+    headers: [String: String?] = [:],
+    sessionToken: String?
+  ) -> AsyncThrowingStream<T, Error> {
+    self.events(
+      of: self.lines(
+        method,
+        to: path,
+        body: nil,
+        headers: headers.compactMapValues { $0 },
+        sessionToken: sessionToken
+      )
+    )
+  }
+
+  func stream<T: Decodable & Sendable, U: Encodable>(
+    _ method: String,
+    to path: String,
+    with body: U,
+    returning type: T.Type,
+    headers: [String: String?] = [:],
+    sessionToken: String?
+  ) -> AsyncThrowingStream<T, Error> {
+    // a body that can't be encoded ends the stream, like any other error
+    let data: Data
+    do {
+      data = try APIClient.encodeAsData(body)
+    } catch {
+      return AsyncThrowingStream { $0.finish(throwing: error) }
+    }
+    return self.events(
+      of: self.lines(
+        method,
+        to: path,
+        body: data,
+        headers: headers.compactMapValues { $0 },
+        sessionToken: sessionToken
+      )
+    )
+  }
+
+  func lines(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) -> AsyncThrowingStream<Data, Error> {
+    self.bufferedLines(method, to: path, body: body, headers: headers, sessionToken: sessionToken)
+  }
+
+  /// Waits for the whole response (with `send`), then splits it into lines, for fetchers that
+  /// can't read a response as it arrives.
+  func bufferedLines(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) -> AsyncThrowingStream<Data, Error> {
+    AsyncThrowingStream { continuation in
+      let task = Task {
+        do {
+          let data = try await self.send(
+            method,
+            to: path,
+            body: body,
+            headers: headers,
+            sessionToken: sessionToken
+          )
+          for line in data.split(separator: UInt8(ascii: "\n")) {
+            continuation.yield(Data(line))
+          }
+          continuation.finish()
+        } catch {
+          continuation.finish(throwing: error)
+        }
+      }
+      continuation.onTermination = { _ in task.cancel() }
+    }
+  }
+
+  /// Decodes each line as a `StreamEvent`, ending the stream at its `end` line, or throwing a
+  /// `StreamError` at a failure (or if the lines stop before the end).
+  private func events<T: Decodable & Sendable>(
+    of lines: AsyncThrowingStream<Data, Error>
+  ) -> AsyncThrowingStream<T, Error> {
+    AsyncThrowingStream { continuation in
+      let task = Task {
+        do {
+          for try await line in lines {
+            switch try StreamEvent<T>(decoding: line) {
+            case .event(let value):
+              continuation.yield(value)
+            case .failure(let message, let status):
+              continuation.finish(throwing: StreamError.failure(message: message, status: status))
+              return
+            case .end:
+              continuation.finish()
+              return
+            }
+          }
+          continuation.finish(throwing: StreamError.disconnected)
+        } catch {
+          continuation.finish(throwing: error)
+        }
+      }
+      continuation.onTermination = { _ in task.cancel() }
+    }
+  }
+}
+
+struct LiveFetcher: Fetcher {
+  var endpoint: String
+
+  func send(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) async throws -> Data {
+    let request = self.request(
+      method,
+      to: path,
+      body: body,
+      headers: headers,
+      sessionToken: sessionToken
+    )
+    let (data, _) = try await URLSession.shared.data(for: request)
+    return data
+  }
+
+  func lines(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) -> AsyncThrowingStream<Data, Error> {
+    #if canImport(FoundationNetworking)
+      // `URLSession.bytes(for:)` is only available on Apple platforms
+      return self.bufferedLines(
+        method,
+        to: path,
+        body: body,
+        headers: headers,
+        sessionToken: sessionToken
+      )
+    #else
+      guard #available(macOS 12, iOS 15, tvOS 15, watchOS 8, *) else {
+        return self.bufferedLines(
+          method,
+          to: path,
+          body: body,
+          headers: headers,
+          sessionToken: sessionToken
+        )
+      }
+      let request = self.request(
+        method,
+        to: path,
+        body: body,
+        headers: headers,
+        sessionToken: sessionToken
+      )
+      return AsyncThrowingStream { continuation in
+        let task = Task {
+          do {
+            let (bytes, _) = try await URLSession.shared.bytes(for: request)
+            for try await line in bytes.lines {
+              continuation.yield(Data(line.utf8))
+            }
+            continuation.finish()
+          } catch {
+            continuation.finish(throwing: error)
+          }
+        }
+        continuation.onTermination = { _ in task.cancel() }
+      }
+    #endif
+  }
+
+  private func request(
+    _ method: String,
+    to path: String,
+    body: Data?,
+    headers: [String: String],
+    sessionToken: String?
+  ) -> URLRequest {
     let url = URL(string: self.endpoint + path)!
     var request = URLRequest(url: url)
-    request.httpMethod = "POST"
+    request.httpMethod = method
     request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+    for (name, value) in headers {
+      request.setValue(value, forHTTPHeaderField: name)
+    }
     if let sessionToken = sessionToken {
       request.setValue("Bearer \(sessionToken)", forHTTPHeaderField: "Authorization")
     }
-
-    let body = try APIClient.encodeAsData(body)
     request.httpBody = body
+    return request
+  }
+}
 
-    let (data, _) = try await URLSession.shared.data(for: request)
+struct NoData: Decodable {}
+
+struct ResponseType: Decodable {
+  var type: String
+}
+
+enum Response<T: Decodable & Sendable>: Decodable, Sendable {
+  case success(T)
+  case failure(message: String, status: Int)
 
+  init(decoding data: Data) throws {
     let tag = try APIClient.decode(data, type: ResponseType.self)
     if tag.type == "success" {
       let response = try APIClient.decode(data, type: SuccessResponse<T>.self)
-      return .success(response.value)
+      self = .success(response.value)
     } else {
       let response = try APIClient.decode(data, type: FailureResponse.self)
-      return .failure(message: response.message, status: response.status)
+      self = .failure(message: response.message, status: response.status)
     }
   }
 }
 
-struct NoData: Decodable {}
+/// What a route with an `@error` section responds with: `failure` is one of the route's own
+/// errors, and `genericFailure` is anything else that went wrong (like a 401).
+enum ResponseWithError<T: Decodable & Sendable, RouteError: Decodable & Sendable>: Sendable {
+  case success(T)
+  case failure(RouteError)
+  case genericFailure(message: String, status: Int)
 
-struct ResponseType: Decodable {
-  var type: String
+  init(decoding data: Data) throws {
+    let tag = try APIClient.decode(data, type: ResponseType.self)
+    switch tag.type {
+    case "success":
+      let response = try APIClient.decode(data, type: SuccessResponse<T>.self)
+      self = .success(response.value)
+    case "error":
+      let response = try APIClient.decode(data, type: ErrorResponse<RouteError>.self)
+      self = .failure(response.value)
+    default:
+      let response = try APIClient.decode(data, type: FailureResponse.self)
+      self = .genericFailure(message: response.message, status: response.status)
+    }
+  }
 }
 
-enum Response<T: Decodable & Sendable>: Decodable, Sendable {
-  case success(T)
+/// One line of a streaming route's response.
+enum StreamEvent<T: Decodable & Sendable>: Sendable {
+  case event(T)
+  case failure(message: String, status: Int)
+  case end
+
+  init(decoding data: Data) throws {
+    let tag = try APIClient.decode(data, type: ResponseType.self)
+    switch tag.type {
+    case "event":
+      let event = try APIClient.decode(data, type: SuccessResponse<T>.self)
+      self = .event(event.value)
+    case "end":
+      self = .end
+    default:
+      let failure = try APIClient.decode(data, type: FailureResponse.self)
+      self = .failure(message: failure.message, status: failure.status)
+    }
+  }
+}
+
+/// Why a stream ended before its last event: the server sent a failure (like a 401, or one from
+/// the route's handler), or the connection closed early.
+enum StreamError: Error, Equatable {
   case failure(message: String, status: Int)
+  case disconnected
 }
 
 struct SuccessResponse<T: Decodable & Sendable>: Decodable, Sendable {
   let value: T
 }
 
+struct ErrorResponse<E: Decodable & Sendable>: Decodable, Sendable {
+  let value: E
+}
+
 struct FailureResponse: Decodable {
   let message: String
   let status: Int
 }
 
+/// A field of a route's input that broke one of its constraints.
+struct ValidationError: Error, Equatable, CustomStringConvertible {
+  var field: String
+  var message: String
+
+  var description: String {
+    "\(self.field) \(self.message)"
+  }
+}
+
 extension ParseStrategy where Self == Date.ISO8601FormatStyle {
   static var iso8601withFractionalSeconds: Self { .init(includingFractionalSeconds: true) }
 }
@@ -132,3 +520,59 @@ extension JSONDecoder.DateDecodingStrategy {
     }
   }
 }
+
+/// Lets `[UUID: T]` dictionaries be coded as JSON objects keyed by UUID strings, rather than as
+/// flat arrays of keys and values.
+@available(macOS 12.3, iOS 15.4, tvOS 15.4, watchOS 8.5, *)
+extension UUID: CodingKeyRepresentable {
+  public var codingKey: CodingKey {
+    UUIDCodingKey(stringValue: self.uuidString)
+  }
+
+  public init?<T: CodingKey>(codingKey: T) {
+    self.init(uuidString: codingKey.stringValue)
+  }
+}
+
+private struct UUIDCodingKey: CodingKey {
+  var stringValue: String
+  var intValue: Int? { nil }
+
+  init(stringValue: String) {
+    self.stringValue = stringValue
+  }
+
+  init?(intValue: Int) {
+    return nil
+  }
+}
+
+/// Decimals are encoded as strings (so no precision is lost), which these overloads take care of
+/// for struct fields and enum values (the only places Fen allows them, since generic code like
+/// `[Decimal]`'s wouldn't pick the overloads up).
+extension KeyedEncodingContainer {
+  mutating func encode(_ value: Decimal, forKey key: Key) throws {
+    try self.encode(value.description, forKey: key)
+  }
+}
+
+extension KeyedDecodingContainer {
+  func decode(_ type: Decimal.Type, forKey key: Key) throws -> Decimal {
+    let string = try self.decode(String.self, forKey: key)
+    guard let value = Decimal(string: string, locale: Locale(identifier: "en_US_POSIX")) else {
+      throw DecodingError.dataCorruptedError(
+        forKey: key,
+        in: self,
+        debugDescription: "Invalid decimal: \(string)"
+      )
+    }
+    return value
+  }
+
+  func decodeIfPresent(_ type: Decimal.Type, forKey key: Key) throws -> Decimal? {
+    guard self.contains(key), try !self.decodeNil(forKey: key) else {
+      return nil
+    }
+    return try self.decode(Decimal.self, forKey: key)
+  }
+}
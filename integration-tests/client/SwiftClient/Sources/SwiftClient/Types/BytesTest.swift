@@ -0,0 +1,77 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+import Foundation
+
+extension APIClient {
+  func bytesTest(thumbnail: Data, signature: Data?) async throws -> Response<BytesTestOutput> {
+    return try await self.fetcher.post(
+      to: "/_fen_/bytes-test",
+      with: BytesTestInput(thumbnail: thumbnail, signature: signature),
+      returning: BytesTestOutput.self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct BytesTestInput: Codable, Equatable {
+  var thumbnail: Data
+  var signature: Data?
+
+  private enum CodingKeys: String, CodingKey {
+    case thumbnail
+    case signature
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.thumbnail, forKey: .thumbnail)
+    switch self.signature {
+    case let .some(value):
+      try container.encode(value, forKey: .signature)
+    case .none:
+      try container.encodeNil(forKey: .signature)
+    }
+  }
+}
+
+enum BytesTestOutput: Codable, Equatable {
+  case empty
+  case image(Data)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum BytesTestOutputType: String, Codable {
+    case empty
+    case image
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(BytesTestOutputType.self, forKey: .type)
+
+    switch type {
+    case .empty:
+      self = .empty
+    case .image:
+      let value = try container.decode(Data.self, forKey: .value)
+      self = .image(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .empty:
+      try container.encode(BytesTestOutputType.empty, forKey: .type)
+    case .image(let value):
+      try container.encode(BytesTestOutputType.image, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
\ No newline at end of file
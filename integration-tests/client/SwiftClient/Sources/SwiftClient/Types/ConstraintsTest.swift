@@ -0,0 +1,45 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+extension APIClient {
+  func constraintsTest(limit: Int, name: String) async throws -> Response<NoData> {
+    let input = ConstraintsTestInput(limit: limit, name: name)
+    if self.validatesInput {
+      let errors = input.validate()
+      if !errors.isEmpty {
+        return .failure(message: errors.map(\.description).joined(separator: "; "), status: 422)
+      }
+    }
+    return try await self.fetcher.post(
+      to: "/_fen_/constraints-test",
+      with: input,
+      returning: NoData.self,
+      sessionToken: nil
+    )
+  }
+}
+
+struct ConstraintsTestInput: Codable, Equatable {
+  var limit: Int
+  var name: String
+}
+
+extension ConstraintsTestInput {
+  /// The constraints this input breaks (the server checks them too).
+  func validate() -> [ValidationError] {
+    var errors: [ValidationError] = []
+    if self.limit < 1 {
+      errors.append(ValidationError(field: "limit", message: "must be at least 1"))
+    }
+    if self.limit > 100 {
+      errors.append(ValidationError(field: "limit", message: "must be at most 100"))
+    }
+    if self.name.count < 1 {
+      errors.append(ValidationError(field: "name", message: "must have at least 1 character"))
+    }
+    if self.name.count > 10 {
+      errors.append(ValidationError(field: "name", message: "must have at most 10 characters"))
+    }
+    return errors
+  }
+}
\ No newline at end of file
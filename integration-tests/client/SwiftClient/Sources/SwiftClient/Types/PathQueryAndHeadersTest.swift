@@ -0,0 +1,45 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+import Foundation
+
+extension APIClient {
+  func pathQueryAndHeadersTest(item_id: UUID, new_name: String, dry_run: Bool?, x_request_id: String, sessionToken: String) async throws -> Response<String> {
+    return try await self.fetcher.request(
+      "PUT",
+      to: "/items/\(APIClient.pathSegment(item_id))" + APIClient.queryString([("dryRun", dry_run.map { "\($0)" })]),
+      with: PathQueryAndHeadersTestInput(itemId: item_id, newName: new_name, dryRun: dry_run, xRequestId: x_request_id),
+      returning: String.self,
+      headers: ["x-request-id": x_request_id],
+      sessionToken: sessionToken
+    )
+  }
+}
+
+struct PathQueryAndHeadersTestInput: Codable, Equatable {
+  var itemId: UUID
+  var newName: String
+  var dryRun: Bool?
+  var xRequestId: String
+
+  private enum CodingKeys: String, CodingKey {
+    case itemId
+    case newName
+    case dryRun
+    case xRequestId
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    try container.encode(self.itemId, forKey: .itemId)
+    try container.encode(self.newName, forKey: .newName)
+    switch self.dryRun {
+    case let .some(value):
+      try container.encode(value, forKey: .dryRun)
+    case .none:
+      try container.encodeNil(forKey: .dryRun)
+    }
+    try container.encode(self.xRequestId, forKey: .xRequestId)
+  }
+}
\ No newline at end of file
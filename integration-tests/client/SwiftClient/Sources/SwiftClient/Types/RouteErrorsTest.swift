@@ -0,0 +1,55 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+extension APIClient {
+  func routeErrorsTest(input: String) async throws -> ResponseWithError<NoData, RouteErrorsTestError> {
+    return try await self.fetcher.request(
+      "POST",
+      to: "/_fen_/route-errors-test",
+      with: input,
+      returning: NoData.self,
+      failingWith: RouteErrorsTestError.self,
+      sessionToken: nil
+    )
+  }
+}
+
+enum RouteErrorsTestError: Codable, Equatable {
+  case taken
+  case tooShort(Int)
+
+  private enum CodingKeys: String, CodingKey {
+    case type
+    case value
+  }
+
+  private enum RouteErrorsTestErrorType: String, Codable {
+    case taken
+    case tooShort
+  }
+
+  init(from decoder: Decoder) throws {
+    let container = try decoder.container(keyedBy: CodingKeys.self)
+    let type = try container.decode(RouteErrorsTestErrorType.self, forKey: .type)
+
+    switch type {
+    case .taken:
+      self = .taken
+    case .tooShort:
+      let value = try container.decode(Int.self, forKey: .value)
+      self = .tooShort(value)
+    }
+  }
+
+  func encode(to encoder: Encoder) throws {
+    var container = encoder.container(keyedBy: CodingKeys.self)
+
+    switch self {
+    case .taken:
+      try container.encode(RouteErrorsTestErrorType.taken, forKey: .type)
+    case .tooShort(let value):
+      try container.encode(RouteErrorsTestErrorType.tooShort, forKey: .type)
+      try container.encode(value, forKey: .value)
+    }
+  }
+}
\ No newline at end of file
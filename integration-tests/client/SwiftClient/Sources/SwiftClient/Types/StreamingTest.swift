@@ -0,0 +1,14 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+extension APIClient {
+  func streamingTest(input: String) -> AsyncThrowingStream<Int, Error> {
+    return self.fetcher.stream(
+      "POST",
+      to: "/_fen_/streaming-test",
+      with: input,
+      returning: Int.self,
+      sessionToken: nil
+    )
+  }
+}
\ No newline at end of file
@@ -422,3 +422,180 @@ import Testing
             ]
     )
 }
+
+@Test func bytes() throws {
+    // input
+    let input = BytesTestInput(thumbnail: Data([0, 1, 2, 3]), signature: nil)
+    let inputJson = try APIClient.encodeAsString(input)
+    #expect(try APIClient.decode(inputJson, type: BytesTestInput.self) == input)
+    #expect(inputJson.contains("\"thumbnail\":\"AAECAw==\""))
+
+    // output
+    let outputJson = """
+        {
+          "value": {
+            "type": "image",
+            "value": "AAECAw=="
+          }
+        }
+        """
+    let output = try APIClient.decode(outputJson, type: SuccessResponse<BytesTestOutput>.self)
+    #expect(output.value == .image(Data([0, 1, 2, 3])))
+}
+
+@Test func queryValuesAreEscaped() {
+    #expect(APIClient.queryString([("q", "a&b=c+1"), ("page", nil)]) == "?q=a%26b%3Dc%2B1")
+    #expect(APIClient.queryString([("page", nil)]) == "")
+}
+
+/// Answers every request with the same response, keeping track of what was sent.
+final class MockFetcher: Fetcher, @unchecked Sendable {
+    let response: String
+    var requests: [(method: String, path: String, headers: [String: String], sessionToken: String?)] = []
+
+    init(response: String) {
+        self.response = response
+    }
+
+    func send(
+        _ method: String,
+        to path: String,
+        body: Data?,
+        headers: [String: String],
+        sessionToken: String?
+    ) async throws -> Data {
+        self.requests.append((method, path, headers, sessionToken))
+        return self.response.data(using: .utf8)!
+    }
+}
+
+/// A fetcher written before `send`, which only implements `get` and `post`.
+struct GetAndPostFetcher: Fetcher {
+    func get<T>(from path: String, sessionToken: String?) async throws -> Response<T> {
+        .failure(message: "Not implemented", status: 501)
+    }
+
+    func post<T: Decodable, U: Encodable>(
+        to path: String,
+        with body: U,
+        returning type: T.Type,
+        sessionToken: String?
+    ) async throws -> Response<T> {
+        .failure(message: "Not implemented", status: 501)
+    }
+}
+
+@Test func pathQueryAndHeaders() async throws {
+    let fetcher = MockFetcher(response: #"{"type": "success", "value": "renamed"}"#)
+    let client = APIClient(fetcher: fetcher)
+    let id = UUID(uuidString: "6BC9B6B8-5075-4296-AF84-534E6FB5916D")!
+
+    let response = try await client.pathQueryAndHeadersTest(
+        item_id: id, new_name: "Martha", dry_run: true, x_request_id: "abc", sessionToken: "token")
+    guard case .success(let value) = response else {
+        Issue.record("expected a success, got \(response)")
+        return
+    }
+    #expect(value == "renamed")
+
+    let request = try #require(fetcher.requests.first)
+    #expect(request.method == "PUT")
+    #expect(request.path == "/items/6BC9B6B8-5075-4296-AF84-534E6FB5916D?dryRun=true")
+    #expect(request.headers == ["x-request-id": "abc"])
+    #expect(request.sessionToken == "token")
+}
+
+@Test func fetchersWithoutSend() async throws {
+    let client = APIClient(fetcher: GetAndPostFetcher())
+    let id = UUID(uuidString: "6BC9B6B8-5075-4296-AF84-534E6FB5916D")!
+
+    await #expect(
+        throws: UnsupportedRequest(
+            method: "PUT", path: "/items/6BC9B6B8-5075-4296-AF84-534E6FB5916D")
+    ) {
+        try await client.pathQueryAndHeadersTest(
+            item_id: id, new_name: "Martha", dry_run: nil, x_request_id: "abc",
+            sessionToken: "token")
+    }
+
+    // routes that only need `post` still go through it
+    let response = try await client.bytesTest(thumbnail: Data(), signature: nil)
+    guard case .failure(_, let status) = response else {
+        Issue.record("expected a failure, got \(response)")
+        return
+    }
+    #expect(status == 501)
+}
+
+@Test func routeErrors() async throws {
+    let fetcher = MockFetcher(
+        response: #"{"type": "error", "value": {"type": "tooShort", "value": 3}}"#)
+    let response = try await APIClient(fetcher: fetcher).routeErrorsTest(input: "ab")
+    guard case .failure(let error) = response else {
+        Issue.record("expected a route error, got \(response)")
+        return
+    }
+    #expect(error == .tooShort(3))
+
+    let genericFetcher = MockFetcher(
+        response: #"{"type": "failure", "message": "Unauthorized", "status": 401}"#)
+    let genericResponse = try await APIClient(fetcher: genericFetcher).routeErrorsTest(input: "ab")
+    guard case .genericFailure(let message, let status) = genericResponse else {
+        Issue.record("expected a generic failure, got \(genericResponse)")
+        return
+    }
+    #expect(message == "Unauthorized")
+    #expect(status == 401)
+}
+
+@Test func constraints() async throws {
+    let input = ConstraintsTestInput(limit: 0, name: "George Washington")
+    #expect(
+        input.validate() == [
+            ValidationError(field: "limit", message: "must be at least 1"),
+            ValidationError(field: "name", message: "must have at most 10 characters"),
+        ]
+    )
+
+    // invalid inputs aren't sent when the client validates them
+    let fetcher = MockFetcher(response: #"{"type": "success", "value": {}}"#)
+    let client = APIClient(fetcher: fetcher, validatesInput: true)
+    let response = try await client.constraintsTest(limit: 0, name: "George Washington")
+    guard case .failure(let message, let status) = response else {
+        Issue.record("expected a failure, got \(response)")
+        return
+    }
+    #expect(message == "limit must be at least 1; name must have at most 10 characters")
+    #expect(status == 422)
+    #expect(fetcher.requests.isEmpty)
+}
+
+@Test func streaming() async throws {
+    let fetcher = MockFetcher(
+        response: """
+            {"type": "event", "value": 1}
+            {"type": "event", "value": 2}
+            {"type": "end"}
+
+            """)
+    var events: [Int] = []
+    for try await event in APIClient(fetcher: fetcher).streamingTest(input: "abc") {
+        events.append(event)
+    }
+    #expect(events == [1, 2])
+
+    let failingFetcher = MockFetcher(
+        response: """
+            {"type": "event", "value": 1}
+            {"type": "failure", "message": "Empty input", "status": 400}
+
+            """)
+    await #expect(throws: StreamError.failure(message: "Empty input", status: 400)) {
+        for try await _ in APIClient(fetcher: failingFetcher).streamingTest(input: "") {}
+    }
+
+    let droppedFetcher = MockFetcher(response: #"{"type": "event", "value": 1}"#)
+    await #expect(throws: StreamError.disconnected) {
+        for try await _ in APIClient(fetcher: droppedFetcher).streamingTest(input: "abc") {}
+    }
+}
@@ -1,4 +1,4 @@
-#![allow(dead_code)]
+#![allow(dead_code, unused_imports)]
 
 mod types;
 
@@ -9,14 +9,17 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use crate::types::*;
+    use axum::{body::Body, http::Request};
     use chrono::TimeZone;
+    use http_body_util::BodyExt;
     use pretty_assertions::assert_eq;
+    use tower::ServiceExt;
 
     fn assert_response<T>(data: T, expected: &str)
     where
         T: serde::Serialize,
     {
-        let json = serde_json::to_string(&Response::success(data)).unwrap();
+        let json = serde_json::to_string(&Response::<T>::success(data)).unwrap();
         assert_eq!(json, expected);
     }
 
@@ -341,4 +344,319 @@ mod tests {
             r#"{"type":"success","value":[{"title":"Song 1","composed":"1904-03-05T00:00:00Z"},{"title":"Song 2","composed":"1904-03-06T00:00:00Z"}]}"#,
         );
     }
+
+    #[test]
+    fn bytes() {
+        // input
+        let input_json = r#"{"thumbnail":"AAEC/w==","signature":null}"#;
+        let input: bytes_test::Input = serde_json::from_str(input_json).unwrap();
+        assert_eq!(
+            input,
+            bytes_test::Input {
+                thumbnail: vec![0, 1, 2, 255],
+                signature: None,
+            }
+        );
+
+        // output
+        let output: bytes_test::Output = bytes_test::Output::Image(vec![0, 1, 2, 255]);
+        assert_response(
+            output,
+            r#"{"type":"success","value":{"type":"image","value":"AAEC/w=="}}"#,
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        let response: Response<(), route_errors_test::Error> =
+            Response::error(route_errors_test::Error::TooShort(3));
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"type":"error","value":{"type":"tooShort","value":3}}"#
+        );
+    }
+
+    #[test]
+    fn constraints() {
+        let input = constraints_test::Input {
+            limit: 0,
+            name: "George Washington".to_string(),
+        };
+        let errors = input.validate().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::new("limit", "must be at least 1"),
+                ValidationError::new("name", "must have at most 10 characters"),
+            ]
+        );
+
+        let response: Response<()> = Response::invalid(&errors);
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"type":"failure","message":"limit must be at least 1; name must have at most 10 characters","status":422}"#
+        );
+    }
+
+    struct TestHandlers;
+
+    impl Handlers for TestHandlers {
+        async fn array_of_structs_with_date_test(
+            &self,
+        ) -> Response<array_of_structs_with_date_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn arrays_test(&self, _input: arrays_test::Input) -> Response<arrays_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn basic_enums_test(
+            &self,
+            _input: basic_enums_test::Input,
+        ) -> Response<basic_enums_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn basic_structs_test(
+            &self,
+            _input: basic_structs_test::Input,
+        ) -> Response<basic_structs_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn bools_test(&self, _input: bools_test::Input) -> Response<bools_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn bytes_test(&self, input: bytes_test::Input) -> Response<bytes_test::Output> {
+            match input.signature {
+                Some(signature) => Response::success(bytes_test::Output::Image(signature)),
+                None => Response::success(bytes_test::Output::Empty),
+            }
+        }
+
+        async fn composing_structs_and_enums_test(
+            &self,
+        ) -> Response<composing_structs_and_enums_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn compound_arrays_and_optionals_test(
+            &self,
+            _input: compound_arrays_and_optionals_test::Input,
+        ) -> Response<compound_arrays_and_optionals_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn constraints_test(&self, _input: constraints_test::Input) -> Response<()> {
+            Response::success(())
+        }
+
+        async fn dates_test(&self, _input: dates_test::Input) -> Response<dates_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn enums_with_associated_values_test(
+            &self,
+        ) -> Response<enums_with_associated_values_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn floats_test(&self, _input: floats_test::Input) -> Response<floats_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn ints_test(&self, _input: ints_test::Input) -> Response<ints_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn nested_structs_test(&self) -> Response<nested_structs_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn optionals_test(
+            &self,
+            _input: optionals_test::Input,
+        ) -> Response<optionals_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn path_query_and_headers_test(
+            &self,
+            input: path_query_and_headers_test::Input,
+            session_token: String,
+        ) -> Response<path_query_and_headers_test::Output> {
+            Response::success(format!(
+                "{} {} {:?} {} {session_token}",
+                input.item_id, input.new_name, input.dry_run, input.x_request_id
+            ))
+        }
+
+        async fn route_errors_test(
+            &self,
+            input: route_errors_test::Input,
+        ) -> Response<(), route_errors_test::Error> {
+            if input.len() < 3 {
+                Response::error(route_errors_test::Error::TooShort(3))
+            } else {
+                Response::error(route_errors_test::Error::Taken)
+            }
+        }
+
+        async fn streaming_test(
+            &self,
+            input: streaming_test::Input,
+        ) -> EventStream<streaming_test::Output> {
+            if input.is_empty() {
+                EventStream::failure(400, "Empty input")
+            } else {
+                EventStream::new(futures::stream::iter([1, 2, 3]))
+            }
+        }
+
+        async fn strings_test(
+            &self,
+            _input: strings_test::Input,
+        ) -> Response<strings_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn structs_with_compound_types_test(
+            &self,
+            _input: structs_with_compound_types_test::Input,
+        ) -> Response<structs_with_compound_types_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+
+        async fn uuids_test(&self, _input: uuids_test::Input) -> Response<uuids_test::Output> {
+            Response::failure(501, "Not implemented")
+        }
+    }
+
+    /// Sends a request through the router, returning the response's body.
+    async fn send(request: Request<Body>) -> String {
+        let response = router(TestHandlers).oneshot(request).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    fn post(path: &str, body: &str) -> Request<Body> {
+        Request::post(path)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn router_decodes_bytes() {
+        let body = send(post(
+            "/_fen_/bytes-test",
+            r#"{"thumbnail":"","signature":"AAEC/w=="}"#,
+        ))
+        .await;
+        assert_eq!(
+            body,
+            r#"{"type":"success","value":{"type":"image","value":"AAEC/w=="}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn router_rejects_invalid_input() {
+        let body = send(post(
+            "/_fen_/constraints-test",
+            r#"{"limit":101,"name":"a"}"#,
+        ))
+        .await;
+        assert_eq!(
+            body,
+            r#"{"type":"failure","message":"limit must be at most 100","status":422}"#
+        );
+
+        let body = send(post(
+            "/_fen_/constraints-test",
+            r#"{"limit":100,"name":"a"}"#,
+        ))
+        .await;
+        assert_eq!(body, r#"{"type":"success","value":null}"#);
+    }
+
+    #[tokio::test]
+    async fn router_sends_route_errors() {
+        let body = send(post("/_fen_/route-errors-test", r#""ab""#)).await;
+        assert_eq!(
+            body,
+            r#"{"type":"error","value":{"type":"tooShort","value":3}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn router_reads_path_query_and_headers() {
+        let request = || {
+            Request::put("/items/6bc9b6b8-5075-4296-af84-534e6fb5916d?dryRun=true")
+                .header("content-type", "application/json")
+                .header("authorization", "Bearer token")
+        };
+
+        let body = send(
+            request()
+                .header("x-request-id", "abc")
+                .body(Body::from(r#"{"newName":"Martha"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(
+            body,
+            r#"{"type":"success","value":"6bc9b6b8-5075-4296-af84-534e6fb5916d Martha Some(true) abc token"}"#
+        );
+
+        let body = send(
+            request()
+                .body(Body::from(r#"{"newName":"Martha"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(
+            body,
+            r#"{"type":"failure","message":"Missing or invalid x-request-id header","status":400}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn router_requires_a_session_token() {
+        let body = send(
+            Request::put("/items/6bc9b6b8-5075-4296-af84-534e6fb5916d")
+                .header("content-type", "application/json")
+                .header("x-request-id", "abc")
+                .body(Body::from(r#"{"newName":"Martha"}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(
+            body,
+            r#"{"type":"failure","message":"Unauthorized","status":401}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn router_streams_events() {
+        let body = send(post("/_fen_/streaming-test", r#""abc""#)).await;
+        assert_eq!(
+            body,
+            [
+                r#"{"type":"event","value":1}"#,
+                r#"{"type":"event","value":2}"#,
+                r#"{"type":"event","value":3}"#,
+                r#"{"type":"end"}"#,
+                "",
+            ]
+            .join("\n")
+        );
+
+        let body = send(post("/_fen_/streaming-test", r#""""#)).await;
+        assert_eq!(
+            body,
+            "{\"type\":\"failure\",\"message\":\"Empty input\",\"status\":400}\n"
+        );
+    }
 }
@@ -1,4 +1,4 @@
-// Created by Fen v0.5.3 at 14:49:02 on 2025-03-05
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
 // Do not manually modify this file as it is automatically generated
 
 use uuid::Uuid;
@@ -0,0 +1,20 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    #[serde(with = "super::fen_base64")]
+    pub thumbnail: Vec<u8>,
+    #[serde(default, with = "super::fen_base64::option")]
+    pub signature: Option<Vec<u8>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Output {
+    Empty,
+    Image(#[serde(with = "super::fen_base64")] Vec<u8>),
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+use serde::{Deserialize, Serialize};
+
+use super::ValidationError;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub limit: isize,
+    pub name: String,
+}
+
+impl Input {
+    /// Checks the input against its constraints, returning every one it breaks.
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        if self.limit < 1 {
+            errors.push(ValidationError::new("limit", "must be at least 1"));
+        }
+        if self.limit > 100 {
+            errors.push(ValidationError::new("limit", "must be at most 100"));
+        }
+        if self.name.chars().count() < 1 {
+            errors.push(ValidationError::new("name", "must have at least 1 character"));
+        }
+        if self.name.chars().count() > 10 {
+            errors.push(ValidationError::new("name", "must have at most 10 characters"));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
\ No newline at end of file
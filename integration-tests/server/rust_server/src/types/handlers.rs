@@ -0,0 +1,334 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+use axum::{
+    extract::{Path, Query},
+    http::{header::AUTHORIZATION, HeaderMap},
+    routing::{get, post, put},
+    Json, Router,
+};
+use std::{future::Future, sync::Arc};
+
+use super::{array_of_structs_with_date_test, arrays_test, basic_enums_test, basic_structs_test, bools_test, bytes_test, composing_structs_and_enums_test, compound_arrays_and_optionals_test, constraints_test, dates_test, enums_with_associated_values_test, floats_test, ints_test, nested_structs_test, optionals_test, path_query_and_headers_test, route_errors_test, streaming_test, strings_test, structs_with_compound_types_test, uuids_test, fen_path, Response, EventStream};
+
+pub trait Handlers: Send + Sync + 'static {
+    fn array_of_structs_with_date_test(
+        &self,
+    ) -> impl Future<Output = Response<array_of_structs_with_date_test::Output>> + Send;
+
+    fn arrays_test(
+        &self,
+        input: arrays_test::Input,
+    ) -> impl Future<Output = Response<arrays_test::Output>> + Send;
+
+    fn basic_enums_test(
+        &self,
+        input: basic_enums_test::Input,
+    ) -> impl Future<Output = Response<basic_enums_test::Output>> + Send;
+
+    /// Get a person by their id
+    fn basic_structs_test(
+        &self,
+        input: basic_structs_test::Input,
+    ) -> impl Future<Output = Response<basic_structs_test::Output>> + Send;
+
+    fn bools_test(
+        &self,
+        input: bools_test::Input,
+    ) -> impl Future<Output = Response<bools_test::Output>> + Send;
+
+    fn bytes_test(
+        &self,
+        input: bytes_test::Input,
+    ) -> impl Future<Output = Response<bytes_test::Output>> + Send;
+
+    fn composing_structs_and_enums_test(
+        &self,
+    ) -> impl Future<Output = Response<composing_structs_and_enums_test::Output>> + Send;
+
+    fn compound_arrays_and_optionals_test(
+        &self,
+        input: compound_arrays_and_optionals_test::Input,
+    ) -> impl Future<Output = Response<compound_arrays_and_optionals_test::Output>> + Send;
+
+    fn constraints_test(
+        &self,
+        input: constraints_test::Input,
+    ) -> impl Future<Output = Response<()>> + Send;
+
+    fn dates_test(
+        &self,
+        input: dates_test::Input,
+    ) -> impl Future<Output = Response<dates_test::Output>> + Send;
+
+    fn enums_with_associated_values_test(
+        &self,
+    ) -> impl Future<Output = Response<enums_with_associated_values_test::Output>> + Send;
+
+    fn floats_test(
+        &self,
+        input: floats_test::Input,
+    ) -> impl Future<Output = Response<floats_test::Output>> + Send;
+
+    fn ints_test(
+        &self,
+        input: ints_test::Input,
+    ) -> impl Future<Output = Response<ints_test::Output>> + Send;
+
+    fn nested_structs_test(
+        &self,
+    ) -> impl Future<Output = Response<nested_structs_test::Output>> + Send;
+
+    fn optionals_test(
+        &self,
+        input: optionals_test::Input,
+    ) -> impl Future<Output = Response<optionals_test::Output>> + Send;
+
+    fn path_query_and_headers_test(
+        &self,
+        input: path_query_and_headers_test::Input,
+        session_token: String,
+    ) -> impl Future<Output = Response<path_query_and_headers_test::Output>> + Send;
+
+    fn route_errors_test(
+        &self,
+        input: route_errors_test::Input,
+    ) -> impl Future<Output = Response<(), route_errors_test::Error>> + Send;
+
+    fn streaming_test(
+        &self,
+        input: streaming_test::Input,
+    ) -> impl Future<Output = EventStream<streaming_test::Output>> + Send;
+
+    fn strings_test(
+        &self,
+        input: strings_test::Input,
+    ) -> impl Future<Output = Response<strings_test::Output>> + Send;
+
+    fn structs_with_compound_types_test(
+        &self,
+        input: structs_with_compound_types_test::Input,
+    ) -> impl Future<Output = Response<structs_with_compound_types_test::Output>> + Send;
+
+    fn uuids_test(
+        &self,
+        input: uuids_test::Input,
+    ) -> impl Future<Output = Response<uuids_test::Output>> + Send;
+}
+
+pub fn router(handlers: impl Handlers) -> Router {
+    let handlers = Arc::new(handlers);
+    Router::new()
+        .route(
+            &fen_path("/array-of-structs-with-date-test"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move || async move {
+                    Json(handlers.array_of_structs_with_date_test().await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/arrays-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<arrays_test::Input>| async move {
+                    Json(handlers.arrays_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/basic-enums-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<basic_enums_test::Input>| async move {
+                    Json(handlers.basic_enums_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/basic-structs-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<basic_structs_test::Input>| async move {
+                    Json(handlers.basic_structs_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/bools-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<bools_test::Input>| async move {
+                    Json(handlers.bools_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/bytes-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<bytes_test::Input>| async move {
+                    Json(handlers.bytes_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/composing-structs-and-enums-test"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move || async move {
+                    Json(handlers.composing_structs_and_enums_test().await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/compound-arrays-and-optionals-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<compound_arrays_and_optionals_test::Input>| async move {
+                    Json(handlers.compound_arrays_and_optionals_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/constraints-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<constraints_test::Input>| async move {
+                    if let Err(errors) = input.validate() {
+                        return Json(Response::invalid(&errors));
+                    }
+                    Json(handlers.constraints_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/dates-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<dates_test::Input>| async move {
+                    Json(handlers.dates_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/enums-with-associated-values-test"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move || async move {
+                    Json(handlers.enums_with_associated_values_test().await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/floats-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<floats_test::Input>| async move {
+                    Json(handlers.floats_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/ints-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<ints_test::Input>| async move {
+                    Json(handlers.ints_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/nested-structs-test"),
+            get({
+                let handlers = Arc::clone(&handlers);
+                move || async move {
+                    Json(handlers.nested_structs_test().await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/optionals-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<optionals_test::Input>| async move {
+                    Json(handlers.optionals_test(input).await)
+                }
+            }),
+        )
+        .route(
+            "/items/{item_id}",
+            put({
+                let handlers = Arc::clone(&handlers);
+                move |headers: HeaderMap, Path(path): Path<path_query_and_headers_test::PathParams>, Query(query): Query<path_query_and_headers_test::QueryParams>, Json(body): Json<path_query_and_headers_test::Body>| async move {
+                    let Some(session_token) = bearer_token(&headers) else {
+                        return Json(Response::failure(401, "Unauthorized"));
+                    };
+                    let Some(x_request_id) = header(&headers, "x-request-id") else {
+                        return Json(Response::failure(400, "Missing or invalid x-request-id header"));
+                    };
+                    let input = path_query_and_headers_test::Input { item_id: path.item_id, new_name: body.new_name, dry_run: query.dry_run, x_request_id };
+                    Json(handlers.path_query_and_headers_test(input, session_token).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/route-errors-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<route_errors_test::Input>| async move {
+                    Json(handlers.route_errors_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/streaming-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<streaming_test::Input>| async move {
+                    handlers.streaming_test(input).await
+                }
+            }),
+        )
+        .route(
+            &fen_path("/strings-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<strings_test::Input>| async move {
+                    Json(handlers.strings_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/structs-with-compound-types-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<structs_with_compound_types_test::Input>| async move {
+                    Json(handlers.structs_with_compound_types_test(input).await)
+                }
+            }),
+        )
+        .route(
+            &fen_path("/uuids-test"),
+            post({
+                let handlers = Arc::clone(&handlers);
+                move |Json(input): Json<uuids_test::Input>| async move {
+                    Json(handlers.uuids_test(input).await)
+                }
+            }),
+        )
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(ToString::to_string)
+}
+
+fn header<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
\ No newline at end of file
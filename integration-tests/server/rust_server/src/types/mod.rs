@@ -1,43 +1,67 @@
-// Created by Fen v0.5.3 at 14:49:02 on 2025-03-05
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
 // Do not manually modify this file as it is automatically generated
 
-pub mod dates_test;
-pub mod strings_test;
 pub mod arrays_test;
-pub mod array_of_structs_with_date_test;
-pub mod basic_enums_test;
-pub mod bools_test;
-pub mod structs_with_compound_types_test;
 pub mod nested_structs_test;
-pub mod enums_with_associated_values_test;
+pub mod basic_enums_test;
 pub mod compound_arrays_and_optionals_test;
 pub mod uuids_test;
-pub mod optionals_test;
+pub mod bytes_test;
 pub mod ints_test;
-pub mod composing_structs_and_enums_test;
+pub mod dates_test;
 pub mod basic_structs_test;
+pub mod enums_with_associated_values_test;
+pub mod optionals_test;
+pub mod structs_with_compound_types_test;
+pub mod bools_test;
+pub mod strings_test;
+pub mod constraints_test;
+pub mod path_query_and_headers_test;
 pub mod floats_test;
+pub mod route_errors_test;
+pub mod composing_structs_and_enums_test;
+pub mod array_of_structs_with_date_test;
+pub mod streaming_test;
+pub mod handlers;
+
+pub use handlers::{router, Handlers};
 
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
-pub enum Response<T> {
+pub enum Response<T, E = NoError> {
     Success(SuccessResponse<T>),
+    Error(ErrorResponse<E>),
     Failure(FailureResponse),
 }
 
-impl<T> Response<T> {
+impl<T, E> Response<T, E> {
     pub const fn success(value: T) -> Self {
         Self::Success(SuccessResponse { value })
     }
 
+    /// One of the errors the route declares in its `@error` section.
+    pub const fn error(value: E) -> Self {
+        Self::Error(ErrorResponse { value })
+    }
+
     pub fn failure(status: isize, message: &str) -> Self {
         Self::Failure(FailureResponse {
             status,
             message: message.to_string(),
         })
     }
+
+    /// A 422 failure listing every constraint an input broke.
+    pub fn invalid(errors: &[ValidationError]) -> Self {
+        let message = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join("; ");
+        Self::failure(422, &message)
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -45,12 +69,165 @@ pub struct SuccessResponse<T> {
     pub value: T,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct ErrorResponse<E> {
+    pub value: E,
+}
+
+/// The error type of routes without an `@error` section, which can't be constructed.
+#[derive(Serialize, Deserialize)]
+pub enum NoError {}
+
 #[derive(Serialize, Deserialize)]
 pub struct FailureResponse {
     pub message: String,
     pub status: isize,
 }
 
+/// A field of a route's input that broke one of its constraints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The field's name, as it's sent (in camelCase).
+    pub field: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", self.field, self.message)
+    }
+}
+
 pub fn fen_path(path: &str) -> String {
     format!("/_fen_{path}")
 }
+
+/// One line of a streaming route's response, which is sent as newline-delimited JSON.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StreamEvent<T> {
+    Event(SuccessResponse<T>),
+    /// Ends the stream early (so it's the last line).
+    Failure(FailureResponse),
+    /// Sent after the last event, so clients can tell a finished stream from a dropped
+    /// connection.
+    End,
+}
+
+/// What a streaming route's handler responds with: each of its events as they're ready, then an
+/// `end` line (or a failure, if one comes first).
+pub struct EventStream<T> {
+    events: futures::stream::BoxStream<'static, Result<T, FailureResponse>>,
+}
+
+impl<T: Serialize + Send + 'static> EventStream<T> {
+    pub fn new(events: impl futures::Stream<Item = T> + Send + 'static) -> Self {
+        Self::try_new(futures::StreamExt::map(events, Ok))
+    }
+
+    /// Like `new`, but the first `Err` is sent as a failure, which ends the stream.
+    pub fn try_new(
+        events: impl futures::Stream<Item = Result<T, FailureResponse>> + Send + 'static,
+    ) -> Self {
+        Self {
+            events: futures::StreamExt::boxed(events),
+        }
+    }
+
+    /// A stream that fails before sending any events.
+    pub fn failure(status: isize, message: &str) -> Self {
+        Self::try_new(futures::stream::iter([Err(FailureResponse {
+            status,
+            message: message.to_string(),
+        })]))
+    }
+
+    /// A 422 failure listing every constraint an input broke.
+    pub fn invalid(errors: &[ValidationError]) -> Self {
+        let message = errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join("; ");
+        Self::failure(422, &message)
+    }
+}
+
+impl<T: Serialize + Send + 'static> axum::response::IntoResponse for EventStream<T> {
+    fn into_response(self) -> axum::response::Response {
+        let lines = futures::stream::unfold(Some(self.events), |events| async move {
+            let mut events = events?;
+            let (line, events) = match futures::StreamExt::next(&mut events).await {
+                Some(Ok(value)) => (StreamEvent::Event(SuccessResponse { value }), Some(events)),
+                Some(Err(failure)) => (StreamEvent::Failure(failure), None),
+                None => (StreamEvent::End, None),
+            };
+            let line = serde_json::to_string(&line).unwrap_or_else(|error| {
+                let failure = StreamEvent::<()>::Failure(FailureResponse {
+                    status: 500,
+                    message: error.to_string(),
+                });
+                serde_json::to_string(&failure).unwrap_or_default()
+            });
+            Some((
+                Ok::<String, std::convert::Infallible>(line + "\n"),
+                events,
+            ))
+        });
+
+        (
+            [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+            axum::body::Body::from_stream(lines),
+        )
+            .into_response()
+    }
+}
+
+/// Serde helpers for `Bytes` fields, which are sent as base64 strings.
+pub mod fen_base64 {
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        STANDARD
+            .decode(String::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+
+    pub mod option {
+        use base64::{engine::general_purpose::STANDARD, Engine as _};
+        use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+        #[allow(clippy::ref_option)]
+        pub fn serialize<S: Serializer>(
+            bytes: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match bytes {
+                Some(bytes) => serializer.serialize_some(&STANDARD.encode(bytes)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|encoded| STANDARD.decode(encoded).map_err(D::Error::custom))
+                .transpose()
+        }
+    }
+}
@@ -0,0 +1,33 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Input {
+    pub item_id: Uuid,
+    pub new_name: String,
+    pub dry_run: Option<bool>,
+    pub x_request_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct PathParams {
+    pub item_id: Uuid,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryParams {
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    pub new_name: String,
+}
+
+pub type Output = String;
\ No newline at end of file
@@ -0,0 +1,13 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+use serde::{Deserialize, Serialize};
+
+pub type Input = String;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum Error {
+    Taken,
+    TooShort(isize),
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+// Created by Fen v0.5.3 at 06:31:46 on 2026-10-18
+// Do not manually modify this file as it is automatically generated
+
+pub type Input = String;
+
+pub type Output = isize;
\ No newline at end of file